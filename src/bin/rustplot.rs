@@ -0,0 +1,319 @@
+//! # rustplot
+//! Command-line tool for plotting delimited data from a file or stdin.
+//!
+//! ```text
+//! cat data.csv | rustplot scatter --x 1 --y 3 --size 80x20
+//! rustplot region --expr "x^2 + y^2 < 1" --domain -1.5,1.5 --range -1.5,1.5
+//! ```
+//!
+//! Run `rustplot --help` for all subcommands and options.
+
+use std::{
+    env,
+    fs::read_to_string,
+    io::{stdin, stdout, ErrorKind, Read, Write},
+    process::exit,
+};
+
+use cgrustplot::{
+    helper::{
+        data::{data_reader, Data},
+        error::PlotError,
+        expression::parse_expression,
+    },
+    plots::{
+        array_plot::array_plot,
+        function_plot::function_plot,
+        line_plot::line_plot,
+        region_plot::region_plot,
+        scatter_plot::scatter_plot,
+        traits::Plot,
+    },
+};
+
+const USAGE: &str = "\
+Plots delimited data from a file or stdin in the terminal.
+
+Usage: rustplot <COMMAND> [OPTIONS] [FILE]
+
+Reads FILE, or stdin if no FILE is given. Columns are given as a number
+counting from 1, or as a name from the header line.
+
+Commands:
+  scatter    Scatter plot of two columns
+  line       Line plot of two columns
+  array      Heatmap of a table of numbers (alias: heatmap)
+  image      Image plot of an image file (FILE is required)
+  function   Graph of an expression in x
+  region     Region where an expression in x and y is true
+
+Options:
+  -x, --x <COL>           Column of x values [default: 1]
+  -y, --y <COL>           Column of y values [default: 2]
+  -c, --columns <COLS>    Columns of an array plot, e.g. 2,3,5 [default: all]
+  -d, --delimiter <DELIM> Column delimiter, e.g. ',' or 'tab' [default: detected]
+      --header            The first line is a header [default: detected]
+      --no-header         The first line is data
+  -e, --expr <EXPR>       Expression for function and region plots, e.g. 'sin(x)'
+      --domain <MIN,MAX>  Domain of function and region plots
+      --range <MIN,MAX>   Range of region plots
+  -s, --size <WxH>        Size of the plot in characters, e.g. 80x20
+      --fit               Size the plot to fit the terminal
+  -t, --title <TITLE>     Title of the plot
+      --no-axes           Don't draw axes and axes labels
+      --color             Draw images in 24-bit color
+  -o, --output <PATH>     Save to a file instead of printing. Paths ending in
+                          .png, .jpg, .bmp or .gif are rendered as images
+  -h, --help              Print this message
+";
+
+/// Parsed command line options.
+#[derive(Default)]
+struct Options {
+    command: String,
+    file: Option<String>,
+    x: Option<String>,
+    y: Option<String>,
+    columns: Option<Vec<String>>,
+    delimiter: Option<char>,
+    header: Option<bool>,
+    expr: Option<String>,
+    domain: Option<(f64, f64)>,
+    range: Option<(f64, f64)>,
+    size: Option<(u32, u32)>,
+    fit: bool,
+    title: Option<String>,
+    axes: bool,
+    color: bool,
+    output: Option<String>,
+}
+
+fn parse_pair<T: std::str::FromStr>(s: &str, sep: char, name: &str) -> Result<(T, T), String> {
+    let err = || format!("Invalid {name} '{s}'");
+    let (a, b) = s.split_once(sep).ok_or_else(err)?;
+    Ok((a.trim().parse().map_err(|_| err())?, b.trim().parse().map_err(|_| err())?))
+}
+
+fn parse_delimiter(s: &str) -> Result<char, String> {
+    match s {
+        "tab" | "\\t" => Ok('\t'),
+        "space" | "whitespace" => Ok(' '),
+        _ => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(d), None) => Ok(d),
+                _ => Err(format!("Invalid delimiter '{s}'. Expected a single charachter, 'tab', or 'space'")),
+            }
+        }
+    }
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut o = Options {axes: true, ..Default::default()};
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        // Supports both `--opt value` and `--opt=value`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((f, v)) if f.starts_with("--") => (f.to_string(), Some(v.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next()).ok_or_else(|| format!("Missing value for {flag}"));
+
+        match flag.as_str() {
+            "-h" | "--help" => {
+                print!("{USAGE}");
+                exit(0);
+            }
+            "-x" | "--x" => o.x = Some(value()?),
+            "-y" | "--y" => o.y = Some(value()?),
+            "-c" | "--columns" => o.columns = Some(value()?.split(',').map(|c| c.to_string()).collect()),
+            "-d" | "--delimiter" => o.delimiter = Some(parse_delimiter(&value()?)?),
+            "--header" => o.header = Some(true),
+            "--no-header" => o.header = Some(false),
+            "-e" | "--expr" => o.expr = Some(value()?),
+            "--domain" => o.domain = Some(parse_pair(&value()?, ',', "domain")?),
+            "--range" => o.range = Some(parse_pair(&value()?, ',', "range")?),
+            "-s" | "--size" => o.size = Some(parse_pair(&value()?.to_lowercase(), 'x', "size")?),
+            "--fit" => o.fit = true,
+            "-t" | "--title" => o.title = Some(value()?),
+            "--no-axes" => o.axes = false,
+            "--color" => o.color = true,
+            "-o" | "--output" => o.output = Some(value()?),
+            f if f.starts_with('-') && f.len() > 1 && f.parse::<f64>().is_err() => return Err(format!("Unknown option '{f}'")),
+            _ if o.command.is_empty() => o.command = arg,
+            _ if o.file.is_none() => o.file = Some(arg),
+            _ => return Err(format!("Unexpected argument '{arg}'")),
+        }
+    }
+
+    if o.command.is_empty() {
+        return Err("Missing command".to_string());
+    }
+
+    Ok(o)
+}
+
+/// Reads the input file, or stdin.
+fn read_input(file: &Option<String>) -> Result<String, String> {
+    match file {
+        Some(path) => read_to_string(path).map_err(|e| format!("Failed to read '{path}': {e}")),
+        None => {
+            let mut s = String::new();
+            stdin().read_to_string(&mut s).map_err(|e| format!("Failed to read stdin: {e}"))?;
+            Ok(s)
+        }
+    }
+}
+
+/// Reads the input as delimited text.
+fn read_data(o: &Options) -> Result<Data, String> {
+    let mut reader = data_reader();
+    if let Some(d) = o.delimiter {reader.set_delimiter(d);}
+    if let Some(h) = o.header {reader.set_header(h);}
+    Ok(reader.parse(&read_input(&o.file)?))
+}
+
+/// Finds a column given as a number counting from 1, or as a name in the header.
+fn resolve_column(data: &Data, column: &str) -> Result<usize, String> {
+    match column.trim().parse::<usize>() {
+        Ok(n) if n > 0 && n <= data.width() => Ok(n - 1),
+        Ok(n) if n > 0 => Err(format!("Column {n} was selected, but the data has {} columns", data.width())),
+        Ok(_) => Err("Columns are counted from 1".to_string()),
+        Err(_) => data.column_index(column).ok_or_else(|| format!("No column named '{column}'")),
+    }
+}
+
+/// Prints the plot, or saves it as text or an image depending on the output path.
+fn output(plot: &dyn Plot, path: &Option<String>) -> Result<(), PlotError> {
+    let Some(path) = path else {
+        // Output piped into e.g. `head` may be closed early, which isn't an error
+        return match writeln!(stdout(), "{}", plot.as_string()) {
+            Err(e) if e.kind() != ErrorKind::BrokenPipe => Err(PlotError::Io {path: "<stdout>".to_string(), source: e}),
+            _ => Ok(()),
+        };
+    };
+
+    let is_image = [".png", ".jpg", ".jpeg", ".bmp", ".gif"].iter().any(|ext| path.to_lowercase().ends_with(ext));
+
+    if !is_image {
+        return plot.try_save(path);
+    }
+
+    #[cfg(feature = "image-export")]
+    return plot.as_image().try_save(path);
+
+    #[cfg(not(feature = "image-export"))]
+    Err(PlotError::InvalidInput("Saving images requires the `image-export` feature".to_string()))
+}
+
+#[cfg(feature = "image-export")]
+fn image(o: &Options) -> Result<(), String> {
+    use cgrustplot::plots::image_plot::{image_plot, RenderMode};
+    use cgrustplot::helper::terminal::{fit_size, terminal_size};
+    use image::imageops::FilterType;
+
+    let path = o.file.as_ref().ok_or("The image command needs an image FILE")?;
+    let img = image::open(path).map_err(|e| format!("Failed to open '{path}': {e}"))?;
+
+    // Color modes draw two pixels per charachter vertically
+    let (w, h) = o.fit.then(|| terminal_size().map(|t| fit_size(t, false, false))).flatten().or(o.size).unwrap_or((60, 30));
+    let img = img.resize(w, h * if o.color {2} else {1}, FilterType::Triangle).to_rgb8();
+
+    // Charachters are about twice as tall as they are wide, so every other row is dropped in monochrome
+    let rows: Vec<Vec<(u8, u8, u8)>> = img
+        .rows()
+        .step_by(if o.color {1} else {2})
+        .map(|r| r.map(|p| (p[0], p[1], p[2])).collect())
+        .collect();
+
+    // Image plots are titled with their path
+    let mut plot = image_plot(rows);
+    plot.set_abs_path(path);
+    if o.color {
+        plot.set_render_mode(RenderMode::TrueColor);
+    }
+
+    output(&plot, &o.output).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "image-export"))]
+fn image(_o: &Options) -> Result<(), String> {
+    Err("The image command requires the `image-export` feature".to_string())
+}
+
+fn run(o: Options) -> Result<(), String> {
+    let title = o.title.as_deref();
+
+    match o.command.as_str() {
+        "scatter" | "line" => {
+            let data = read_data(&o)?;
+            let x = resolve_column(&data, o.x.as_deref().unwrap_or("1"))?;
+            let y = resolve_column(&data, o.y.as_deref().unwrap_or("2"))?;
+            let points = data.points(x, y).map_err(|e| e.to_string())?;
+
+            if o.command == "scatter" {
+                let mut plot = scatter_plot(&points);
+                plot.set_axes(o.axes).set_fit_terminal(o.fit);
+                if let Some(s) = o.size {plot.set_size(s);}
+                if let Some(t) = title {plot.set_title(t);}
+                output(&plot, &o.output)
+            } else {
+                let mut plot = line_plot(&points);
+                plot.set_axes(o.axes).set_fit_terminal(o.fit);
+                if let Some(s) = o.size {plot.set_size(s);}
+                if let Some(t) = title {plot.set_title(t);}
+                output(&plot, &o.output)
+            }.map_err(|e| e.to_string())
+        }
+        "array" | "heatmap" => {
+            let data = read_data(&o)?;
+            let table = match &o.columns {
+                Some(cols) => data.select(&cols.iter().map(|c| resolve_column(&data, c)).collect::<Result<Vec<usize>, String>>()?),
+                None => data.matrix(),
+            }.map_err(|e| e.to_string())?;
+
+            let mut plot = array_plot(&table);
+            plot.set_axes(o.axes);
+            if let Some(t) = title {plot.set_title(t);}
+            output(&plot, &o.output).map_err(|e| e.to_string())
+        }
+        "image" => image(&o),
+        "function" => {
+            let expr = parse_expression(o.expr.as_deref().ok_or("The function command needs --expr")?, &["x"]).map_err(|e| e.to_string())?;
+            let f = |x: f64| expr.eval(&[x]);
+
+            let mut plot = function_plot(&f);
+            plot.set_axes(o.axes).set_fit_terminal(o.fit);
+            if let Some(d) = o.domain {plot.set_domain(d);}
+            if let Some(r) = o.range {plot.set_range(r);}
+            if let Some(s) = o.size {plot.set_size(s);}
+            if let Some(t) = title {plot.set_title(t);}
+            output(&plot, &o.output).map_err(|e| e.to_string())
+        }
+        "region" => {
+            let expr = parse_expression(o.expr.as_deref().ok_or("The region command needs --expr")?, &["x", "y"]).map_err(|e| e.to_string())?;
+            let pred = |x: f64, y: f64| expr.eval_bool(&[x, y]);
+
+            let mut plot = region_plot(&pred);
+            plot.set_axes(o.axes).set_fit_terminal(o.fit);
+            if let Some(s) = o.size {plot.set_size(s);}
+            if o.domain.is_some() || o.range.is_some() {
+                plot.set_domain_and_range((o.domain.unwrap_or((-1., 1.)), o.range.unwrap_or((-1., 1.))));
+            }
+            if let Some(t) = title {plot.set_title(t);}
+            output(&plot, &o.output).map_err(|e| e.to_string())
+        }
+        c => Err(format!("Unknown command '{c}'")),
+    }
+}
+
+fn main() {
+    let result = parse_args(env::args().skip(1)).and_then(run);
+
+    if let Err(e) = result {
+        eprintln!("rustplot: {e}");
+        eprintln!("Run `rustplot --help` for usage.");
+        exit(1);
+    }
+}
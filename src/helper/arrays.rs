@@ -1,7 +1,10 @@
 //! Helper file for working with, modifying, and manipulating arrays, tables, and vectors
 
 use std::collections::HashSet;
-use crate::helper::math::{non_nan_type::*, subdivide};
+use crate::helper::{
+    error::PlotError,
+    math::{non_nan_type::*, subdivide},
+};
 use num::ToPrimitive;
 
 /// Takes a list of table indexes and returns the counts for each cell of the table.
@@ -27,21 +30,32 @@ use num::ToPrimitive;
 /// 
 /// Nan-valued input indices are ignored.
 /// 
-pub fn table_indices_to_counts<T: ToPrimitive + PartialEq>(points: &Vec<(T, T)>, range: ((f64, f64), (f64, f64)), size: (u32, u32)) -> Vec<Vec<u32>> {
-    let pts: Vec<&(T, T)> = points.iter().filter(|i| i.0 == i.0 && i.1 == i.1).collect();
-
-    let ptsxf64: Vec<f64> = pts.iter().map(|i| i.0.to_f64().unwrap()).collect();
-    let ptsyf64: Vec<f64> = pts.iter().map(|i| i.1.to_f64().unwrap()).collect();
+pub fn table_indices_to_counts<T: ToPrimitive + PartialEq>(points: &[(T, T)], range: ((f64, f64), (f64, f64)), size: (u32, u32)) -> Vec<Vec<u32>> {
+    point_counts(
+        points.iter().filter(|i| i.0 == i.0 && i.1 == i.1).map(|i| (i.0.to_f64().unwrap(), i.1.to_f64().unwrap())),
+        range,
+        size
+    )
+}
 
-    let ptsxbin: Vec<u32> = bin_vec_bounded(&ptsxf64, size.0, range.0);
-    let ptsybin: Vec<u32> = bin_vec_bounded(&ptsyf64, size.1, range.1);
+/// Same as `table_indices_to_counts`, but for any iterator of float points.
+pub(crate) fn point_counts(points: impl Iterator<Item = (f64, f64)>, range: ((f64, f64), (f64, f64)), size: (u32, u32)) -> Vec<Vec<u32>> {
+    let x_subdivisions = subdivide(range.0.0, range.0.1, size.0 + 1);
+    let y_subdivisions = subdivide(range.1.0, range.1.1, size.1 + 1);
 
     let mut o: Vec<Vec<u32>> = vec![vec![0u32; size.0 as usize]; size.1 as usize];
-    (0..pts.len()).for_each(|i| o[ptsybin[i] as usize][ptsxbin[i] as usize] += 1);
-    
+    points
+    .filter(|p| !p.0.is_nan() && !p.1.is_nan())
+    .for_each(|(x, y)| o[bin_index(&y_subdivisions, y) as usize][bin_index(&x_subdivisions, x) as usize] += 1);
+
     o
 }
 
+/// Converts points to floats, removing any NaN points.
+pub(crate) fn points_to_f64<T: ToPrimitive + PartialEq>(points: impl Iterator<Item = (T, T)>) -> impl Iterator<Item = (f64, f64)> {
+    points.filter(|i| i.0 == i.0 && i.1 == i.1).map(|i| (i.0.to_f64().unwrap(), i.1.to_f64().unwrap()))
+}
+
 /// Transposes a table (2D-array).
 /// 
 /// The output values reference the values of the original table.
@@ -58,17 +72,39 @@ pub fn table_indices_to_counts<T: ToPrimitive + PartialEq>(points: &Vec<(T, T)>,
 /// # Notes
 /// Input array must be rectangular and must have a non-zero size.
 /// 
-pub fn transpose_table<T>(arr: &Vec<Vec<T>>) -> Vec<Vec<&T>> {
-    // Rectangular Array
-    assert!(arr.iter().all(|i| i.len() == arr[0].len()));
+pub fn transpose_table<T>(arr: &[Vec<T>]) -> Vec<Vec<&T>> {
+    match try_transpose_table(arr) {
+        Ok(o) => o,
+        Err(e) => panic!("{e}"),
+    }
+}
 
-    (0..arr[0].len())
-    .map(|j|
-        (0..arr.len())
-        .map(|i|
-            &arr[i][j]
+/// Transposes a table (2D-array), returning an error if it isn't rectangular or is empty.
+/// 
+/// # Example
+///
+/// ```
+/// use cgrustplot::helper::arrays::try_transpose_table;
+/// assert!(try_transpose_table(&vec![vec![1, 2], vec![3]]).is_err());
+/// ```
+pub fn try_transpose_table<T>(arr: &[Vec<T>]) -> Result<Vec<Vec<&T>>, PlotError> {
+    if arr.is_empty() {
+        return Err(PlotError::InvalidInput("Cannot transpose an empty table".to_string()));
+    }
+
+    if !arr.iter().all(|i| i.len() == arr[0].len()) {
+        return Err(PlotError::InvalidInput("Cannot transpose a table which isn't rectangular".to_string()));
+    }
+
+    Ok(
+        (0..arr[0].len())
+        .map(|j|
+            (0..arr.len())
+            .map(|i|
+                &arr[i][j]
+            ).collect()
         ).collect()
-    ).collect()
+    )
 }
 
 /// Finds distinct elements in a table (2D-array).
@@ -83,24 +119,18 @@ pub fn transpose_table<T>(arr: &Vec<Vec<T>>) -> Vec<Vec<&T>> {
 /// # Example
 ///
 /// ```text
-/// let result = distinct_in_table_non_nan(&vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]]);
+/// let result = distinct_in_table_non_nan(vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]].iter().flatten());
 /// assert_eq!(result, (1..10).map(|i| NonNanWrapper::from(i)).collect());
 /// ```
 /// 
 /// # Notes
 /// 
 /// Nan-values are removed and ignored.
-pub(crate) fn distinct_in_table_non_nan<T: PartialOrd + Copy>(arr: &Vec<Vec<T>>) -> Vec<NonNanWrapper<T>> {
-    arr
-    .iter()
-    .flat_map(|i| {
-        let mut o = i.clone();
-        o.retain(|i| i == i);
-        o.iter()
-        .map(|j|
-            NonNanWrapper::from(*j)
-        ).collect::<Vec<NonNanWrapper<T>>>()
-    }).collect::<HashSet<NonNanWrapper<T>>>()
+pub(crate) fn distinct_in_table_non_nan<'a, T: PartialOrd + Copy + 'a>(values: impl Iterator<Item = &'a T>) -> Vec<NonNanWrapper<T>> {
+    values
+    .filter(|i| i == i)
+    .map(|j| NonNanWrapper::from(*j))
+    .collect::<HashSet<NonNanWrapper<T>>>()
     .into_iter()
     .collect()
 }
@@ -109,18 +139,20 @@ pub(crate) fn distinct_in_table_non_nan<T: PartialOrd + Copy>(arr: &Vec<Vec<T>>)
 /// 
 /// Creates a number of bins, then returns the index of the bin into which each value in the vec first fits.
 /// Ignores NaN values.
-pub(crate) fn bin_vec_bounded(v: &Vec<f64>, bins: u32, range: (f64, f64)) -> Vec<u32> {
+pub(crate) fn bin_vec_bounded(v: &[f64], bins: u32, range: (f64, f64)) -> Vec<u32> {
     // Bounds for the bins
     let subdivisions = subdivide(range.0, range.1, (bins + 1) as u32);
 
-    // Place each value into the index of the first bin it fits in
-    v.into_iter().map(|j| {
-        match (0..subdivisions.len() - 1).into_iter()
-            .position(|i| subdivisions[i] <= *j && *j <= subdivisions[i + 1])
-            {Some(val) => val as u32, None => 0} // 0 case not needed
-    }).collect()
+    v.iter().map(|j| bin_index(&subdivisions, *j)).collect()
+}
+
+/// The index of the first bin a value fits in, given the bounds of each bin.
+fn bin_index(subdivisions: &[f64], v: f64) -> u32 {
+    match (0..subdivisions.len() - 1)
+        .position(|i| subdivisions[i] <= v && v <= subdivisions[i + 1])
+        {Some(val) => val as u32, None => 0} // 0 case not needed
 }
-pub(crate) fn bin_arr_bounded(arr: &Vec<Vec<f64>>, bins: u32, range: (f64, f64)) -> Vec<Vec<u32>> {
+pub(crate) fn bin_arr_bounded(arr: &[Vec<f64>], bins: u32, range: (f64, f64)) -> Vec<Vec<u32>> {
     arr.into_iter().map(|row| bin_vec_bounded(row, bins, range)).collect()
 }
 
@@ -198,7 +230,7 @@ pub fn padded_vec_to<T: Clone>(v: Vec<T>, n: usize, el: T) -> Vec<T> {
 /// 
 /// Input table must be rectangular, but *may* have a size of zero.
 /// 
-pub fn pad_table<T: Clone>(tab: &Vec<Vec<T>>, el: T, padding: ((i32, i32), (i32, i32))) -> Vec<Vec<T>> {
+pub fn pad_table<T: Clone>(tab: &[Vec<T>], el: T, padding: ((i32, i32), (i32, i32))) -> Vec<Vec<T>> {
     let ((left, right), (top, bottom)) = padding;
 
     let height = tab.len() as i32;
@@ -1,14 +1,211 @@
 //! Helper file for adding axes, axes labels, and titles to existing plot strings
 
 use crate::helper::{
-    charset::axes_chars,
+    charset::{axes_chars, grid_chars, legend_chars},
+    color::{Color, split_cells, join_cells, visible_len},
     math::{min_always, max_always},
     arrays::pad_table,
+    scale::Scale,
+    terminal::fit_terminal_size,
+    time::time_ticks,
 };
 
-/// Splits a single \n-seperated string into a table of charachters
-fn string_to_char_table(s: &str) -> Vec<Vec<char>> {
-    s.split('\n').map(|line| line.chars().collect()).collect()
+/// Options for how axes are drawn.
+/// 
+/// # Options
+/// 
+/// * `color` - Color of the axes lines and labels. Default is Color::Default.
+/// * `x_scale` - Scale of the horizontal axis, which the labels are mapped back through. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis, which the labels are mapped back through. Default is Scale::Linear.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis, centered under its tick labels. Default is None.
+/// * `y_label` - Title of the vertical axis, drawn vertically beside it, or above it if the plot is too short. Default is None.
+/// * `grid` - Whether to draw dotted gridlines at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines at, such as 0. for the y axis. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines at, such as 0. for the x axis. Default is none.
+/// * `secondary_range` - Range of a secondary vertical axis, drawn on the right of the plot. Default is None, for no secondary axis.
+/// * `secondary_ticks` - How ticks on the secondary axis are chosen. Default is Ticks::Even.
+/// * `secondary_label` - Title of the secondary axis, drawn like `y_label`. Default is None.
+/// 
+/// Gridlines and reference lines are drawn underneath the data, only filling blank charachters.
+/// 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct AxesConfig {
+    pub color: Color,
+    pub x_scale: Scale,
+    pub y_scale: Scale,
+    pub x_ticks: Ticks,
+    pub y_ticks: Ticks,
+    pub x_label: Option<String>,
+    pub y_label: Option<String>,
+    pub grid: bool,
+    pub x_lines: Vec<f64>,
+    pub y_lines: Vec<f64>,
+    pub secondary_range: Option<(f64, f64)>,
+    pub secondary_ticks: Ticks,
+    pub secondary_label: Option<String>,
+}
+
+impl AxesConfig {
+    /// The matplotlib calls which add the axis labels, each on a new line.
+    #[cfg_attr(not(feature = "matplotlib"), allow(dead_code))]
+    pub(crate) fn pyplot_labels(&self) -> String {
+        let mut o = String::new();
+        if let Some(l) = &self.x_label {o.push_str(&format!("\nplt.xlabel({l:?})"))}
+        if let Some(l) = &self.y_label {o.push_str(&format!("\nplt.ylabel({l:?})"))}
+        o
+    }
+}
+
+/// Title, axes, and size options shared by the plot builders, as they were set.
+/// 
+/// Each builder holds one of these, with its setters generated by `plots::traits::impl_plot_options!`.
+/// Unset options are filled in with their defaults when the plot is built.
+/// 
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AxesOptions<'a> {
+    pub(crate) title: Option<&'a str>,
+    pub(crate) caption: Option<&'a str>,
+    pub(crate) axes: Option<bool>,
+    pub(crate) color: Option<Color>,
+    pub(crate) x_ticks: Option<Ticks>,
+    pub(crate) y_ticks: Option<Ticks>,
+    pub(crate) x_label: Option<String>,
+    pub(crate) y_label: Option<String>,
+    pub(crate) grid: Option<bool>,
+    pub(crate) x_lines: Option<Vec<f64>>,
+    pub(crate) y_lines: Option<Vec<f64>>,
+    pub(crate) x_scale: Option<Scale>,
+    pub(crate) y_scale: Option<Scale>,
+    pub(crate) size: Option<(u32, u32)>,
+    pub(crate) fit_terminal: Option<bool>,
+}
+
+impl<'a> AxesOptions<'a> {
+    /// Whether to display axes. Default is true.
+    pub(crate) fn axes(&self) -> bool {
+        self.axes.unwrap_or(true)
+    }
+
+    /// The (x, y) scales. Default is Scale::Linear.
+    pub(crate) fn scales(&self) -> (Scale, Scale) {
+        (self.x_scale.unwrap_or_default(), self.y_scale.unwrap_or_default())
+    }
+
+    /// The size of the plot: the size which fits the current terminal if `fit_terminal` is set,
+    /// then the set `size`, then the builder's default.
+    pub(crate) fn size(&self, default: (u32, u32)) -> (u32, u32) {
+        self.fit_terminal
+            .unwrap_or(false)
+            .then(|| fit_terminal_size(self.axes(), self.title.is_some(), self.caption, (self.x_label.as_deref(), self.y_label.as_deref())))
+            .flatten()
+            .unwrap_or(self.size.unwrap_or(default))
+    }
+
+    /// The options for drawing the axes, with defaults for anything unset.
+    pub(crate) fn build_axes_config(&self) -> AxesConfig {
+        let (x_scale, y_scale) = self.scales();
+
+        AxesConfig {
+            color: self.color.unwrap_or_default(),
+            x_scale,
+            y_scale,
+            x_ticks: self.x_ticks.clone().unwrap_or_default(),
+            y_ticks: self.y_ticks.clone().unwrap_or_default(),
+            x_label: self.x_label.clone(),
+            y_label: self.y_label.clone(),
+            grid: self.grid.unwrap_or(false),
+            x_lines: self.x_lines.clone().unwrap_or_default(),
+            y_lines: self.y_lines.clone().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+}
+
+/// How the ticks along an axis are chosen.
+/// 
+/// * `Even` - Ticks are an even number of charachters apart, labelled with the value at each one.
+/// * `Nice` - Ticks are at round values, in steps of 1, 2, or 5 times a power of 10, on the charachters nearest to those values.
+/// * `Custom` - Ticks are at the given (value, label) pairs. Values outside of the plot are left out.
+/// * `Categories` - Each label is a category, with the i-th label at the value i. See `categorical_points`.
+/// * `Time` - Values are Unix timestamps, with ticks on whole seconds, minutes, hours, days, months, or years,
+///   labelled like `14:05`, `Mar 03`, or `2026`. See `helper::time`.
+/// 
+/// On a scaled axis, `Nice` ticks are round in scaled space, so a log axis gets ticks at powers of 10.
+/// 
+/// Custom and category labels are truncated past 12 charachters. Labels on the horizontal axis which don't fit
+/// before the next tick are drawn vertically instead, keeping only every few ticks if they are too close together.
+/// 
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Ticks {
+    #[default]
+    Even,
+    Nice,
+    Custom(Vec<(f64, String)>),
+    Categories(Vec<String>),
+    Time,
+}
+
+impl Ticks {
+    /// Ticks at the given (value, label) pairs.
+    /// 
+    /// # Example
+    /// ```
+    /// use std::f64::consts::PI;
+    /// use cgrustplot::helper::axes::Ticks;
+    /// 
+    /// let ticks = Ticks::custom([(0., "0"), (PI, "π"), (2. * PI, "2π")]);
+    /// assert_eq!(ticks, Ticks::Custom(vec![(0., "0".to_string()), (PI, "π".to_string()), (2. * PI, "2π".to_string())]));
+    /// ```
+    pub fn custom<S: ToString>(ticks: impl IntoIterator<Item = (f64, S)>) -> Ticks {
+        Ticks::Custom(ticks.into_iter().map(|(v, s)| (v, s.to_string())).collect())
+    }
+
+    /// Ticks labelling the values 0, 1, 2, ... with each category.
+    pub fn categories<S: ToString>(labels: impl IntoIterator<Item = S>) -> Ticks {
+        Ticks::Categories(labels.into_iter().map(|s| s.to_string()).collect())
+    }
+}
+
+/// Converts string-keyed data to points, with each distinct key at its own x value, and ticks labelling them.
+/// 
+/// Keys are numbered in the order they first appear, so repeated keys share an x value.
+/// 
+/// # Example
+/// ```
+/// use cgrustplot::helper::axes::{categorical_points, Ticks};
+/// 
+/// let data = [("Mon", 3.), ("Tue", 5.), ("Mon", 4.)];
+/// let (points, ticks) = categorical_points(&data);
+/// 
+/// assert_eq!(points, vec![(0., 3.), (1., 5.), (0., 4.)]);
+/// assert_eq!(ticks, Ticks::categories(["Mon", "Tue"]));
+/// ```
+pub fn categorical_points<S: AsRef<str>>(data: &[(S, f64)]) -> (Vec<(f64, f64)>, Ticks) {
+    let mut keys: Vec<&str> = Vec::new();
+
+    let points = data.iter().map(|(k, y)| {
+        let i = keys.iter().position(|c| *c == k.as_ref()).unwrap_or_else(|| {
+            keys.push(k.as_ref());
+            keys.len() - 1
+        });
+        (i as f64, *y)
+    }).collect();
+
+    (points, Ticks::categories(keys))
+}
+
+/// Splits a single \n-seperated string into a table of visible charachters.
+/// 
+/// Each cell keeps any color escape codes which apply to it.
+fn string_to_cell_table(s: &str) -> Vec<Vec<String>> {
+    s.split('\n').map(split_cells).collect()
+}
+
+/// Joins a table of cells back into a single \n-seperated string.
+fn cell_table_to_string(tab: &[Vec<String>]) -> String {
+    tab.iter().map(|r| join_cells(r)).collect::<Vec<String>>().join("\n")
 }
 
 /// Formats a list of numbers to be displayed with the specified number of digits.
@@ -117,14 +314,16 @@ fn kfy(n: f64, sep_amount: f64) -> f64 {
 /// Generates the numbers and labels for a single axis.
 /// 
 /// ll value is Some for the vertical axis, representing the spacing between numbers.
-fn single_axes_labels(n: usize, range: (f64, f64), ll: Option<usize>) -> (usize, Vec<String>) {
+/// 
+/// The range is in scaled space, and labels show the values mapped back through the scale.
+fn single_axes_labels(n: usize, range: (f64, f64), ll: Option<usize>, scale: Scale) -> (usize, Vec<String>) {
     // number of ticks (k) and seperation amount (s)
     let mut k = if ll.is_none() {kf(n as f64, 4., 8., 2.)} else {kfy(n as f64, 2.)} as usize;
     let mut s = ((n - 1) / k) + 1 - if ll.is_none() {1} else {0};
 
     for _ in 0..s {
         let nums_c: Vec<f64> = (0..k).map(|i| i as f64 * s as f64 + 0.5).collect();
-        let nums_u: Vec<f64> = nums_c.iter().map(|x| range.0 + x * (range.1 - range.0) / n as f64).collect();
+        let nums_u: Vec<f64> = nums_c.iter().map(|x| scale.inverse(range.0 + x * (range.1 - range.0) / n as f64)).collect();
 
         // uses ll or s depending on vertical vs horizontal
         let labs = format_nums(&nums_u, ll.unwrap_or(s - 1));
@@ -140,65 +339,395 @@ fn single_axes_labels(n: usize, range: (f64, f64), ll: Option<usize>) -> (usize,
     return (s, vec!["err".to_string()]);
 }
 
+/// Smallest step of 1, 2, or 5 times a power of 10 which is at least x.
+fn nice_step(x: f64) -> f64 {
+    let p = 10f64.powf(x.log10().floor());
+    [1., 2., 5.].into_iter().map(|m| m * p).find(|s| *s >= x * (1. - 1e-9)).unwrap_or(10. * p)
+}
+
+/// Formats round numbers with the fewest digits which show them exactly, in decimal or scientific notation.
+/// 
+/// Returns None if they can't be shown exactly in max_len charachters.
+fn format_round_nums(nums: &[f64], max_len: usize) -> Option<Vec<String>> {
+    let exact = |o: &Vec<String>| o.iter().zip(nums).all(|(s, x)| s.parse::<f64>().is_ok_and(|v| (v - x).abs() <= 1e-9 * x.abs()));
+    let fits = |o: &Vec<String>| o.iter().all(|s| s.len() <= max_len);
+
+    // Adding 0 turns -0 into 0
+    let decimal = (0..=6).map(|d| nums.iter().map(|x| format!("{:.d$}", x + 0.)).collect()).find(exact);
+    if let Some(o) = decimal.filter(fits) {
+        return Some(o);
+    }
+
+    (0..=3)
+        .map(|d| nums.iter().map(|x| if *x == 0. {"0".to_string()} else {format!("{x:.d$E}")}).collect())
+        .find(exact)
+        .filter(fits)
+}
+
+/// Generates ticks at round values for a single axis, on the charachters nearest to them.
+/// 
+/// Returns the (position, label) of each tick, and how far the labels extend past the end of the axis.
+/// Returns None if no round values fit on the axis.
+fn nice_axes_labels(n: usize, range: (f64, f64), ll: Option<usize>, scale: Scale) -> Option<(Vec<(usize, String)>, usize)> {
+    let width = range.1 - range.0;
+    if n < 2 || !(width.is_finite() && width > 0.) {
+        return None;
+    }
+
+    // Same minimum seperation as even ticks
+    let min_sep = if ll.is_none() {4} else {2};
+    let mut step = nice_step(min_sep as f64 * width / n as f64);
+
+    // Larger steps can only have a single tick, or none
+    while step <= 10. * width {
+        let (first, last) = ((range.0 / step).ceil() as i64, (range.1 / step).floor() as i64);
+
+        // Each value goes on the charachter it falls in
+        let values: Vec<f64> = (first..=last).map(|k| k as f64 * step).collect();
+        let positions: Vec<usize> = values.iter().map(|v| (((v - range.0) / width * n as f64) as usize).min(n - 1)).collect();
+
+        let sep = positions.windows(2).map(|w| w[1] - w[0]).min().unwrap_or(n);
+
+        if !positions.is_empty() && sep + 1 >= min_sep {
+            let nums: Vec<f64> = values.iter().map(|v| scale.inverse(*v)).collect();
+
+            if let Some(labels) = format_round_nums(&nums, ll.unwrap_or(sep.saturating_sub(1))) {
+                let overhang = positions.iter().zip(labels.iter()).map(|(p, l)| p + l.len()).max().unwrap_or(0).saturating_sub(n) + 1;
+                return Some((positions.into_iter().zip(labels).collect(), overhang));
+            }
+        }
+
+        step = nice_step(step * 1.01);
+    }
+
+    None
+}
+
+/// Most charachters a custom or category label can have before it is truncated.
+const MAX_LABEL_LENGTH: usize = 12;
+
+/// Shortens a label to at most len charachters, ending with '…' if anything was cut.
+fn truncate_label(s: &str, len: usize) -> String {
+    if s.chars().count() <= len {
+        s.to_string()
+    } else if len == 0 {
+        String::new()
+    } else {
+        s.chars().take(len - 1).chain(['…']).collect()
+    }
+}
+
+/// The charachter a value falls in, on a single axis n charachters long. None if it is outside of the range.
+fn value_cell(n: usize, range: (f64, f64), scale: Scale, v: f64) -> Option<usize> {
+    let x = (scale.forward(v) - range.0) / (range.1 - range.0) * n as f64;
+    (0. ..=n as f64).contains(&x).then(|| (x as usize).min(n.saturating_sub(1)))
+}
+
+/// Places labels at given values on a single axis, on the charachters those values fall in.
+/// 
+/// Values outside of the range, or on the same charachter as an earlier value, are left out.
+fn labelled_cells(n: usize, range: (f64, f64), scale: Scale, ticks: &[(f64, String)]) -> (Vec<(usize, String)>, usize) {
+    let mut o: Vec<(usize, String)> = Vec::new();
+
+    for (v, label) in ticks {
+        if let Some(pos) = value_cell(n, range, scale, *v) {
+            if o.iter().all(|t| t.0 != pos) {
+                o.push((pos, truncate_label(label, MAX_LABEL_LENGTH)));
+            }
+        }
+    }
+
+    o.sort_by_key(|t| t.0);
+
+    let overhang = o.iter().map(|(p, l)| p + l.chars().count()).max().unwrap_or(0).saturating_sub(n) + 1;
+    (o, overhang)
+}
+
+/// Fits labels on the horizontal axis into the space before the next tick.
+/// 
+/// If any don't fit, only every few ticks are kept so that there is a free column between each one,
+/// and their labels are drawn vertically. Returns whether the labels should be drawn vertically.
+fn fit_x_labels(ticks: &mut Vec<(usize, String)>) -> bool {
+    // The last label can extend past the end of the axis
+    let room: Vec<usize> = ticks.windows(2).map(|w| w[1].0 - w[0].0 - 1).chain([usize::MAX]).collect();
+
+    if ticks.iter().zip(room.iter()).all(|(t, r)| t.1.chars().count() <= *r) {
+        return false;
+    }
+
+    let every = (1..=ticks.len())
+        .find(|k| ticks.iter().step_by(*k).zip(ticks.iter().step_by(*k).skip(1)).all(|(a, b)| b.0 - a.0 >= 2))
+        .unwrap_or(1);
+
+    *ticks = ticks.iter().step_by(every).cloned().collect();
+    true
+}
+
+/// The (position, label) of each tick on an axis n charachters long, and how far the labels extend past the end of the axis.
+/// 
+/// Positions are charachter indices, from the left of a horizontal axis or the bottom of a vertical one.
+fn tick_cells(n: usize, range: (f64, f64), vertical: bool, scale: Scale, ticks: &Ticks) -> (Vec<(usize, String)>, usize) {
+    let ll = vertical.then_some(Y_LABEL_LENGTH);
+
+    let even = || {
+        let (sep, labels) = single_axes_labels(n, range, ll, scale);
+        (labels.into_iter().enumerate().map(|(i, label)| (i * sep, label)).collect(), sep)
+    };
+
+    match ticks {
+        Ticks::Even => even(),
+        Ticks::Nice => nice_axes_labels(n, range, ll, scale).unwrap_or_else(even),
+        Ticks::Custom(t) => labelled_cells(n, range, scale, t),
+        Ticks::Categories(c) => labelled_cells(n, range, scale, &c.iter().enumerate().map(|(i, l)| (i as f64, l.clone())).collect::<Vec<_>>()),
+        Ticks::Time => {
+            let position = |t: f64| ((scale.forward(t) - range.0) / (range.1 - range.0) * n as f64) as usize;
+            let ticks = time_ticks(n, scale.inverse_range(range), vertical, position);
+            if ticks.is_empty() {even()} else {labelled_cells(n, range, scale, &ticks)}
+        }
+    }
+}
+
+/// Number of charachters available for each label on the vertical axis.
+const Y_LABEL_LENGTH: usize = 5;
+
+/// Most charachters the last label on the horizontal axis can extend past the end of the plot.
+const X_LABEL_OVERHANG: usize = 7;
+
+/// The number of (columns, rows) which `add_axes` adds around a plot, at most.
+/// 
+/// Used to size a plot so that it fits in a given space once axes are added.
+pub(crate) fn axes_margin() -> (u32, u32) {
+    ((Y_LABEL_LENGTH + 2 + X_LABEL_OVERHANG) as u32, 2)
+}
+
+/// The number of (columns, rows) which axis titles add around a plot `height` lines tall.
+/// 
+/// A y label takes two columns when drawn vertically, or a row when it is too long and goes above the axis.
+pub(crate) fn labels_margin(x_label: Option<&str>, y_label: Option<&str>, height: usize) -> (u32, u32) {
+    let (cols, rows) = match y_label {
+        Some(l) if l.chars().count() <= height => (2, 0),
+        Some(_) => (0, 1),
+        None => (0, 0),
+    };
+    (cols, rows + x_label.is_some() as u32)
+}
+
+/// The (value, label) of each tick on a single axis, for a plot which is n charachters long along that axis.
+/// 
+/// These are the same ticks as `add_axes` draws, for outputs which draw their own axes.
+/// Each tick is at the center of the charachter it labels, in scaled space, while its label is the original value.
+pub(crate) fn axis_ticks(n: usize, range: (f64, f64), vertical: bool, scale: Scale, ticks: &Ticks) -> Vec<(f64, String)> {
+    if n == 0 {
+        return Vec::new();
+    }
+
+    tick_cells(n, range, vertical, scale, ticks).0
+        .into_iter()
+        .map(|(pos, label)| (range.0 + (pos as f64 + 0.5) * (range.1 - range.0) / n as f64, label))
+        .collect()
+}
+
+/// The (position, label) of each tick on an axis.
+type TickCells = Vec<(usize, String)>;
+
+/// The ticks which `add_axes` draws on a plot of the given (width, height).
+/// 
+/// Returns the horizontal ticks, how far their labels extend past the plot, whether those labels are drawn vertically,
+/// and the vertical ticks.
+fn plot_ticks(size: (usize, usize), range: ((f64, f64), (f64, f64)), config: &AxesConfig) -> (TickCells, usize, bool, TickCells) {
+    let (mut x_ticks, x_spacing) = tick_cells(size.0, range.0, false, config.x_scale, &config.x_ticks);
+    let (y_ticks, _) = tick_cells(size.1, range.1, true, config.y_scale, &config.y_ticks);
+    let vertical = fit_x_labels(&mut x_ticks);
+
+    (x_ticks, x_spacing, vertical, y_ticks)
+}
+
+/// Draws gridlines at the ticks, and reference lines at the values set in the config, into the blank charachters of a plot.
+fn add_background_lines(s: &str, range: ((f64, f64), (f64, f64)), config: &AxesConfig) -> String {
+    if !config.grid && config.x_lines.is_empty() && config.y_lines.is_empty() {
+        return s.to_string();
+    }
+
+    let mut tab = string_to_cell_table(s);
+    let height = tab.len();
+    let width = if height > 0 {tab[0].len()} else {0};
+    let paint = |c: char| config.color.paint(&c.to_string());
+
+    // Only charachters which started out blank are drawn over, so the lines stay underneath the data
+    let blank: Vec<Vec<bool>> = tab.iter().map(|r| r.iter().map(|c| c == " ").collect()).collect();
+    let draw = |tab: &mut Vec<Vec<String>>, row: usize, col: usize, c: char| {
+        if blank[row][col] {tab[row][col] = paint(c)}
+    };
+
+    if config.grid {
+        let (x_ticks, _, _, y_ticks) = plot_ticks((width, height), range, config);
+        for (pos, _) in y_ticks {
+            (0..width).for_each(|j| draw(&mut tab, height - 1 - pos, j, grid_chars::HORIZONTAL));
+        }
+        for (pos, _) in x_ticks {
+            (0..height).for_each(|i| draw(&mut tab, i, pos, grid_chars::VERTICAL));
+        }
+    }
+
+    let rows: Vec<usize> = config.y_lines.iter().filter_map(|v| value_cell(height, range.1, config.y_scale, *v)).map(|pos| height - 1 - pos).collect();
+    let cols: Vec<usize> = config.x_lines.iter().filter_map(|v| value_cell(width, range.0, config.x_scale, *v)).collect();
+
+    for row in rows.iter() {
+        (0..width).for_each(|j| draw(&mut tab, *row, j, grid_chars::REF_HORIZONTAL));
+    }
+    for col in cols.iter() {
+        (0..height).for_each(|i| draw(&mut tab, i, *col, if rows.contains(&i) {grid_chars::REF_CROSS} else {grid_chars::REF_VERTICAL}));
+    }
+
+    cell_table_to_string(&tab)
+}
+
 /// Adds axes to an input string
+#[allow(dead_code)]
 pub(crate) fn add_axes(s: &str, range: ((f64, f64), (f64, f64))) -> String {
-    let tab = string_to_char_table(s);
+    add_axes_with_config(s, range, &AxesConfig::default())
+}
+
+/// Adds axes to an input string, drawn with the specified options
+pub(crate) fn add_axes_with_config(s: &str, range: ((f64, f64), (f64, f64)), config: &AxesConfig) -> String {
+    let tab = string_to_cell_table(s);
+    let paint = |c: char| config.color.paint(&c.to_string());
 
     let tab_height = tab.len();
     let tab_width = if tab_height > 0 {tab[0].len()} else {0};
     
-    let (x_spacing, x_labels) = single_axes_labels(tab_width as usize, range.0, None);
-    let x_num_labels = x_labels.len();
-
-    let default_y_label_length = 5;
-    let (y_label_sep, y_labels) = single_axes_labels(tab_height as usize, range.1, Some(default_y_label_length));
-    let y_num_labels = y_labels.len();
-    let y_label_len = y_labels.iter().map(|s| s.len()).max().unwrap();
+    let (x_ticks, x_spacing, vertical, y_ticks) = plot_ticks((tab_width, tab_height), range, config);
+    let y_label_len = y_ticks.iter().map(|t| t.1.chars().count()).max().unwrap_or(0);
+
+    // Even labels which run too far past the end of the plot are cut off, as they are often partial.
+    // Other ticks only make labels which fit, so leave room for all of them.
+    let x_spacing = if config.x_ticks == Ticks::Even {x_spacing} else {x_spacing + y_label_len + 1};
+
+    // Vertical labels take a row for each charachter
+    let label_rows = if vertical {x_ticks.iter().map(|t| t.1.chars().count()).max().unwrap_or(1)} else {1};
+
+    // A secondary axis needs room on the right for its labels, and its title if that goes beside it
+    let secondary_ticks = config.secondary_range.map(|r| tick_cells(tab_height, r, true, Scale::Linear, &config.secondary_ticks).0);
+    let secondary_label_len = secondary_ticks.iter().flatten().map(|t| t.1.chars().count()).max().unwrap_or(0);
+    let secondary_title = config.secondary_label.as_ref().filter(|_| secondary_ticks.is_some());
+    let secondary_beside = secondary_title.filter(|l| l.chars().count() <= tab_height);
+    let right_width = match secondary_ticks {
+        Some(_) => 2 + secondary_label_len + if secondary_beside.is_some() {2} else {0},
+        None => 0,
+    };
     
-    let mut o = pad_table(&tab, ' ', ((y_label_len as i32 + 2, x_spacing as i32), (0, 2)));
-    let o_height = tab_height as usize + 2;
-    let o_width = tab_width as usize + x_spacing as usize;
+    let mut o = pad_table(&tab, " ".to_string(), ((y_label_len as i32 + 2, x_spacing.max(right_width) as i32), (0, 1 + label_rows as i32)));
+    let axis_row = tab_height;
+    let o_width = tab_width + x_spacing;
+    let right_axis = tab_width + y_label_len + 2;
 
     // Add in the axes
-    ((y_label_len + 1)..(tab_width + y_label_len + 2)).for_each(|i| o[o_height - 2][i] = axes_chars::HORIZONTAL); // X
-    (0..(o_height - 2)).for_each(|i| o[i][y_label_len + 1] = axes_chars::VERTICAL); // Y
-    o[o_height - 2][y_label_len + 1] = axes_chars::CORNER; // O
+    ((y_label_len + 1)..(tab_width + y_label_len + 2)).for_each(|i| o[axis_row][i] = paint(axes_chars::HORIZONTAL)); // X
+    (0..axis_row).for_each(|i| o[i][y_label_len + 1] = paint(axes_chars::VERTICAL)); // Y
+    o[axis_row][y_label_len + 1] = paint(axes_chars::CORNER); // O
 
-    for i in 0..x_num_labels {
-        let x_pos = (i * x_spacing) as usize + y_label_len + 2;
+    for (pos, label) in x_ticks.iter() {
+        let x_pos = pos + y_label_len + 2;
 
-        o[o_height - 2][x_pos] = axes_chars::CROSS;
+        o[axis_row][x_pos] = paint(axes_chars::CROSS);
 
-        x_labels[i as usize]
+        label
         .chars()
         .enumerate()
         .for_each(|(j, c)|
-            if (i * x_spacing) as usize + j + y_label_len + 1 < o_width {
-                o[o_height - 1][x_pos + j] = c
+            if vertical {
+                o[axis_row + 1 + j][x_pos] = paint(c)
+            } else if pos + j + y_label_len + 1 < o_width {
+                o[axis_row + 1][x_pos + j] = paint(c)
             }
         );
     }
 
-    for i in 0..y_num_labels {
-        let y_pos = o_height - 3 - i * y_label_sep;
+    for (pos, label) in y_ticks.iter() {
+        let y_pos = axis_row - 1 - pos;
 
-        o[y_pos][y_label_len + 1] = axes_chars::CROSS;
+        o[y_pos][y_label_len + 1] = paint(axes_chars::CROSS);
 
-        y_labels[i as usize]
+        label
         .chars()
         .enumerate()
         .for_each(|(j, c)| 
-            o[y_pos][j] = c
+            o[y_pos][j] = paint(c)
         );
     }
 
-    let trailing_spaces = min_always(&o.iter().map(|r| r.iter().rev().take_while(|c: &&char| **c == ' ').count()).collect(), 0);
+    if let Some(ticks) = &secondary_ticks {
+        (0..axis_row).for_each(|i| o[i][right_axis] = paint(axes_chars::VERTICAL));
+        o[axis_row][right_axis] = paint(axes_chars::CORNER_RIGHT);
+
+        for (pos, label) in ticks.iter() {
+            let y_pos = axis_row - 1 - pos;
+
+            o[y_pos][right_axis] = paint(axes_chars::CROSS);
+            label.chars().enumerate().for_each(|(j, c)| o[y_pos][right_axis + 2 + j] = paint(c));
+        }
+
+        // Vertically, centered beside the labels
+        if let Some(label) = secondary_beside {
+            let start = (tab_height - label.chars().count()) / 2;
+            label.chars().enumerate().for_each(|(i, c)| o[start + i][right_axis + 3 + secondary_label_len] = paint(c));
+        }
+    }
+
+    // Axis titles are kept within the width used by the axes, so they don't stop trailing spaces from being trimmed
+    let row_width = o[0].len();
+    let used_width = row_width - min_always(&o.iter().map(|r| r.iter().rev().take_while(|c: &&String| *c == " ").count()).collect(), 0);
+
+    if let Some(label) = &config.x_label {
+        let label = truncate_label(label, used_width);
+        let len = label.chars().count();
+
+        // Centered under the plot
+        let start = (y_label_len + 2 + tab_width.saturating_sub(len) / 2).min(used_width - len);
+        let mut row = vec![" ".to_string(); row_width];
+        label.chars().enumerate().for_each(|(j, c)| row[start + j] = paint(c));
+        o.push(row);
+    }
+
+    // Titles of vertical axes which are taller than the plot go in a row above it, starting above their axis
+    let above: Vec<(usize, &String)> = [(y_label_len + 1, config.y_label.as_ref()), (right_axis, secondary_title)]
+        .into_iter()
+        .filter_map(|(col, label)| label.filter(|l| l.chars().count() > tab_height).map(|l| (col, l)))
+        .collect();
+
+    let top_rows = !above.is_empty() as usize;
+    if !above.is_empty() {
+        let mut row = vec![" ".to_string(); row_width];
+        let mut free = 0;
+
+        for (col, label) in above {
+            let label = truncate_label(label, used_width.saturating_sub(free));
+            let len = label.chars().count();
+            let start = col.min(used_width - len).max(free);
+            label.chars().enumerate().for_each(|(j, c)| row[start + j] = paint(c));
+            free = start + len + 1;
+        }
+
+        o.insert(0, row);
+    }
+
+    if let Some(label) = config.y_label.as_ref().filter(|l| l.chars().count() <= tab_height) {
+        // Vertically, centered beside the plot
+        let len = label.chars().count();
+        let start = (tab_height - len) / 2 + top_rows;
+        let mut chars = label.chars();
+        for (i, r) in o.iter_mut().enumerate() {
+            let c = if (start..start + len).contains(&i) {chars.next().map_or(" ".to_string(), paint)} else {" ".to_string()};
+            r.splice(0..0, [c, " ".to_string()]);
+        }
+    }
+
+    let trailing_spaces = min_always(&o.iter().map(|r| r.iter().rev().take_while(|c: &&String| *c == " ").count()).collect(), 0);
 
     o
     .into_iter()
     .map(|r|
-        r[..r.len() - trailing_spaces]
-        .into_iter().collect()
+        join_cells(&r[..r.len() - trailing_spaces])
     ).collect::<Vec<String>>()
     .join("\n")
 }
@@ -211,6 +740,114 @@ pub(crate) fn add_title(s: &String, title: String) -> String {
     o
 }
 
+/// Where a legend is placed relative to a plot.
+/// 
+/// The corner positions draw the legend over the plot area, while
+/// `Right` and `Below` place it outside of the plot and its axes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LegendPosition {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+    Right,
+    Below,
+}
+
+impl LegendPosition {
+    /// Whether the legend is drawn over the plot area, rather than next to it.
+    pub(crate) fn is_inside(&self) -> bool {
+        !matches!(self, LegendPosition::Right | LegendPosition::Below)
+    }
+}
+
+/// Generates the lines of a boxed legend, with each entry's marker next to its label.
+fn legend_lines(entries: &[(String, String)]) -> Vec<String> {
+    let rows: Vec<String> = entries.iter().map(|(marker, label)| format!("{marker} {label}")).collect();
+    let width = rows.iter().map(|r| visible_len(r)).max().unwrap_or(0);
+
+    let border = legend_chars::HORIZONTAL.to_string().repeat(width);
+
+    let mut o = vec![format!("{}{border}{}", legend_chars::TOP_LEFT, legend_chars::TOP_RIGHT)];
+    o.extend(rows.iter().map(|r|
+        format!("{}{r}{}{}", legend_chars::VERTICAL, " ".repeat(width - visible_len(r)), legend_chars::VERTICAL)
+    ));
+    o.push(format!("{}{border}{}", legend_chars::BOTTOM_LEFT, legend_chars::BOTTOM_RIGHT));
+    o
+}
+
+/// Adds an optional caption, such as a source or footnote, below an input string.
+pub(crate) fn add_opt_caption(s: String, caption: Option<&str>) -> String {
+    match caption {
+        Some(c) => format!("{s}\n{c}"),
+        None => s,
+    }
+}
+
+/// Adds a legend to an input string.
+/// 
+/// # Arguments
+/// 
+/// * `s` - The input plot.
+/// * `entries` - The (marker, label) pair for each series.
+/// * `position` - Where to place the legend.
+/// 
+/// # Notes
+/// 
+/// Legends inside the plot are drawn over the plot's characters, and are cut off if the plot is too small.
+/// 
+pub fn add_legend(s: &str, entries: &[(String, String)], position: LegendPosition) -> String {
+    if entries.is_empty() {
+        return s.to_string();
+    }
+
+    let legend = legend_lines(entries);
+    let legend_width = visible_len(&legend[0]);
+
+    match position {
+        LegendPosition::Below => {
+            format!("{s}\n{}", legend.join("\n"))
+        }
+        LegendPosition::Right => {
+            let lines: Vec<&str> = s.split('\n').collect();
+            let width = lines.iter().map(|l| visible_len(l)).max().unwrap_or(0);
+
+            (0..std::cmp::max(lines.len(), legend.len())).map(|i| {
+                let line = lines.get(i).copied().unwrap_or("");
+                match legend.get(i) {
+                    Some(l) => format!("{line}{}  {l}", " ".repeat(width - visible_len(line))),
+                    None => line.to_string(),
+                }
+            }).collect::<Vec<String>>()
+            .join("\n")
+        }
+        _ => {
+            let mut tab = string_to_cell_table(s);
+            let height = tab.len();
+            let width = tab.iter().map(|r| r.len()).max().unwrap_or(0);
+
+            let top = match position {
+                LegendPosition::TopLeft | LegendPosition::TopRight => 0,
+                _ => height.saturating_sub(legend.len()),
+            };
+            let left = match position {
+                LegendPosition::TopLeft | LegendPosition::BottomLeft => 0,
+                _ => width.saturating_sub(legend_width),
+            };
+
+            for (i, l) in legend.iter().enumerate() {
+                if let Some(row) = tab.get_mut(top + i) {
+                    split_cells(l).into_iter().enumerate().for_each(|(j, c)|
+                        if let Some(cell) = row.get_mut(left + j) {*cell = c}
+                    );
+                }
+            }
+
+            cell_table_to_string(&tab)
+        }
+    }
+}
+
 /// Formats a plot to add a title and axes, depending on options.
 /// 
 /// # Arguments
@@ -228,12 +865,47 @@ pub(crate) fn add_title(s: &String, title: String) -> String {
 /// implementation of how labels are formatted.
 /// 
 pub fn add_opt_axes_and_opt_titles(s: &String, range: ((f64, f64), (f64, f64)), include_axes: bool, title: Option<&str>) -> String {
+    let config = AxesConfig::default();
+    add_opt_axes_and_opt_titles_and_opt_legend(s, range, include_axes.then_some(&config), title, None)
+}
+
+/// Formats a plot to add a title, axes, and a legend, depending on options.
+/// 
+/// # Arguments
+/// 
+/// * `s` - The input plot.
+/// * `range` - The range of numbers over which the input is plotted.
+/// * `axes` - Options for drawing axes and axes labels, or None to not add them.
+/// * `title` - An optional title to be added to the plot.
+/// * `legend` - An optional list of (marker, label) entries, and where to place them.
+/// 
+/// # Notes
+/// 
+/// Gridlines and reference lines are added first, underneath everything else.
+/// Legends inside the plot are added before the axes, so they never cover the axes labels.
+/// Legends outside the plot are added after the axes, but before the title.
+/// 
+pub fn add_opt_axes_and_opt_titles_and_opt_legend(s: &String, range: ((f64, f64), (f64, f64)), axes: Option<&AxesConfig>, title: Option<&str>, legend: Option<(&[(String, String)], LegendPosition)>) -> String {
     let mut o = String::new();
 
-    if include_axes {
-        o.push_str(&add_axes(s, range));
-    } else {
-        o.push_str(&s);
+    let plot = match axes {
+        Some(config) => add_background_lines(s, range, config),
+        None => s.to_string(),
+    };
+
+    let plot = match legend {
+        Some((entries, position)) if position.is_inside() => add_legend(&plot, entries, position),
+        _ => plot,
+    };
+
+    match axes {
+        Some(config) => o.push_str(&add_axes_with_config(&plot, range, config)),
+        None => o.push_str(&plot),
+    }
+
+    match legend {
+        Some((entries, position)) if !position.is_inside() => o = add_legend(&o, entries, position),
+        _ => ()
     }
 
     match title {
@@ -15,11 +15,45 @@ pub const NULL_CHR: char = '�';
 /// 
 /// CORNER:      '└'
 /// 
+/// CORNER_RIGHT: '┘'
+/// 
 pub mod axes_chars {
     pub const VERTICAL: char = '│';
     pub const HORIZONTAL: char = '─';
     pub const CROSS: char = '┼';
     pub const CORNER: char = '└';
+    pub const CORNER_RIGHT: char = '┘';
+}
+
+/// Used for drawing the box around a legend.
+/// 
+/// # Characters
+/// 
+/// `"┌┐└┘─│"`
+/// 
+pub mod legend_chars {
+    pub const TOP_LEFT: char = '┌';
+    pub const TOP_RIGHT: char = '┐';
+    pub const BOTTOM_LEFT: char = '└';
+    pub const BOTTOM_RIGHT: char = '┘';
+    pub const HORIZONTAL: char = '─';
+    pub const VERTICAL: char = '│';
+}
+
+/// Used for drawing gridlines and reference lines behind a plot.
+/// 
+/// Gridlines are dotted, and reference lines are dashed so they stand out from the grid.
+/// 
+/// # Characters
+/// 
+/// `"┈┊╌╎┼"`
+/// 
+pub mod grid_chars {
+    pub const HORIZONTAL: char = '┈';
+    pub const VERTICAL: char = '┊';
+    pub const REF_HORIZONTAL: char = '╌';
+    pub const REF_VERTICAL: char = '╎';
+    pub const REF_CROSS: char = '┼';
 }
 
 /// Used for shading a charachter to a specific brightness.
@@ -38,8 +72,8 @@ pub mod axes_chars {
 /// 
 /// Contains charachter gradients of multiple sizes, of the form Vec<String>
 pub mod gradient_chars {
-    // Vec<String> instead of Vec<char> for future ideas:
-    // e.g. using ANSI escape codes for color / bold if the terminal supports it
+    // Vec<String> instead of Vec<char> so that each charachter can carry
+    // ANSI escape codes for color / bold. See helper::color::paint_gradient
     // Charachter sets largely based on https://paulbourke.net/dataformats/asciiart/
     pub fn binary_chars() -> Vec<String> {[" ", "█"].iter().map(|&s| s.into()).collect()}
     pub fn shade_chars() -> Vec<String> {[" ", "░", "▒", "▓", "█"].iter().map(|&s| s.into()).collect()}
@@ -0,0 +1,410 @@
+//! Helper file for coloring plots with ANSI escape codes
+//!
+//! Colored plots are still ordinary strings, so every line may contain escape
+//! codes which take up bytes, but no space on the screen. Any code measuring or
+//! splitting plots should use `visible_len` and `split_cells` rather than `len` and `chars`.
+
+/// Resets all colors and styles back to the terminal default.
+pub const RESET: &str = "\x1b[0m";
+
+/// SGR parameter for one of the 16 standard colors, where `base` is 30 for foreground or 40 for background.
+fn ansi16_code(n: u8, base: u32) -> u32 {
+    let n = n as u32 % 16;
+    if n < 8 {base + n} else {base + 60 + n - 8}
+}
+
+/// A color which can be applied to text in a terminal.
+///
+/// # Variants
+///
+/// * `Default` - The terminal's own color. Adds no escape codes.
+/// * `Ansi16` - One of the 16 standard colors (0-7 normal, 8-15 bright).
+/// * `Ansi256` - One of the 256 extended colors.
+/// * `TrueColor` - Any (r, g, b) color, for terminals which support 24-bit color.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum Color {
+    #[default]
+    Default,
+    Ansi16(u8),
+    Ansi256(u8),
+    TrueColor(u8, u8, u8),
+}
+
+impl Color {
+    pub const BLACK: Color = Color::Ansi16(0);
+    pub const RED: Color = Color::Ansi16(1);
+    pub const GREEN: Color = Color::Ansi16(2);
+    pub const YELLOW: Color = Color::Ansi16(3);
+    pub const BLUE: Color = Color::Ansi16(4);
+    pub const MAGENTA: Color = Color::Ansi16(5);
+    pub const CYAN: Color = Color::Ansi16(6);
+    pub const WHITE: Color = Color::Ansi16(7);
+
+    /// Escape code setting the foreground (text) color.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::color::Color;
+    /// assert_eq!(Color::RED.fg_code(), "\x1b[31m");
+    /// assert_eq!(Color::Ansi256(208).fg_code(), "\x1b[38;5;208m");
+    /// assert_eq!(Color::Default.fg_code(), "");
+    /// ```
+    pub fn fg_code(&self) -> String {
+        match self {
+            Color::Default => String::new(),
+            Color::Ansi16(n) => format!("\x1b[{}m", ansi16_code(*n, 30)),
+            Color::Ansi256(n) => format!("\x1b[38;5;{n}m"),
+            Color::TrueColor(r, g, b) => format!("\x1b[38;2;{r};{g};{b}m"),
+        }
+    }
+
+    /// Escape code setting the background color.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::color::Color;
+    /// assert_eq!(Color::Ansi16(9).bg_code(), "\x1b[101m");
+    /// assert_eq!(Color::TrueColor(1, 2, 3).bg_code(), "\x1b[48;2;1;2;3m");
+    /// ```
+    pub fn bg_code(&self) -> String {
+        match self {
+            Color::Default => String::new(),
+            Color::Ansi16(n) => format!("\x1b[{}m", ansi16_code(*n, 40)),
+            Color::Ansi256(n) => format!("\x1b[48;5;{n}m"),
+            Color::TrueColor(r, g, b) => format!("\x1b[48;2;{r};{g};{b}m"),
+        }
+    }
+
+    /// Colors the foreground of every line of a string.
+    ///
+    /// Each line is colored and reset seperately, so the string can still be split on newlines.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::color::Color;
+    /// assert_eq!(Color::RED.paint("a\nb"), "\x1b[31ma\x1b[0m\n\x1b[31mb\x1b[0m");
+    /// assert_eq!(Color::Default.paint("a\nb"), "a\nb");
+    /// ```
+    pub fn paint(&self, s: &str) -> String {
+        if *self == Color::Default {
+            return s.to_string();
+        }
+
+        let code = self.fg_code();
+        s.split('\n').map(|line|
+            if line.is_empty() {String::new()} else {format!("{code}{line}{RESET}")}
+        ).collect::<Vec<String>>()
+        .join("\n")
+    }
+
+    /// The (r, g, b) value of a color, using the standard xterm palette.
+    ///
+    /// Returns None for `Color::Default`, which depends on the terminal.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::color::Color;
+    /// assert_eq!(Color::RED.to_rgb(), Some((205, 0, 0)));
+    /// assert_eq!(Color::Ansi256(196).to_rgb(), Some((255, 0, 0)));
+    /// assert_eq!(Color::Default.to_rgb(), None);
+    /// ```
+    pub fn to_rgb(&self) -> Option<(u8, u8, u8)> {
+        const ANSI16: [(u8, u8, u8); 16] = [
+            (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0), (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+            (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0), (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+        ];
+        const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+        match *self {
+            Color::Default => None,
+            Color::Ansi16(n) => Some(ANSI16[n as usize % 16]),
+            Color::Ansi256(n) if n < 16 => Some(ANSI16[n as usize]),
+            Color::Ansi256(n) if n < 232 => {
+                let i = (n - 16) as usize;
+                Some((LEVELS[i / 36], LEVELS[i / 6 % 6], LEVELS[i % 6]))
+            }
+            Color::Ansi256(n) => {
+                let v = 8 + 10 * (n - 232);
+                Some((v, v, v))
+            }
+            Color::TrueColor(r, g, b) => Some((r, g, b)),
+        }
+    }
+}
+
+/// Finds the closest of the 256 extended colors to an (r, g, b) color.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::rgb_to_ansi256;
+/// assert_eq!(rgb_to_ansi256((255, 0, 0)), 196);
+/// assert_eq!(rgb_to_ansi256((128, 128, 128)), 244);
+/// ```
+///
+/// # Notes
+///
+/// Only the 6x6x6 color cube (16-231) and the grayscale ramp (232-255) are used,
+/// as the first 16 colors are often changed by terminal themes.
+pub fn rgb_to_ansi256(rgb: (u8, u8, u8)) -> u8 {
+    const LEVELS: [i32; 6] = [0, 95, 135, 175, 215, 255];
+
+    let closest_level = |v: u8| (0..6).min_by_key(|i| (LEVELS[*i] - v as i32).abs()).unwrap();
+    let dist = |a: (i32, i32, i32)| (a.0 - rgb.0 as i32).pow(2) + (a.1 - rgb.1 as i32).pow(2) + (a.2 - rgb.2 as i32).pow(2);
+
+    // Closest color in the cube
+    let (r, g, b) = (closest_level(rgb.0), closest_level(rgb.1), closest_level(rgb.2));
+    let cube = (LEVELS[r], LEVELS[g], LEVELS[b]);
+
+    // Closest gray in the ramp, whose levels are 8, 18, ..., 238
+    let avg = (rgb.0 as i32 + rgb.1 as i32 + rgb.2 as i32) / 3;
+    let gi = ((avg - 8 + 5) / 10).clamp(0, 23);
+    let gv = 8 + 10 * gi;
+
+    if dist((gv, gv, gv)) < dist(cube) {
+        232 + gi as u8
+    } else {
+        16 + 36 * r as u8 + 6 * g as u8 + b as u8
+    }
+}
+
+/// Colors each charachter of a gradient, spreading the colors evenly across it.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::{paint_gradient, Color};
+/// let chars: Vec<String> = vec![" ".into(), "▒".into(), "█".into()];
+/// let result = paint_gradient(&chars, &[Color::BLUE, Color::RED]);
+/// assert_eq!(result, vec![" ", "\x1b[34m▒\x1b[0m", "\x1b[31m█\x1b[0m"]);
+/// ```
+///
+/// # Notes
+///
+/// Blank (whitespace) charachters are left uncolored.
+pub fn paint_gradient(chars: &[String], colors: &[Color]) -> Vec<String> {
+    if colors.is_empty() {
+        return chars.to_vec();
+    }
+
+    let n = chars.len();
+    chars.iter().enumerate().map(|(i, c)| {
+        if c.trim().is_empty() {
+            return c.clone();
+        }
+        // Split the charachters into equally sized groups, one for each color
+        let ci = std::cmp::min(i * colors.len() / n, colors.len() - 1);
+        colors[ci].paint(c)
+    }).collect()
+}
+
+/// Length of an escape code at the start of a string, or 0 if there isn't one.
+fn escape_len(s: &str) -> usize {
+    let bytes = s.as_bytes();
+    if bytes.first() != Some(&0x1b) {
+        return 0;
+    }
+
+    match bytes.get(1) {
+        // Control sequence: ESC [ params final, where final is in 0x40..=0x7E
+        Some(b'[') => {
+            match bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)) {
+                Some(p) => p + 3,
+                None => bytes.len(),
+            }
+        }
+        // Two charachter escape: ESC followed by a single charachter, which may be more than one byte
+        Some(_) => 1 + s[1..].chars().next().map_or(0, char::len_utf8),
+        None => 1,
+    }
+}
+
+/// Removes every ANSI escape code from a string.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::strip_ansi;
+/// assert_eq!(strip_ansi("\x1b[31mred\x1b[0m text"), "red text");
+/// ```
+pub fn strip_ansi(s: &str) -> String {
+    let mut o = String::new();
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let e = escape_len(rest);
+        if e > 0 {
+            rest = &rest[e..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            o.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    o
+}
+
+/// Number of charachters in a string that are visible on the screen.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::visible_len;
+/// assert_eq!(visible_len("\x1b[38;5;208m██\x1b[0m"), 2);
+/// ```
+pub fn visible_len(s: &str) -> usize {
+    if !s.contains('\x1b') {
+        return s.chars().count();
+    }
+    strip_ansi(s).chars().count()
+}
+
+/// Splits a single line into its visible charachters, each with the escape codes that apply to it.
+///
+/// Every cell can be moved or copied on its own without its color leaking into other cells.
+/// Use `join_cells` to turn them back into a string.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::split_cells;
+/// assert_eq!(split_cells("\x1b[31mab\x1b[0mc"), vec!["\x1b[31ma\x1b[0m", "\x1b[31mb\x1b[0m", "c"]);
+/// ```
+pub fn split_cells(s: &str) -> Vec<String> {
+    if !s.contains('\x1b') {
+        return s.chars().map(|c| c.to_string()).collect();
+    }
+
+    let mut o = Vec::new();
+    let mut style = String::new();
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let e = escape_len(rest);
+        if e > 0 {
+            let code = &rest[..e];
+            if code == RESET || code == "\x1b[m" {
+                style.clear();
+            } else {
+                style.push_str(code);
+            }
+            rest = &rest[e..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            if style.is_empty() {
+                o.push(c.to_string());
+            } else {
+                o.push(format!("{style}{c}{RESET}"));
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    o
+}
+
+/// Splits a cell from `split_cells` into its escape codes and its visible charachter.
+fn cell_parts(cell: &str) -> (&str, &str) {
+    let mut i = 0;
+    while escape_len(&cell[i..]) > 0 {
+        i += escape_len(&cell[i..]);
+    }
+    let visible = cell[i..].strip_suffix(RESET).unwrap_or(&cell[i..]);
+    (&cell[..i], visible)
+}
+
+/// The visible charachter of a cell from `split_cells`.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::visible_char;
+/// assert_eq!(visible_char("\x1b[31ma\x1b[0m"), 'a');
+/// ```
+pub fn visible_char(cell: &str) -> char {
+    cell_parts(cell).1.chars().next().unwrap_or(' ')
+}
+
+/// The (foreground, background) colors set by the escape codes of a cell from `split_cells`.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::{cell_colors, Color};
+/// assert_eq!(cell_colors("\x1b[31;44ma\x1b[0m"), (Color::RED, Color::BLUE));
+/// assert_eq!(cell_colors("\x1b[38;2;1;2;3ma\x1b[0m"), (Color::TrueColor(1, 2, 3), Color::Default));
+/// assert_eq!(cell_colors("a"), (Color::Default, Color::Default));
+/// ```
+///
+/// # Notes
+///
+/// Only color codes are read. Other styles, such as bold or underline, are ignored.
+pub fn cell_colors(cell: &str) -> (Color, Color) {
+    let (mut fg, mut bg) = (Color::Default, Color::Default);
+    let mut rest = cell_parts(cell).0;
+
+    while !rest.is_empty() {
+        let e = escape_len(rest).max(1);
+        let code = &rest[..e];
+        rest = &rest[e..];
+
+        let Some(params) = code.strip_prefix("\x1b[").and_then(|c| c.strip_suffix('m')) else {continue};
+        let params: Vec<u32> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+
+        let mut i = 0;
+        while i < params.len() {
+            let p = params[i];
+            match p {
+                0 => (fg, bg) = (Color::Default, Color::Default),
+                30..=37 => fg = Color::Ansi16((p - 30) as u8),
+                90..=97 => fg = Color::Ansi16((p - 90 + 8) as u8),
+                40..=47 => bg = Color::Ansi16((p - 40) as u8),
+                100..=107 => bg = Color::Ansi16((p - 100 + 8) as u8),
+                39 => fg = Color::Default,
+                49 => bg = Color::Default,
+                38 | 48 => {
+                    // Extended colors: 38;5;n or 38;2;r;g;b
+                    let arg = |j: usize| params.get(i + j).copied().unwrap_or(0) as u8;
+                    let (color, len) = match params.get(i + 1) {
+                        Some(5) => (Color::Ansi256(arg(2)), 3),
+                        Some(2) => (Color::TrueColor(arg(2), arg(3), arg(4)), 5),
+                        _ => (Color::Default, 1),
+                    };
+                    if p == 38 {fg = color} else {bg = color}
+                    i += len - 1;
+                }
+                _ => (),
+            }
+            i += 1;
+        }
+    }
+
+    (fg, bg)
+}
+
+/// Joins cells from `split_cells` back into a single line, without repeating shared escape codes.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::color::{split_cells, join_cells};
+/// let s = "\x1b[31mab\x1b[0mc";
+/// assert_eq!(join_cells(&split_cells(s)), s);
+/// ```
+pub fn join_cells<S: AsRef<str>>(cells: &[S]) -> String {
+    let mut o = String::new();
+    let mut current = "";
+
+    for cell in cells.iter() {
+        let (style, visible) = cell_parts(cell.as_ref());
+
+        if style != current {
+            if !current.is_empty() {
+                o.push_str(RESET);
+            }
+            o.push_str(style);
+            current = style;
+        }
+        o.push_str(visible);
+    }
+
+    if !current.is_empty() {
+        o.push_str(RESET);
+    }
+
+    o
+}
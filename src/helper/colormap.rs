@@ -0,0 +1,215 @@
+//! Helper file for mapping numbers to colors
+//!
+//! A colormap turns a number from 0 to 1 into an (R, G, B) color. Tables of numbers can be
+//! turned into images with `apply_colormap`, to be used with `image_plot` and `animation_plot`.
+//!
+//! # Example
+//! ```
+//! use cgrustplot::{
+//!     helper::colormap::{Colormap, apply_colormap},
+//!     plots::image_plot::image_plot,
+//! };
+//!
+//! let data: Vec<Vec<f64>> = (0..100).map(|r| (0..100).map(|c| (0.1 * r as f64).sin() + (0.1 * c as f64).cos()).collect()).collect();
+//! let img = apply_colormap(&data, &Colormap::viridis());
+//!
+//! # #[cfg(feature = "image-export")]
+//! image_plot(&img).set_rel_path("testoutput/doctest_colormap.png").save();
+//! ```
+
+use crate::helper::{
+    color::Color,
+    math::{max_always, min_always},
+};
+use crate::helper::parallel::*;
+
+/// A map from numbers in [0, 1] to colors.
+///
+/// Colors are linearly interpolated between evenly-spaced stops.
+///
+/// # Colormaps
+///
+/// * `viridis`, `magma`, `inferno`, `plasma` - Perceptually uniform colormaps.
+/// * `coolwarm` - Diverging colormap from blue to red, with gray in the middle.
+/// * `grayscale` - Black to white.
+/// * `Colormap::new` - Any user-defined gradient.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Colormap {
+    stops: Vec<(u8, u8, u8)>,
+    bands: Option<u32>,
+}
+
+impl Colormap {
+    /// Creates a colormap which passes through each color in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::colormap::Colormap;
+    /// let cmap = Colormap::new(vec![(0, 0, 0), (255, 0, 0), (255, 255, 0)]);
+    /// assert_eq!(cmap.sample(0.25), (128, 0, 0));
+    /// assert_eq!(cmap.sample(0.75), (255, 128, 0));
+    /// ```
+    ///
+    /// # Notes
+    ///
+    /// Panics if no colors are given.
+    pub fn new(stops: Vec<(u8, u8, u8)>) -> Self {
+        assert!(!stops.is_empty(), "A colormap needs at least one color");
+        Colormap { stops, bands: None }
+    }
+
+    pub fn viridis() -> Self {
+        Colormap::new(vec![
+            (68, 1, 84), (71, 44, 122), (59, 81, 139), (44, 113, 142), (33, 145, 140),
+            (39, 173, 129), (92, 200, 99), (170, 220, 50), (253, 231, 37),
+        ])
+    }
+
+    pub fn magma() -> Self {
+        Colormap::new(vec![
+            (0, 0, 4), (28, 16, 68), (79, 18, 123), (129, 37, 129), (181, 54, 122),
+            (229, 80, 100), (251, 135, 97), (254, 194, 135), (252, 253, 191),
+        ])
+    }
+
+    pub fn inferno() -> Self {
+        Colormap::new(vec![
+            (0, 0, 4), (31, 12, 72), (85, 15, 109), (136, 34, 106), (186, 54, 85),
+            (227, 89, 51), (249, 140, 10), (249, 201, 50), (252, 255, 164),
+        ])
+    }
+
+    pub fn plasma() -> Self {
+        Colormap::new(vec![
+            (13, 8, 135), (75, 3, 161), (125, 3, 168), (168, 34, 150), (203, 70, 121),
+            (229, 107, 93), (248, 148, 65), (253, 195, 40), (240, 249, 33),
+        ])
+    }
+
+    pub fn coolwarm() -> Self {
+        Colormap::new(vec![
+            (59, 76, 192), (98, 130, 234), (141, 176, 254), (184, 208, 249), (221, 221, 221),
+            (245, 196, 173), (244, 154, 123), (222, 96, 77), (180, 4, 38),
+        ])
+    }
+
+    pub fn grayscale() -> Self {
+        Colormap::new(vec![(0, 0, 0), (255, 255, 255)])
+    }
+
+    /// Returns the same colormap, going from high to low.
+    pub fn reversed(&self) -> Self {
+        Colormap {
+            stops: self.stops.iter().rev().copied().collect(),
+            bands: self.bands,
+        }
+    }
+
+    /// Returns the colormap split into n flat bands of color.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::colormap::Colormap;
+    /// let cmap = Colormap::grayscale().discretized(2);
+    /// assert_eq!(cmap.sample(0.1), (0, 0, 0));
+    /// assert_eq!(cmap.sample(0.9), (255, 255, 255));
+    /// ```
+    pub fn discretized(&self, n: u32) -> Self {
+        Colormap {
+            stops: self.stops.clone(),
+            bands: Some(n.max(1)),
+        }
+    }
+
+    /// The color at a point from 0 to 1. Points outside of [0, 1] are clamped.
+    ///
+    /// # Notes
+    ///
+    /// NaN is mapped to black.
+    pub fn sample(&self, t: f64) -> (u8, u8, u8) {
+        if t.is_nan() {
+            return (0, 0, 0);
+        }
+
+        let mut t = t.clamp(0., 1.);
+
+        // Snap to the center of a band
+        if let Some(n) = self.bands {
+            t = if n == 1 {0.5} else {(t * n as f64).floor().min(n as f64 - 1.) / (n - 1) as f64};
+        }
+
+        if self.stops.len() == 1 {
+            return self.stops[0];
+        }
+
+        let pos = t * (self.stops.len() - 1) as f64;
+        let i = (pos.floor() as usize).min(self.stops.len() - 2);
+        let frac = pos - i as f64;
+
+        let lerp = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * frac).round() as u8;
+        let (a, b) = (self.stops[i], self.stops[i + 1]);
+
+        (lerp(a.0, b.0), lerp(a.1, b.1), lerp(a.2, b.2))
+    }
+
+    /// The color at a point from 0 to 1, as a terminal color.
+    pub fn sample_color(&self, t: f64) -> Color {
+        let (r, g, b) = self.sample(t);
+        Color::TrueColor(r, g, b)
+    }
+
+    /// n evenly-spaced colors from the colormap, e.g. for `array_plot(..).set_colors(..)`.
+    ///
+    /// # Examples
+    /// ```
+    /// use cgrustplot::helper::{colormap::Colormap, color::Color};
+    /// assert_eq!(Colormap::grayscale().colors(2), vec![Color::TrueColor(0, 0, 0), Color::TrueColor(255, 255, 255)]);
+    /// ```
+    pub fn colors(&self, n: u32) -> Vec<Color> {
+        (0..n).map(|i| self.sample_color(if n <= 1 {0.5} else {i as f64 / (n - 1) as f64})).collect()
+    }
+}
+
+/// Maps a table of numbers to colors, where the bounds are mapped to either end of the colormap.
+pub fn apply_colormap_bounded(data: &Vec<Vec<f64>>, cmap: &Colormap, bounds: (f64, f64)) -> Vec<Vec<(u8, u8, u8)>> {
+    let width = bounds.1 - bounds.0;
+
+    data.par_iter().map(|row|
+        row.iter().map(|x|
+            // A table of a single value is mapped to the center
+            cmap.sample(if width == 0. {0.5} else {(x - bounds.0) / width})
+        ).collect()
+    ).collect()
+}
+
+/// Maps a table of numbers to colors, from the lowest to the highest value.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::colormap::{Colormap, apply_colormap};
+/// let result = apply_colormap(&vec![vec![1., 2., 3.]], &Colormap::grayscale());
+/// assert_eq!(result, vec![vec![(0, 0, 0), (128, 128, 128), (255, 255, 255)]]);
+/// ```
+///
+/// # Notes
+///
+/// NaN values are ignored when finding the bounds, and are mapped to black.
+pub fn apply_colormap(data: &Vec<Vec<f64>>, cmap: &Colormap) -> Vec<Vec<(u8, u8, u8)>> {
+    apply_colormap_bounded(data, cmap, table_bounds(data.iter()))
+}
+
+/// Maps each frame of an animation to colors, with the same bounds across every frame.
+///
+/// The output can be used with `animation_plot`.
+pub fn apply_colormap_frames(frames: &[Vec<Vec<f64>>], cmap: &Colormap) -> Vec<Vec<Vec<(u8, u8, u8)>>> {
+    let bounds = table_bounds(frames.iter().flatten());
+
+    frames.iter().map(|frame| apply_colormap_bounded(frame, cmap, bounds)).collect()
+}
+
+/// Minimum and maximum non-NaN value over some rows.
+fn table_bounds<'a>(rows: impl Iterator<Item = &'a Vec<f64>>) -> (f64, f64) {
+    let (mins, maxs): (Vec<f64>, Vec<f64>) = rows.map(|r| (min_always(r, f64::NAN), max_always(r, f64::NAN))).unzip();
+    (min_always(&mins, 0.), max_always(&maxs, 0.))
+}
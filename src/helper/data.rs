@@ -0,0 +1,278 @@
+//! Helper file for reading plot data from delimited text, such as CSV or TSV files
+//!
+//! Text is read into a `Data` table of cells, which can then be converted into
+//! the inputs each plot expects:
+//!
+//! * `points` - (x, y) pairs from two columns, for scatter and line plots.
+//! * `matrix` - Every row of numbers, for array plots.
+//! * `grouped_points` - (x, y) pairs for each distinct value of a key column, e.g. one series per label.
+//!
+//! Empty cells, and cells such as `NaN`, `NA`, or `null`, are read as NaN.
+//! Cells which aren't numbers give a `PlotError::Parse` with the line they are on.
+//!
+//! # Example
+//! ```
+//! use cgrustplot::helper::data::data_reader;
+//!
+//! let text = "time,loss\n0,1.0\n1,0.5\n2,\n3,0.2";
+//! let data = data_reader().parse(text);
+//!
+//! assert_eq!(data.header(), Some(&["time".to_string(), "loss".to_string()][..]));
+//!
+//! let points = data.points(0, data.column_index("loss").unwrap()).unwrap();
+//! assert_eq!(points[1], (1., 0.5));
+//! assert!(points[2].1.is_nan());
+//! ```
+
+use std::fs::read_to_string;
+
+use crate::helper::error::PlotError;
+
+/// Builder for reading delimited text.
+/// Set various options for how the text is split into cells.
+///
+/// # Options
+///
+/// * `delimiter` - Charachter between cells. A space splits on any run of whitespace. Default is detected from the first line: a comma, tab, or semicolon if it has one, otherwise whitespace.
+/// * `header` - Whether the first line is a header of column names. Default is detected: true if the first line has text where the second line has a number.
+/// * `comment` - Lines starting with this charachter are skipped. Default is '#'.
+///
+#[derive(Clone, Debug)]
+pub struct DataReaderBuilder {
+    delimiter: Option<char>,
+    header: Option<bool>,
+    comment: Option<char>,
+}
+
+/// A table of cells read from delimited text.
+///
+/// Rows keep the line they were read from, so that errors can point to it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Data {
+    header: Option<Vec<String>>,
+    rows: Vec<(usize, Vec<String>)>,
+}
+
+/// A key and its (x, y) points, as returned by `Data::grouped_points`.
+pub type Series = (String, Vec<(f64, f64)>);
+
+/// Whether a cell represents a missing value.
+fn is_missing(cell: &str) -> bool {
+    matches!(cell.to_lowercase().as_str(), "" | "nan" | "na" | "n/a" | "null" | "none")
+}
+
+/// Parses a cell as a number, with missing values as NaN.
+fn parse_cell(cell: &str) -> Option<f64> {
+    if is_missing(cell) {
+        Some(f64::NAN)
+    } else {
+        cell.parse::<f64>().ok()
+    }
+}
+
+/// Splits a line into cells. Cells may be quoted with '"', in which case they can contain
+/// the delimiter, and `""` is a literal quote.
+fn split_line(line: &str, delimiter: char) -> Vec<String> {
+    if delimiter == ' ' {
+        return line.split_whitespace().map(|c| c.trim_matches('"').to_string()).collect();
+    }
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                cell.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => cells.push(std::mem::take(&mut cell).trim().to_string()),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell.trim().to_string());
+
+    cells
+}
+
+/// Guesses the delimiter of a line.
+fn detect_delimiter(line: &str) -> char {
+    [',', '\t', ';'].into_iter().find(|d| line.contains(*d)).unwrap_or(' ')
+}
+
+/// Guesses whether the first row is a header, by whether it has text in a column where the second row has a number.
+fn detect_header(rows: &[(usize, Vec<String>)]) -> bool {
+    let Some((_, first)) = rows.first() else {return false};
+    let is_text = |c: &String| parse_cell(c).is_none();
+
+    match rows.get(1) {
+        Some((_, second)) => first.iter().zip(second.iter()).any(|(a, b)| is_text(a) && parse_cell(b).is_some_and(|n| !n.is_nan())),
+        None => first.iter().all(is_text),
+    }
+}
+
+impl DataReaderBuilder {
+    /// Create a reader with default options.
+    fn new() -> Self {
+        DataReaderBuilder {
+            delimiter: None,
+            header: None,
+            comment: None,
+        }
+    }
+
+    pub fn set_delimiter(&mut self, delimiter: char) -> &mut Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn set_header(&mut self, header: bool) -> &mut Self {
+        self.header = Some(header);
+        self
+    }
+
+    pub fn set_comment(&mut self, comment: char) -> &mut Self {
+        self.comment = Some(comment);
+        self
+    }
+
+    /// Reads a table from a string of delimited text.
+    pub fn parse(&self, text: &str) -> Data {
+        let comment = self.comment.unwrap_or('#');
+
+        let lines: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(i, l)| (i + 1, l))
+            .filter(|(_, l)| !l.trim().is_empty() && !l.trim_start().starts_with(comment))
+            .collect();
+
+        let delimiter = self.delimiter.unwrap_or_else(|| detect_delimiter(lines.first().map_or("", |l| l.1)));
+
+        let mut rows: Vec<(usize, Vec<String>)> = lines.into_iter().map(|(i, l)| (i, split_line(l, delimiter))).collect();
+
+        let header = match self.header.unwrap_or_else(|| detect_header(&rows)) {
+            true if !rows.is_empty() => Some(rows.remove(0).1),
+            _ => None,
+        };
+
+        Data {header, rows}
+    }
+
+    /// Reads a table from a file of delimited text.
+    pub fn read(&self, path: &str) -> Result<Data, PlotError> {
+        let text = read_to_string(path).map_err(|source| PlotError::Io {path: path.to_string(), source})?;
+        Ok(self.parse(&text))
+    }
+}
+
+impl Data {
+    /// The column names, if the text had a header.
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+
+    /// Index of the column with the given name in the header.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.header.as_ref()?.iter().position(|h| h == name)
+    }
+
+    /// Number of rows, not including the header.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Number of columns in the widest row, or in the header.
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|(_, r)| r.len()).chain(self.header.iter().map(|h| h.len())).max().unwrap_or(0)
+    }
+
+    /// The raw text of each row's cell in a column. Rows which are too short give an error.
+    pub fn text_column(&self, column: usize) -> Result<Vec<&str>, PlotError> {
+        self.rows.iter().map(|(line, r)|
+            r.get(column).map(|c| c.as_str()).ok_or_else(|| PlotError::Parse {line: *line, message: format!("Expected at least {} columns, found {}", column + 1, r.len())})
+        ).collect()
+    }
+
+    /// Parses the cell in a column of a row, with missing cells as NaN.
+    fn number(line: usize, row: &[String], column: usize) -> Result<f64, PlotError> {
+        match row.get(column) {
+            Some(c) => parse_cell(c).ok_or_else(|| PlotError::Parse {line, message: format!("'{c}' in column {} is not a number", column + 1)}),
+            None => Ok(f64::NAN),
+        }
+    }
+
+    /// The numbers in a column. Missing cells are NaN.
+    pub fn column(&self, column: usize) -> Result<Vec<f64>, PlotError> {
+        self.rows.iter().map(|(line, r)| Data::number(*line, r, column)).collect()
+    }
+
+    /// (x, y) points from two columns, e.g. for a scatter or line plot. Missing cells are NaN.
+    pub fn points(&self, x: usize, y: usize) -> Result<Vec<(f64, f64)>, PlotError> {
+        self.rows.iter().map(|(line, r)| Ok((Data::number(*line, r, x)?, Data::number(*line, r, y)?))).collect()
+    }
+
+    /// Every row as numbers, e.g. for an array plot. Rows shorter than the widest are padded with NaN.
+    pub fn matrix(&self) -> Result<Vec<Vec<f64>>, PlotError> {
+        self.select(&(0..self.width()).collect::<Vec<usize>>())
+    }
+
+    /// The given columns of every row as numbers. Missing cells are NaN.
+    pub fn select(&self, columns: &[usize]) -> Result<Vec<Vec<f64>>, PlotError> {
+        self.rows.iter().map(|(line, r)| columns.iter().map(|c| Data::number(*line, r, *c)).collect()).collect()
+    }
+
+    /// (x, y) points for each distinct value of a key column, in the order each key first appears.
+    ///
+    /// The key can be any text, such as a label or category.
+    pub fn grouped_points(&self, key: usize, x: usize, y: usize) -> Result<Vec<Series>, PlotError> {
+        let mut groups: Vec<Series> = Vec::new();
+
+        for ((line, r), k) in self.rows.iter().zip(self.text_column(key)?) {
+            let point = (Data::number(*line, r, x)?, Data::number(*line, r, y)?);
+
+            match groups.iter_mut().find(|(g, _)| g == k) {
+                Some((_, points)) => points.push(point),
+                None => groups.push((k.to_string(), vec![point])),
+            }
+        }
+
+        Ok(groups)
+    }
+}
+
+/// Generates a DataReaderBuilder, for reading delimited text into plot inputs.
+///
+/// # Example
+///
+/// ```
+/// use cgrustplot::helper::data::data_reader;
+///
+/// let text = "# run, step, loss\ntrain 0 1.0\nval 0 1.2\ntrain 1 0.6\nval 1 0.9";
+/// let data = data_reader().parse(text);
+///
+/// let series = data.grouped_points(0, 1, 2).unwrap();
+/// assert_eq!(series[0], ("train".to_string(), vec![(0., 1.0), (1., 0.6)]));
+/// assert_eq!(series[1], ("val".to_string(), vec![(0., 1.2), (1., 0.9)]));
+///
+/// // Errors point to the line with the problem
+/// let err = data_reader().parse("1,2\n3,x").matrix().unwrap_err();
+/// assert_eq!(err.to_string(), "Line 2: 'x' in column 2 is not a number");
+/// ```
+///
+/// # Options
+///
+/// * `delimiter` - Charachter between cells. A space splits on any run of whitespace. Default is detected from the first line: a comma, tab, or semicolon if it has one, otherwise whitespace.
+/// * `header` - Whether the first line is a header of column names. Default is detected: true if the first line has text where the second line has a number.
+/// * `comment` - Lines starting with this charachter are skipped. Default is '#'.
+///
+pub fn data_reader() -> DataReaderBuilder {
+    DataReaderBuilder::new()
+}
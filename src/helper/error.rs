@@ -0,0 +1,69 @@
+//! Helper file for errors from saving and displaying plots.
+//! 
+//! Outputs such as `save` and `pyplot` never fail loudly, and only call warn!() if
+//! something goes wrong. Each of them has a `try_` version (e.g. `try_save`, `try_pyplot`)
+//! which returns a `PlotError` instead, for code which needs to know whether a plot was written.
+//! 
+//! # Example
+//! ```
+//! use cgrustplot::plots::function_plot::function_plot;
+//! 
+//! let f = |x: f64| x * x;
+//! let result = function_plot(&f).try_save("/this/directory/does/not/exist/plot.txt");
+//! assert!(result.is_err());
+//! ```
+
+use std::{error::Error, fmt};
+use log::warn;
+
+/// An error from saving or displaying a plot.
+/// 
+/// # Variants
+/// 
+/// * `Io` - Reading or writing a file or directory failed.
+/// * `Image` - Encoding or saving an image failed. Only with the `image-export` feature.
+/// * `Command` - An external program (e.g. python3, ffmpeg, magick) could not be run, or exited unsuccessfully.
+/// * `InvalidInput` - The input can't be used, such as a table which isn't rectangular.
+/// * `Parse` - Text data couldn't be read, at the given line (counting from 1).
+/// 
+/// Which variants exist depends on the enabled features, so matches on a `PlotError` need a `_` arm.
+/// 
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum PlotError {
+    Io {path: String, source: std::io::Error},
+    #[cfg(feature = "image-export")]
+    Image {path: String, source: image::ImageError},
+    Command {program: String, message: String},
+    InvalidInput(String),
+    Parse {line: usize, message: String},
+}
+
+impl fmt::Display for PlotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PlotError::Io {path, source} => write!(f, "Failed to access '{path}': {source}"),
+            #[cfg(feature = "image-export")]
+            PlotError::Image {path, source} => write!(f, "Failed to save image to '{path}': {source}"),
+            PlotError::Command {program, message} => write!(f, "Failed to run {program}: {message}"),
+            PlotError::InvalidInput(message) => write!(f, "Invalid input: {message}"),
+            PlotError::Parse {line, message} => write!(f, "Line {line}: {message}"),
+        }
+    }
+}
+
+impl Error for PlotError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PlotError::Io {source, ..} => Some(source),
+            #[cfg(feature = "image-export")]
+            PlotError::Image {source, ..} => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Calls warn!() if a result is an error, for outputs which don't return their errors.
+pub(crate) fn warn_on_err(result: Result<(), PlotError>) {
+    if let Err(e) = result {warn!("{e}");}
+}
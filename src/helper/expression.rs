@@ -0,0 +1,376 @@
+//! Helper file for parsing and evaluating math expressions
+//!
+//! Used to plot functions and regions given as text, such as from the command line.
+//! Expressions support numbers, named variables, `pi` and `e`, the operators
+//! `+ - * / % ^`, comparisons `< <= > >= == !=`, logic `&& || !`, parentheses,
+//! and common functions such as `sin`, `sqrt`, `abs`, `ln`, `min` and `max`.
+//!
+//! Comparisons and logic evaluate to 1 for true and 0 for false, so `x^2 + y^2 < 1`
+//! can be used as the predicate of a region plot.
+//!
+//! # Example
+//! ```
+//! use cgrustplot::helper::expression::parse_expression;
+//!
+//! let expr = parse_expression("2 * sin(x) + y^2", &["x", "y"]).unwrap();
+//! assert_eq!(expr.eval(&[0., 3.]), 9.);
+//!
+//! let disk = parse_expression("x^2 + y^2 < 1", &["x", "y"]).unwrap();
+//! assert!(disk.eval_bool(&[0.5, 0.5]));
+//! assert!(!disk.eval_bool(&[1., 1.]));
+//! ```
+
+use crate::helper::error::PlotError;
+
+/// A parsed expression, which can be evaluated for any values of its variables.
+#[derive(Clone, Debug)]
+pub struct Expression {
+    node: Node,
+    variables: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Num(f64),
+    Var(usize),
+    Neg(Box<Node>),
+    Not(Box<Node>),
+    Binary(Op, Box<Node>, Box<Node>),
+    Call1(fn(f64) -> f64, Box<Node>),
+    Call2(fn(f64, f64) -> f64, Box<Node>, Box<Node>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Op {
+    Add, Sub, Mul, Div, Rem, Pow,
+    Lt, Le, Gt, Ge, Eq, Ne,
+    And, Or,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    Num(f64),
+    Ident(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    Comma,
+}
+
+fn truthy(v: f64) -> bool {
+    v != 0. && !v.is_nan()
+}
+
+fn from_bool(b: bool) -> f64 {
+    if b {1.} else {0.}
+}
+
+impl Expression {
+    /// Evaluates the expression, with `values` given in the same order as the variables it was parsed with.
+    ///
+    /// # Panics
+    ///
+    /// If fewer values are given than there are variables.
+    pub fn eval(&self, values: &[f64]) -> f64 {
+        assert!(values.len() >= self.variables, "Expected {} values, got {}", self.variables, values.len());
+        self.node.eval(values)
+    }
+
+    /// Evaluates the expression as a condition, which is true if it is nonzero and not NaN.
+    pub fn eval_bool(&self, values: &[f64]) -> bool {
+        truthy(self.eval(values))
+    }
+}
+
+impl Node {
+    fn eval(&self, values: &[f64]) -> f64 {
+        match self {
+            Node::Num(n) => *n,
+            Node::Var(i) => values[*i],
+            Node::Neg(a) => -a.eval(values),
+            Node::Not(a) => from_bool(!truthy(a.eval(values))),
+            Node::Call1(f, a) => f(a.eval(values)),
+            Node::Call2(f, a, b) => f(a.eval(values), b.eval(values)),
+            Node::Binary(op, a, b) => {
+                let l = a.eval(values);
+
+                // Short circuit logic
+                match op {
+                    Op::And if !truthy(l) => return 0.,
+                    Op::Or if truthy(l) => return 1.,
+                    _ => (),
+                }
+
+                let r = b.eval(values);
+
+                match op {
+                    Op::Add => l + r,
+                    Op::Sub => l - r,
+                    Op::Mul => l * r,
+                    Op::Div => l / r,
+                    Op::Rem => l % r,
+                    Op::Pow => l.powf(r),
+                    Op::Lt => from_bool(l < r),
+                    Op::Le => from_bool(l <= r),
+                    Op::Gt => from_bool(l > r),
+                    Op::Ge => from_bool(l >= r),
+                    Op::Eq => from_bool(l == r),
+                    Op::Ne => from_bool(l != r),
+                    Op::And | Op::Or => from_bool(truthy(r)),
+                }
+            }
+        }
+    }
+}
+
+/// Operators, longest first so that e.g. `<=` isn't read as `<`.
+const OPERATORS: [&str; 16] = ["<=", ">=", "==", "!=", "&&", "||", "+", "-", "*", "/", "%", "^", "<", ">", "!", "="];
+
+fn invalid(message: String) -> PlotError {
+    PlotError::InvalidInput(message)
+}
+
+/// Splits an expression into tokens.
+fn tokenize(s: &str) -> Result<Vec<Token>, PlotError> {
+    let chars: Vec<char> = s.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            // Exponent, e.g. 1e-3
+            if i < chars.len() && (chars[i] == 'e' || chars[i] == 'E') {
+                let mut j = i + 1;
+                if j < chars.len() && (chars[j] == '+' || chars[j] == '-') {
+                    j += 1;
+                }
+                if j < chars.len() && chars[j].is_ascii_digit() {
+                    i = j;
+                    while i < chars.len() && chars[i].is_ascii_digit() {
+                        i += 1;
+                    }
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            let n = text.parse::<f64>().map_err(|_| invalid(format!("'{text}' is not a number, at position {start}")))?;
+            tokens.push(Token::Num(n));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            tokens.push(Token::Ident(chars[start..i].iter().collect()));
+        } else if c == '(' {
+            tokens.push(Token::LParen);
+            i += 1;
+        } else if c == ')' {
+            tokens.push(Token::RParen);
+            i += 1;
+        } else if c == ',' {
+            tokens.push(Token::Comma);
+            i += 1;
+        } else {
+            let op = OPERATORS.iter().find(|op| op.chars().enumerate().all(|(j, oc)| chars.get(i + j) == Some(&oc)));
+            match op {
+                // A single = is the same as ==
+                Some(&"=") => tokens.push(Token::Op("==")),
+                Some(op) => tokens.push(Token::Op(op)),
+                None => return Err(invalid(format!("Unexpected character '{c}' at position {i}"))),
+            }
+            i += op.map_or(1, |op| op.len());
+        }
+    }
+
+    Ok(tokens)
+}
+
+fn function1(name: &str) -> Option<fn(f64) -> f64> {
+    Some(match name {
+        "sin" => f64::sin,
+        "cos" => f64::cos,
+        "tan" => f64::tan,
+        "asin" => f64::asin,
+        "acos" => f64::acos,
+        "atan" => f64::atan,
+        "sinh" => f64::sinh,
+        "cosh" => f64::cosh,
+        "tanh" => f64::tanh,
+        "sqrt" => f64::sqrt,
+        "abs" => f64::abs,
+        "exp" => f64::exp,
+        "ln" => f64::ln,
+        "log" | "log10" => f64::log10,
+        "log2" => f64::log2,
+        "floor" => f64::floor,
+        "ceil" => f64::ceil,
+        "round" => f64::round,
+        "sign" => f64::signum,
+        _ => return None,
+    })
+}
+
+fn function2(name: &str) -> Option<fn(f64, f64) -> f64> {
+    Some(match name {
+        "min" => f64::min,
+        "max" => f64::max,
+        "atan2" => f64::atan2,
+        "pow" => f64::powf,
+        _ => return None,
+    })
+}
+
+/// Recursive descent parser, with one function per level of precedence.
+struct Parser<'a> {
+    tokens: Vec<Token>,
+    pos: usize,
+    variables: &'a [&'a str],
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let t = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        t
+    }
+
+    fn expect(&mut self, token: Token) -> Result<(), PlotError> {
+        match self.next() {
+            Some(t) if t == token => Ok(()),
+            Some(t) => Err(invalid(format!("Expected {token:?}, found {t:?}"))),
+            None => Err(invalid(format!("Expected {token:?}, found the end of the expression"))),
+        }
+    }
+
+    /// Parses one level of left-associative binary operators.
+    fn binary(&mut self, ops: &[(&str, Op)], next: fn(&mut Self) -> Result<Node, PlotError>) -> Result<Node, PlotError> {
+        let mut node = next(self)?;
+
+        while let Some(Token::Op(s)) = self.peek() {
+            let Some(&(_, op)) = ops.iter().find(|(o, _)| o == s) else {break};
+            self.pos += 1;
+            node = Node::Binary(op, Box::new(node), Box::new(next(self)?));
+        }
+
+        Ok(node)
+    }
+
+    fn or(&mut self) -> Result<Node, PlotError> {
+        self.binary(&[("||", Op::Or)], Self::and)
+    }
+
+    fn and(&mut self) -> Result<Node, PlotError> {
+        self.binary(&[("&&", Op::And)], Self::comparison)
+    }
+
+    fn comparison(&mut self) -> Result<Node, PlotError> {
+        self.binary(&[("<", Op::Lt), ("<=", Op::Le), (">", Op::Gt), (">=", Op::Ge), ("==", Op::Eq), ("!=", Op::Ne)], Self::additive)
+    }
+
+    fn additive(&mut self) -> Result<Node, PlotError> {
+        self.binary(&[("+", Op::Add), ("-", Op::Sub)], Self::multiplicative)
+    }
+
+    fn multiplicative(&mut self) -> Result<Node, PlotError> {
+        self.binary(&[("*", Op::Mul), ("/", Op::Div), ("%", Op::Rem)], Self::unary)
+    }
+
+    fn unary(&mut self) -> Result<Node, PlotError> {
+        match self.peek() {
+            Some(Token::Op("-")) => {self.pos += 1; Ok(Node::Neg(Box::new(self.unary()?)))}
+            Some(Token::Op("+")) => {self.pos += 1; self.unary()}
+            Some(Token::Op("!")) => {self.pos += 1; Ok(Node::Not(Box::new(self.unary()?)))}
+            _ => self.power(),
+        }
+    }
+
+    /// Powers are right-associative, and bind tighter than unary minus, so -x^2 is -(x^2).
+    fn power(&mut self) -> Result<Node, PlotError> {
+        let base = self.primary()?;
+
+        if let Some(Token::Op("^")) = self.peek() {
+            self.pos += 1;
+            return Ok(Node::Binary(Op::Pow, Box::new(base), Box::new(self.unary()?)));
+        }
+
+        Ok(base)
+    }
+
+    fn primary(&mut self) -> Result<Node, PlotError> {
+        match self.next() {
+            Some(Token::Num(n)) => Ok(Node::Num(n)),
+            Some(Token::LParen) => {
+                let node = self.or()?;
+                self.expect(Token::RParen)?;
+                Ok(node)
+            }
+            Some(Token::Ident(name)) => {
+                if let Some(i) = self.variables.iter().position(|v| *v == name) {
+                    return Ok(Node::Var(i));
+                }
+
+                if self.peek() == Some(&Token::LParen) {
+                    self.pos += 1;
+                    let a = self.or()?;
+
+                    let node = if let Some(f) = function1(&name) {
+                        Node::Call1(f, Box::new(a))
+                    } else if let Some(f) = function2(&name) {
+                        self.expect(Token::Comma)?;
+                        Node::Call2(f, Box::new(a), Box::new(self.or()?))
+                    } else {
+                        return Err(invalid(format!("Unknown function '{name}'")));
+                    };
+
+                    self.expect(Token::RParen)?;
+                    return Ok(node);
+                }
+
+                match name.as_str() {
+                    "pi" => Ok(Node::Num(std::f64::consts::PI)),
+                    "e" => Ok(Node::Num(std::f64::consts::E)),
+                    _ => Err(invalid(format!("Unknown variable '{name}'. Expected one of {:?}", self.variables))),
+                }
+            }
+            Some(t) => Err(invalid(format!("Unexpected {t:?}"))),
+            None => Err(invalid("Unexpected end of the expression".to_string())),
+        }
+    }
+}
+
+/// Parses an expression in terms of the given variable names.
+///
+/// Returns `PlotError::InvalidInput` describing the problem if the expression can't be parsed.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::expression::parse_expression;
+///
+/// let f = parse_expression("-x^2 + 2x", &["x"]);
+/// assert!(f.is_err()); // Multiplication must be written out
+///
+/// let f = parse_expression("-x^2 + 2*x", &["x"]).unwrap();
+/// assert_eq!(f.eval(&[3.]), -3.);
+/// ```
+pub fn parse_expression(s: &str, variables: &[&str]) -> Result<Expression, PlotError> {
+    let mut parser = Parser {tokens: tokenize(s)?, pos: 0, variables};
+
+    let node = parser.or()?;
+
+    if let Some(t) = parser.peek() {
+        return Err(invalid(format!("Unexpected {t:?} after the end of the expression")));
+    }
+
+    Ok(Expression {node, variables: variables.len()})
+}
@@ -4,7 +4,11 @@
 
 use std::{env::current_dir, fs::write};
 use log::warn;
+#[cfg(feature = "image-export")]
 use image::{ImageBuffer, RgbImage, Rgb};
+use crate::helper::error::{PlotError, warn_on_err};
+#[cfg(feature = "image-export")]
+use crate::helper::table::Pixels;
 
 /// Returns the current directory of the project.
 /// 
@@ -40,8 +44,12 @@ pub fn get_current_dir() -> String {
 /// // testoutput/doctest_save_to_file.txt now contains "test"
 /// ```
 pub fn save_to_file(s: &str, path: &str) {
-    let r = write(path, s);
-    if let Err(e) = r {warn!("Failed to write to '{path}': {e}");}
+    warn_on_err(try_save_to_file(s, path));
+}
+
+/// Saves a string to a file, returning an error if it fails.
+pub fn try_save_to_file(s: &str, path: &str) -> Result<(), PlotError> {
+    write(path, s).map_err(|source| PlotError::Io {path: path.to_string(), source})
 }
 
 /// Saves an image (represented by a table of RGB values) to an image file.
@@ -63,10 +71,23 @@ pub fn save_to_file(s: &str, path: &str) {
 /// 
 /// Internally copies and reformats the image, so may need optimization in the future.
 /// 
-pub fn save_image(img: &Vec<Vec<(u8, u8, u8)>>, path: &str) {
+#[cfg(feature = "image-export")]
+pub fn save_image(img: &[Vec<(u8, u8, u8)>], path: &str) {
+    warn_on_err(try_save_image(img, path));
+}
+
+/// Saves an image (represented by a table of RGB values) to an image file, returning an error if it fails.
+/// 
+/// The image must be rectangular.
+#[cfg(feature = "image-export")]
+pub fn try_save_image(img: &[Vec<(u8, u8, u8)>], path: &str) -> Result<(), PlotError> {
     let height = img.len() as u32;
     let width = if height > 0 {img[0].len() as u32} else {0};
 
+    if img.iter().any(|row| row.len() as u32 != width) {
+        return Err(PlotError::InvalidInput(format!("Image for '{path}' is not rectangular")));
+    }
+
     // Benchmarked to be faster than parallelized approach with iterators
     let mut buffer = Vec::with_capacity((width * height * 3) as usize);
     img.iter().for_each(|row| {
@@ -77,8 +98,24 @@ pub fn save_image(img: &Vec<Vec<(u8, u8, u8)>>, path: &str) {
         });
     });
 
-    let image: ImageBuffer<Rgb<u8>, Vec<u8>> = RgbImage::from_raw(width, height, buffer).expect("Buffer size mismatch");
+    try_save_rgb_buffer(buffer, (width, height), path)
+}
+
+/// Saves the pixels of an image plot, which may be stored as rows or as an ndarray.
+#[cfg(feature = "image-export")]
+pub(crate) fn try_save_pixels(img: &Pixels, path: &str) -> Result<(), PlotError> {
+    match img {
+        Pixels::Rows(rows) => try_save_image(rows, path),
+        // Channels are already stored as R, G, B for each pixel, row by row
+        #[cfg(feature = "ndarray")]
+        Pixels::Array(arr) => try_save_rgb_buffer(arr.iter().copied().collect(), (img.width() as u32, img.height() as u32), path),
+    }
+}
+
+#[cfg(feature = "image-export")]
+fn try_save_rgb_buffer(buffer: Vec<u8>, size: (u32, u32), path: &str) -> Result<(), PlotError> {
+    let image: ImageBuffer<Rgb<u8>, Vec<u8>> = RgbImage::from_raw(size.0, size.1, buffer)
+        .ok_or_else(|| PlotError::InvalidInput("Buffer size mismatch".to_string()))?;
 
-    let r = image.save(path);
-    if let Err(e) = r {warn!("Failed to save image to '{path}': {e}");}
+    image.save(path).map_err(|source| PlotError::Image {path: path.to_string(), source})
 }
\ No newline at end of file
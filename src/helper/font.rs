@@ -0,0 +1,271 @@
+//! Embedded monospace bitmap font, used for rendering text plots to images.
+//!
+//! Printable ascii uses a 5x7 pixel font. Every other charachter this crate
+//! outputs (box drawing, blocks, shades, braille, sextants, etc.) is drawn
+//! procedurally, so that neighbouring cells line up with each other exactly.
+//!
+//! Charachters which aren't covered are drawn as an empty box.
+
+use crate::helper::charset::{
+    axes_chars, grid_chars, legend_chars, line_chars,
+    subdiv_chars::{blocks_two_by_two, blocks_two_by_three, dots_two_by_four},
+    NULL_CHR,
+};
+
+/// Width (in pixels) of a single charachter before scaling.
+pub(crate) const CELL_WIDTH: usize = 8;
+
+/// Height (in pixels) of a single charachter before scaling.
+pub(crate) const CELL_HEIGHT: usize = 16;
+
+/// 5x7 font for printable ascii (' ' to '~').
+///
+/// Each charachter is five columns, and bit i of a column is row i (from the top).
+const ASCII_FONT: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x55, 0x22, 0x50], // &
+    [0x00, 0x05, 0x03, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x08, 0x2A, 0x1C, 0x2A, 0x08], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // F
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x07, 0x08, 0x70, 0x08, 0x07], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // \
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x40, 0x40, 0x40, 0x40, 0x40], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x0C, 0x52, 0x52, 0x52, 0x3E], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x20, 0x40, 0x44, 0x3D, 0x00], // j
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0x7C, 0x14, 0x14, 0x14, 0x08], // p
+    [0x08, 0x14, 0x14, 0x18, 0x7C], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x0C, 0x50, 0x50, 0x50, 0x3C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+/// Pixels of a single charachter's cell, indexed as [row][column].
+pub(crate) type GlyphMask = [[bool; CELL_WIDTH]; CELL_HEIGHT];
+
+/// Fills the pixels in [x0, x1) by [y0, y1).
+fn fill_rect(m: &mut GlyphMask, x: (usize, usize), y: (usize, usize)) {
+    (y.0..y.1).for_each(|r| (x.0..x.1).for_each(|c| m[r][c] = true));
+}
+
+/// Draws a charachter from the ascii font, with each font pixel two pixels tall.
+fn ascii_glyph(m: &mut GlyphMask, c: char) {
+    let columns = ASCII_FONT[c as usize - ' ' as usize];
+
+    for (x, col) in columns.iter().enumerate() {
+        for y in 0..7 {
+            if col >> y & 1 == 1 {
+                fill_rect(m, (x + 1, x + 2), (2 * y + 1, 2 * y + 3));
+            }
+        }
+    }
+}
+
+/// Draws a charachter from one of the subdivided charsets, by filling in each of its subpixels.
+///
+/// Subpixels are indexed in the same way as the charsets: bit (col * rows + row) is the subpixel at (col, row).
+fn subdiv_glyph(m: &mut GlyphMask, index: usize, dims: (usize, usize), dots: bool) {
+    let (w, h) = dims;
+
+    for col in 0..w {
+        for row in 0..h {
+            if index >> (col * h + row) & 1 == 0 {
+                continue;
+            }
+
+            let (x0, x1) = (col * CELL_WIDTH / w, (col + 1) * CELL_WIDTH / w);
+            let (y0, y1) = (row * CELL_HEIGHT / h, (row + 1) * CELL_HEIGHT / h);
+
+            if dots {
+                // A small square in the center of the subpixel
+                let (cx, cy) = ((x0 + x1) / 2, (y0 + y1) / 2);
+                fill_rect(m, (cx - 1, cx + 1), (cy - 1, cy + 1));
+            } else {
+                fill_rect(m, (x0, x1), (y0, y1));
+            }
+        }
+    }
+}
+
+/// Draws box drawing lines from the center of the cell to each of its (up, down, left, right) edges.
+fn box_glyph(m: &mut GlyphMask, up: bool, down: bool, left: bool, right: bool) {
+    let (cx, cy) = (CELL_WIDTH / 2, CELL_HEIGHT / 2);
+
+    if up {fill_rect(m, (cx, cx + 1), (0, cy + 1))}
+    if down {fill_rect(m, (cx, cx + 1), (cy, CELL_HEIGHT))}
+    if left {fill_rect(m, (0, cx + 1), (cy, cy + 1))}
+    if right {fill_rect(m, (cx, CELL_WIDTH), (cy, cy + 1))}
+}
+
+/// Draws a dotted or dashed box drawing line through the center of the cell, split into `dashes` evenly spaced dashes.
+///
+/// Dotted lines leave a gap as long as each dot, and dashed lines a gap a third as long as each dash.
+fn dashed_glyph(m: &mut GlyphMask, vertical: bool, dashes: usize, dotted: bool) {
+    let (cx, cy) = (CELL_WIDTH / 2, CELL_HEIGHT / 2);
+    let period = if vertical {CELL_HEIGHT} else {CELL_WIDTH} / dashes;
+    let gap = if dotted {period / 2} else {period / 4};
+
+    for i in 0..dashes {
+        // Half of the gap is on each side, so that the dashes line up across neighbouring cells
+        let (start, end) = (i * period + gap / 2, (i + 1) * period - (gap - gap / 2));
+
+        if vertical {
+            fill_rect(m, (cx, cx + 1), (start, end));
+        } else {
+            fill_rect(m, (start, end), (cy, cy + 1));
+        }
+    }
+}
+
+/// Generates the pixels of a single charachter.
+pub(crate) fn glyph_mask(c: char) -> GlyphMask {
+    let mut m = [[false; CELL_WIDTH]; CELL_HEIGHT];
+
+    match c {
+        ' ' | '⠀' => (),
+
+        // Lines which connect to neighbouring cells
+        axes_chars::VERTICAL => box_glyph(&mut m, true, true, false, false),
+        axes_chars::HORIZONTAL | line_chars::FLAT_MED => box_glyph(&mut m, false, false, true, true),
+        axes_chars::CROSS => box_glyph(&mut m, true, true, true, true),
+        axes_chars::CORNER => box_glyph(&mut m, true, false, false, true),
+        legend_chars::TOP_LEFT => box_glyph(&mut m, false, true, false, true),
+        legend_chars::TOP_RIGHT => box_glyph(&mut m, false, true, true, false),
+        legend_chars::BOTTOM_RIGHT => box_glyph(&mut m, true, false, true, false),
+        grid_chars::HORIZONTAL => dashed_glyph(&mut m, false, 4, true),
+        grid_chars::VERTICAL => dashed_glyph(&mut m, true, 4, true),
+        grid_chars::REF_HORIZONTAL => dashed_glyph(&mut m, false, 2, false),
+        grid_chars::REF_VERTICAL => dashed_glyph(&mut m, true, 2, false),
+        line_chars::FLAT_LOW => fill_rect(&mut m, (0, CELL_WIDTH), (CELL_HEIGHT - 1, CELL_HEIGHT)),
+        line_chars::FLAT_HIGH => fill_rect(&mut m, (0, CELL_WIDTH), (0, 1)),
+        line_chars::UP_ONE | line_chars::DOWN_ONE => {
+            for (y, row) in m.iter_mut().enumerate() {
+                let x = y * CELL_WIDTH / CELL_HEIGHT;
+                row[if c == line_chars::UP_ONE {CELL_WIDTH - 1 - x} else {x}] = true;
+            }
+        }
+
+        // Shades
+        '░' => (0..CELL_HEIGHT).for_each(|y| (0..CELL_WIDTH).for_each(|x| m[y][x] = x % 2 == 0 && y % 2 == 0)),
+        '▒' => (0..CELL_HEIGHT).for_each(|y| (0..CELL_WIDTH).for_each(|x| m[y][x] = (x + y) % 2 == 0)),
+        '▓' => (0..CELL_HEIGHT).for_each(|y| (0..CELL_WIDTH).for_each(|x| m[y][x] = x % 2 == 0 || y % 2 == 0)),
+
+        // Circle for dots_one_by_one
+        '●' => {
+            let (cx, cy) = (CELL_WIDTH as f64 / 2. - 0.5, CELL_HEIGHT as f64 / 2. - 0.5);
+            (0..CELL_HEIGHT).for_each(|y| (0..CELL_WIDTH).for_each(|x|
+                m[y][x] = (x as f64 - cx).powi(2) + (y as f64 - cy).powi(2) <= 9.
+            ));
+        }
+
+        // Ellipsis for truncated labels, with dots the same size as '.'
+        '…' => [0, 3, 6].into_iter().for_each(|x| fill_rect(&mut m, (x, x + 2), (11, 13))),
+
+        NULL_CHR => {
+            fill_rect(&mut m, (1, CELL_WIDTH - 1), (2, 3));
+            fill_rect(&mut m, (1, CELL_WIDTH - 1), (CELL_HEIGHT - 3, CELL_HEIGHT - 2));
+            fill_rect(&mut m, (1, 2), (2, CELL_HEIGHT - 2));
+            fill_rect(&mut m, (CELL_WIDTH - 2, CELL_WIDTH - 1), (2, CELL_HEIGHT - 2));
+            fill_rect(&mut m, (3, 5), (6, 10));
+        }
+
+        ' '..='~' => ascii_glyph(&mut m, c),
+
+        _ => {
+            if let Some(i) = blocks_two_by_two().iter().position(|b| *b == c) {
+                subdiv_glyph(&mut m, i, (2, 2), false);
+            } else if let Some(i) = blocks_two_by_three().iter().position(|b| *b == c) {
+                subdiv_glyph(&mut m, i, (2, 3), false);
+            } else if let Some(i) = dots_two_by_four().iter().position(|b| *b == c) {
+                subdiv_glyph(&mut m, i, (2, 4), true);
+            } else {
+                // Unknown charachter, drawn as an empty box
+                fill_rect(&mut m, (1, CELL_WIDTH - 1), (3, 4));
+                fill_rect(&mut m, (1, CELL_WIDTH - 1), (CELL_HEIGHT - 4, CELL_HEIGHT - 3));
+                fill_rect(&mut m, (1, 2), (3, CELL_HEIGHT - 3));
+                fill_rect(&mut m, (CELL_WIDTH - 2, CELL_WIDTH - 1), (3, CELL_HEIGHT - 3));
+            }
+        }
+    }
+
+    m
+}
@@ -11,7 +11,7 @@
 //! in the original file were already implemented in the math module.
 
 use crate::helper::math::*;
-use rayon::prelude::*;
+use crate::helper::parallel::*;
 
 // pad_range included in math
 
@@ -1,5 +1,5 @@
 use std::process::Command;
-use log::warn;
+use crate::helper::error::{PlotError, warn_on_err};
 
 /// Creates and runs a python file to use matplotlib to display a plot.
 /// 
@@ -22,6 +22,15 @@ use log::warn;
 /// // Pyplot image file is now saved at "./testoutput/doctest_pyplot.png"
 /// ```
 pub fn pyplot(plt_command: &str, title: Option<&str>, axes: Option<bool>, rge: Option<((f64, f64), (f64, f64))>, path: Option<&str>) {
+    warn_on_err(try_pyplot(plt_command, title, axes, rge, path));
+}
+
+/// Creates and runs a python file to use matplotlib to display a plot, returning an error if it fails.
+/// 
+/// Fails if python3 can't be run, or if the script exits unsuccessfully (e.g. if matplotlib is not installed).
+/// 
+/// Takes the same arguments as `pyplot`.
+pub fn try_pyplot(plt_command: &str, title: Option<&str>, axes: Option<bool>, rge: Option<((f64, f64), (f64, f64))>, path: Option<&str>) -> Result<(), PlotError> {
     let title_str = match title {Some(s) => &vec!["\"",  s, "\""].join(""), None => "None" };
     let axes_str = match axes {Some(b) => if b {"True"} else {"False"}, None => "None"};
     let rge_str = match rge {Some(r) => &vec!["((", &r.0.0.to_string(), ", ", &r.0.1.to_string(), "), (", &r.1.0.to_string(), ", ", &r.1.1.to_string(), "))"].join(""), None => "None"};
@@ -59,11 +68,18 @@ else:
     plt.show()
 ",  title=title_str, axes=axes_str, rge=rge_str, path=path_str, plot_command=plt_command);
 
-    if let Err(e) = Command::new("python3")
+    let output = Command::new("python3")
         .arg("-c")
         .arg(script)
-        .output() {
-            warn!("Failed to run matplotlib script: {e}");
-        }
-    
+        .output()
+        .map_err(|e| PlotError::Command {program: "python3".to_string(), message: e.to_string()})?;
+
+    if !output.status.success() {
+        return Err(PlotError::Command {
+            program: "python3".to_string(),
+            message: format!("{}: {}", output.status, String::from_utf8_lossy(&output.stderr).trim()),
+        });
+    }
+
+    Ok(())
 }
@@ -21,5 +21,19 @@ pub mod charset;
 pub mod axes;
 pub mod func_plot_domain;
 pub mod rendering;
+#[cfg(feature = "matplotlib")]
 pub mod mat_plot_lib;
-pub mod file;
\ No newline at end of file
+pub mod file;
+pub mod color;
+pub mod colormap;
+pub(crate) mod font;
+pub mod svg;
+pub mod error;
+pub(crate) mod table;
+pub(crate) mod parallel;
+pub mod terminal;
+pub mod expression;
+pub mod data;
+pub mod snapshot;
+pub mod scale;
+pub mod time;
\ No newline at end of file
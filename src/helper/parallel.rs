@@ -0,0 +1,53 @@
+//! Helper file for parallel iteration
+//!
+//! With the `parallel` feature, this re-exports rayon's parallel iterators. Without it,
+//! the same methods (`par_iter`, `into_par_iter`, `flat_map_iter`) are provided as
+//! sequential fallbacks on standard iterators, so plotting code is the same either way.
+
+#[cfg(feature = "parallel")]
+pub(crate) use rayon::prelude::*;
+
+#[cfg(not(feature = "parallel"))]
+pub(crate) use sequential::*;
+
+#[cfg(not(feature = "parallel"))]
+mod sequential {
+    /// Sequential fallback for `rayon::iter::IntoParallelIterator`.
+    pub(crate) trait IntoParallelIterator {
+        type Iter: Iterator;
+
+        fn into_par_iter(self) -> Self::Iter;
+    }
+
+    impl<I: IntoIterator> IntoParallelIterator for I {
+        type Iter = I::IntoIter;
+
+        fn into_par_iter(self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// Sequential fallback for `rayon::iter::IntoParallelRefIterator`.
+    pub(crate) trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    impl<'a, I: 'a + ?Sized> IntoParallelRefIterator<'a> for I where &'a I: IntoIterator {
+        type Iter = <&'a I as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+
+    /// Sequential fallback for methods of `rayon::iter::ParallelIterator` which aren't on `Iterator`.
+    pub(crate) trait ParallelIterator: Iterator + Sized {
+        fn flat_map_iter<U: IntoIterator, F: FnMut(Self::Item) -> U>(self, f: F) -> std::iter::FlatMap<Self, U, F> {
+            self.flat_map(f)
+        }
+    }
+
+    impl<I: Iterator> ParallelIterator for I {}
+}
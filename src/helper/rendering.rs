@@ -1,14 +1,39 @@
-use std::{hash::{Hash, Hasher}, process::Command};
+//! Helper file for rendering text to images.
+//! 
+//! Text is drawn with the built-in bitmap font in `helper::font`, so no external programs are needed.
+
 use log::warn;
-use crate::helper::file::{save_to_file, get_current_dir};
+use crate::helper::parallel::*;
+use crate::helper::{
+    color::{cell_colors, split_cells, visible_char},
+    font::{glyph_mask, CELL_WIDTH, CELL_HEIGHT},
+};
+#[cfg(any(feature = "image-export", feature = "magick"))]
+use crate::helper::error::{PlotError, warn_on_err};
+#[cfg(feature = "image-export")]
+use crate::helper::file::try_save_image;
+#[cfg(feature = "magick")]
+use {
+    std::{hash::{Hash, Hasher}, process::Command},
+    crate::helper::file::{try_save_to_file, get_current_dir},
+};
 
 /// Builder struct for rendering text to an image.
 /// 
-/// `magick` must be installed.
+/// Any ANSI colors in the text are kept in the image.
+/// 
+/// # Options
+/// 
+/// * `font_path` - Font used by `save_with_magick`. Default is "DejaVu-Sans-Mono".
+/// * `font_color` - Color of uncolored text. Default is "white".
+/// * `font_size` - Height of a line of text, in pixels. Default is 24.
+/// * `background_color` - Color behind uncolored text. Default is "black".
 /// 
 /// # Notes
 /// 
-/// `font_color` and `background_color` must be an acceptable color definition for magick
+/// `font_color` and `background_color` can be a name (e.g. "white", "red", "gray"), or hex (e.g. "#ff8800" or "#f80").
+/// 
+/// The built-in font can only be scaled by whole numbers, so `font_size` is rounded to the nearest multiple of 16.
 pub struct RenderableTextBuilder<'a> {
     s: String,
     font_path: Option<&'a str>,
@@ -19,12 +44,51 @@ pub struct RenderableTextBuilder<'a> {
 
 struct RenderableText<'a> {
     s: String,
+    #[cfg_attr(not(feature = "magick"), allow(dead_code))]
     font_path: &'a str,
     font_color: &'a str,
     font_size: u32,
     background_color: &'a str,
 }
 
+/// Parses a color name or hex code into (r, g, b).
+/// 
+/// # Examples
+/// ```
+/// use cgrustplot::helper::rendering::parse_color;
+/// assert_eq!(parse_color("white"), Some((255, 255, 255)));
+/// assert_eq!(parse_color("#ff8000"), Some((255, 128, 0)));
+/// assert_eq!(parse_color("#f80"), Some((255, 136, 0)));
+/// assert_eq!(parse_color("not a color"), None);
+/// ```
+pub fn parse_color(s: &str) -> Option<(u8, u8, u8)> {
+    let s = s.trim().to_lowercase();
+
+    if let Some(hex) = s.strip_prefix('#') {
+        let digit = |i: usize, len: usize| u8::from_str_radix(hex.get(i..i + len)?, 16).ok();
+        return match hex.len() {
+            6 => Some((digit(0, 2)?, digit(2, 2)?, digit(4, 2)?)),
+            3 => Some((digit(0, 1)? * 17, digit(1, 1)? * 17, digit(2, 1)? * 17)),
+            _ => None,
+        };
+    }
+
+    match s.as_str() {
+        "black" => Some((0, 0, 0)),
+        "white" => Some((255, 255, 255)),
+        "red" => Some((255, 0, 0)),
+        "green" => Some((0, 128, 0)),
+        "lime" => Some((0, 255, 0)),
+        "blue" => Some((0, 0, 255)),
+        "yellow" => Some((255, 255, 0)),
+        "cyan" => Some((0, 255, 255)),
+        "magenta" => Some((255, 0, 255)),
+        "orange" => Some((255, 165, 0)),
+        "gray" | "grey" => Some((128, 128, 128)),
+        _ => None,
+    }
+}
+
 impl<'a> RenderableTextBuilder<'a> {
     pub fn from(s: String) -> Self {
         RenderableTextBuilder {
@@ -36,22 +100,22 @@ impl<'a> RenderableTextBuilder<'a> {
         }
     }
 
-    pub fn set_font_path<'b: 'a>(&mut self, font_path: &'b str) -> &Self {
+    pub fn set_font_path<'b: 'a>(&mut self, font_path: &'b str) -> &mut Self {
         self.font_path = Some(font_path);
         self
     }
 
-    pub fn set_font_color<'b: 'a>(&mut self, font_color: &'b str) -> &Self {
+    pub fn set_font_color<'b: 'a>(&mut self, font_color: &'b str) -> &mut Self {
         self.font_color = Some(font_color);
         self
     }
 
-    pub fn set_font_size(&mut self, font_size: u32) -> &Self {
+    pub fn set_font_size(&mut self, font_size: u32) -> &mut Self {
         self.font_size = Some(font_size);
         self
     }
 
-    pub fn set_background_color<'b: 'a>(&mut self, color: &'b str) -> &Self {
+    pub fn set_background_color<'b: 'a>(&mut self, color: &'b str) -> &mut Self {
         self.background_color = Some(color);
         self
     }
@@ -66,14 +130,84 @@ impl<'a> RenderableTextBuilder<'a> {
         }
     }
 
+    /// Returns the rendered image as a table of RGB values
+    pub fn render(&self) -> Vec<Vec<(u8, u8, u8)>> {
+        self.build().render()
+    }
+
     /// Saves an image to a file
+    #[cfg(feature = "image-export")]
     pub fn save(&self, path: &str) {
-        self.build().save(path);
+        warn_on_err(self.try_save(path));
+    }
+
+    /// Saves an image to a file, returning an error if it fails
+    #[cfg(feature = "image-export")]
+    pub fn try_save(&self, path: &str) -> Result<(), PlotError> {
+        self.build().save(path)
+    }
+
+    /// Saves an image to a file, using ImageMagick and a system font instead of the built-in font
+    /// 
+    /// `magick` must be installed, and colors must be an acceptable color definition for magick.
+    #[cfg(feature = "magick")]
+    pub fn save_with_magick(&self, path: &str) {
+        warn_on_err(self.try_save_with_magick(path));
+    }
+
+    /// Saves an image to a file using ImageMagick, returning an error if it fails
+    #[cfg(feature = "magick")]
+    pub fn try_save_with_magick(&self, path: &str) -> Result<(), PlotError> {
+        self.build().save_with_magick(path)
     }
 }
 
 impl<'a> RenderableText<'a> {
-    fn save(&self, path: &str) {
+    /// Parses a color option, falling back to a default if it isn't recognized.
+    fn color_or(&self, s: &str, default: (u8, u8, u8)) -> (u8, u8, u8) {
+        parse_color(s).unwrap_or_else(|| {
+            warn!("Unrecognized color '{s}'. Using {default:?}");
+            default
+        })
+    }
+
+    fn render(&self) -> Vec<Vec<(u8, u8, u8)>> {
+        let font_color = self.color_or(self.font_color, (255, 255, 255));
+        let background_color = self.color_or(self.background_color, (0, 0, 0));
+
+        let scale = ((self.font_size as f64 / CELL_HEIGHT as f64).round() as usize).max(1);
+        let (cw, ch) = (CELL_WIDTH * scale, CELL_HEIGHT * scale);
+
+        let lines: Vec<Vec<String>> = self.s.split('\n').map(split_cells).collect();
+        let width = lines.iter().map(|l| l.len()).max().unwrap_or(0).max(1) * cw;
+
+        lines.par_iter().flat_map_iter(|line| {
+            let mut rows = vec![vec![background_color; width]; ch];
+
+            for (i, cell) in line.iter().enumerate() {
+                let (fg, bg) = cell_colors(cell);
+                let fg = fg.to_rgb().unwrap_or(font_color);
+                let bg = bg.to_rgb().unwrap_or(background_color);
+                let mask = glyph_mask(visible_char(cell));
+
+                for (y, row) in rows.iter_mut().enumerate() {
+                    for x in 0..cw {
+                        row[i * cw + x] = if mask[y / scale][x / scale] {fg} else {bg};
+                    }
+                }
+            }
+
+            rows
+        }).collect()
+    }
+
+    #[cfg(feature = "image-export")]
+    fn save(&self, path: &str) -> Result<(), PlotError> {
+        try_save_image(&self.render(), path)
+    }
+
+    #[cfg(feature = "magick")]
+    fn save_with_magick(&self, path: &str) -> Result<(), PlotError> {
         let mut save_path = path;
         if path.len() > 255 {
             eprintln!("Maximum path length exceeded. Proceeding with truncated name.");
@@ -84,7 +218,7 @@ impl<'a> RenderableText<'a> {
         path.hash(&mut hasher);
 
         let temp_text_path = get_current_dir() + "temp_file_for_image_rendering_" + &((hasher.finish() & 0xFFFF_FFFF) as u32).to_string() + ".txt";
-        save_to_file(&self.s, &temp_text_path);
+        try_save_to_file(&self.s, &temp_text_path)?;
 
         let mut binding = Command::new("magick");
         let cmd = binding
@@ -99,8 +233,14 @@ impl<'a> RenderableText<'a> {
             .arg(format!("label:@{temp_text_path}"))
             .arg(save_path);
 
-        if let Err(e) = cmd.status() {warn!("{e}");}
+        let status = cmd.status();
         
         if let Err(e) = std::fs::remove_file(temp_text_path) {warn!("Could not remove temporary file during the creation of {path}: {e}")}
+
+        match status {
+            Ok(s) if s.success() => Ok(()),
+            Ok(s) => Err(PlotError::Command {program: "magick".to_string(), message: s.to_string()}),
+            Err(e) => Err(PlotError::Command {program: "magick".to_string(), message: e.to_string()}),
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+//! Helper file for axis scales, such as logarithmic axes
+//!
+//! Plots with a scale are drawn in "scaled space", where every value has been passed
+//! through `Scale::forward`. Axes labels are mapped back with `Scale::inverse`, so they
+//! still show the original values.
+//!
+//! Non-positive numbers have no logarithm, so points with them are left out of plots on
+//! `Log10` and `Log2` axes, with a warning. `Symlog` can show data on both sides of zero.
+//!
+//! # Example
+//! ```
+//! use cgrustplot::helper::scale::Scale;
+//!
+//! assert_eq!(Scale::Log10.forward(1000.), 3.);
+//! assert_eq!(Scale::Log2.inverse(3.), 8.);
+//! assert!(Scale::Log10.forward(-1.).is_nan());
+//! assert_eq!(Scale::Symlog(1.).forward(-9.), -1.);
+//! ```
+
+use std::f64::consts::LN_10;
+
+use log::warn;
+
+/// How values along an axis are spaced on a plot.
+///
+/// * `Linear` - Equal differences take equal space. This is the default.
+/// * `Log10` - Each power of 10 takes equal space. Only positive values can be shown.
+/// * `Log2` - Each power of 2 takes equal space. Only positive values can be shown.
+/// * `Symlog(threshold)` - Symmetric log, which is close to linear between -threshold and threshold, and logarithmic outside of it. Any value can be shown.
+///   The threshold must be positive; plots use a `Linear` scale in place of any other threshold.
+///
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Scale {
+    #[default]
+    Linear,
+    Log10,
+    Log2,
+    Symlog(f64),
+}
+
+impl Scale {
+    /// Maps a value into scaled space. Values which can't be shown on the scale are NaN.
+    pub fn forward(&self, v: f64) -> f64 {
+        match *self {
+            Scale::Linear => v,
+            Scale::Log10 => if v > 0. {v.log10()} else {f64::NAN},
+            Scale::Log2 => if v > 0. {v.log2()} else {f64::NAN},
+            Scale::Symlog(c) => v.signum() * (v.abs() / c).ln_1p() / LN_10,
+        }
+    }
+
+    /// Maps a value in scaled space back to the original value.
+    pub fn inverse(&self, t: f64) -> f64 {
+        match *self {
+            Scale::Linear => t,
+            Scale::Log10 => 10f64.powf(t),
+            Scale::Log2 => t.exp2(),
+            Scale::Symlog(c) => t.signum() * c * (t.abs() * LN_10).exp_m1(),
+        }
+    }
+
+    /// Whether the scale can only show positive values.
+    pub fn is_log(&self) -> bool {
+        matches!(self, Scale::Log10 | Scale::Log2)
+    }
+
+    /// Replaces a `Symlog` scale whose threshold isn't positive and finite with `Linear`, with a warning.
+    ///
+    /// Such thresholds would map every value to NaN or to 0.
+    pub(crate) fn validated(self) -> Scale {
+        match self {
+            Scale::Symlog(c) if !(c > 0. && c.is_finite()) => {
+                warn!("The Symlog threshold must be positive and finite, but is {c}. Using a Linear scale");
+                Scale::Linear
+            }
+            s => s,
+        }
+    }
+
+    /// Maps a (min, max) range into scaled space.
+    ///
+    /// Returns None, with a warning, if either end can't be shown on the scale.
+    pub(crate) fn forward_range(&self, range: (f64, f64), name: &str) -> Option<(f64, f64)> {
+        let o = (self.forward(range.0), self.forward(range.1));
+
+        if o.0.is_nan() || o.1.is_nan() {
+            warn!("The {name} {range:?} includes non-positive values, which can't be shown on a {self:?} scale. Using a computed {name}");
+            return None;
+        }
+
+        Some(o)
+    }
+
+    /// Maps a (min, max) range in scaled space back to the original values.
+    pub(crate) fn inverse_range(&self, range: (f64, f64)) -> (f64, f64) {
+        (self.inverse(range.0), self.inverse(range.1))
+    }
+
+    /// The matplotlib call which sets an axis ("x" or "y") to this scale.
+    #[cfg_attr(not(feature = "matplotlib"), allow(dead_code))]
+    pub(crate) fn pyplot_command(&self, axis: &str) -> String {
+        match *self {
+            Scale::Linear => format!("{axis}scale('linear')"),
+            Scale::Log10 => format!("{axis}scale('log', base=10)"),
+            Scale::Log2 => format!("{axis}scale('log', base=2)"),
+            Scale::Symlog(c) => format!("{axis}scale('symlog', linthresh={c})"),
+        }
+    }
+}
+
+/// Maps a point into scaled space, or `None` if it can't be shown on the scales.
+///
+/// Points which were already NaN are kept, as plots skip them anyway.
+fn scale_point((x, y): (f64, f64), scales: (Scale, Scale)) -> Option<(f64, f64)> {
+    let p = (scales.0.forward(x), scales.1.forward(y));
+    if (p.0.is_nan() && !x.is_nan()) || (p.1.is_nan() && !y.is_nan()) {
+        None
+    } else {
+        Some(p)
+    }
+}
+
+/// Maps points into scaled space as they are iterated over, without copying them.
+///
+/// Points which can't be shown on the scales are left out. Use `warn_unscaled_points` to say how many.
+pub(crate) fn scale_points(points: impl Iterator<Item = (f64, f64)>, scales: (Scale, Scale)) -> impl Iterator<Item = (f64, f64)> {
+    points.filter_map(move |p| scale_point(p, scales))
+}
+
+/// Warns if any points can't be shown on the scales, saying how many.
+///
+/// Every point can be shown on linear scales, so the points are only counted for other scales.
+pub(crate) fn warn_unscaled_points(points: impl Iterator<Item = (f64, f64)>, scales: (Scale, Scale)) {
+    if scales == (Scale::Linear, Scale::Linear) {
+        return;
+    }
+
+    let skipped = points.filter(|&p| scale_point(p, scales).is_none()).count();
+    if skipped > 0 {
+        warn!("Skipped {skipped} point(s) with non-positive values, which can't be shown on a log scale. Consider Scale::Symlog for data which crosses zero");
+    }
+}
@@ -0,0 +1,135 @@
+//! Helper file for snapshot testing plot output
+//!
+//! A snapshot is a text file holding the expected `as_string()` output of a plot.
+//! `assert_plot_snapshot!` compares a plot against its snapshot, and on a mismatch panics
+//! with a charachter-by-charachter diff of the lines which differ.
+//!
+//! To create or update snapshots, run the tests with the `UPDATE_SNAPSHOTS` environment
+//! variable set, e.g. `UPDATE_SNAPSHOTS=1 cargo test`. Snapshots which don't exist yet
+//! fail otherwise, so a missing file is never silently accepted.
+//!
+//! # Example
+//! ```no_run
+//! use cgrustplot::{assert_plot_snapshot, plots::array_plot::array_plot};
+//!
+//! let data = vec![vec![0, 1], vec![1, 0]];
+//! assert_plot_snapshot!(array_plot(&data).set_axes(false), "tests/snapshots/checkerboard.txt");
+//! ```
+
+use std::{env::var, fs, path::Path};
+
+use crate::helper::color::{split_cells, visible_char};
+
+/// Environment variable which, if set, makes snapshot assertions write their snapshot instead of comparing.
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UPDATE_SNAPSHOTS";
+
+/// Shows how one line differs, with a '^' under each charachter which changed.
+///
+/// Cells are compared with their colors, but only the visible charachters are shown.
+fn line_diff(i: usize, expected: Option<&str>, actual: Option<&str>) -> String {
+    let cells = |s: Option<&str>| s.map(split_cells).unwrap_or_default();
+    let (e, a) = (cells(expected), cells(actual));
+
+    let show = |s: Option<&str>, c: &[String]| match s {
+        Some(_) => c.iter().map(|c| visible_char(c)).collect::<String>(),
+        None => "<no line>".to_string(),
+    };
+
+    let markers: String = (0..e.len().max(a.len()))
+        .map(|j| if e.get(j) == a.get(j) {' '} else {'^'})
+        .collect();
+
+    format!(
+        "line {}:\n  expected: {}\n  actual:   {}\n            {}",
+        i + 1, show(expected, &e), show(actual, &a), markers.trim_end(),
+    )
+}
+
+/// Returns a readable diff of two plot strings, or None if they are the same.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::snapshot::snapshot_diff;
+///
+/// assert_eq!(snapshot_diff("ab\ncd", "ab\ncd"), None);
+/// assert_eq!(
+///     snapshot_diff("ab\ncd", "ab\nce").unwrap(),
+///     "line 2:\n  expected: cd\n  actual:   ce\n             ^\n(1 of 2 lines differ)",
+/// );
+/// ```
+pub fn snapshot_diff(expected: &str, actual: &str) -> Option<String> {
+    if expected == actual {
+        return None;
+    }
+
+    let e: Vec<&str> = expected.split('\n').collect();
+    let a: Vec<&str> = actual.split('\n').collect();
+    let lines = e.len().max(a.len());
+
+    let diffs: Vec<String> = (0..lines)
+        .filter(|i| e.get(*i) != a.get(*i))
+        .map(|i| line_diff(i, e.get(i).copied(), a.get(i).copied()))
+        .collect();
+
+    Some(format!("{}\n({} of {lines} lines differ)", diffs.join("\n"), diffs.len()))
+}
+
+/// Compares a plot string against the snapshot file at `path`, or writes it there if `update` is true.
+///
+/// Returns a message describing the problem if the snapshot is missing or different.
+/// Line endings in the snapshot file are normalized, so snapshots checked out with CRLF or edited
+/// to end with a newline still match.
+pub fn check_snapshot(actual: &str, path: &str, update: bool) -> Result<(), String> {
+    if update {
+        if let Some(dir) = Path::new(path).parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("Failed to create '{}': {e}", dir.display()))?;
+        }
+        return fs::write(path, actual).map_err(|e| format!("Failed to write snapshot '{path}': {e}"));
+    }
+
+    let expected = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read snapshot '{path}': {e}\nSet {UPDATE_SNAPSHOTS_VAR}=1 to create it."))?
+        .replace("\r\n", "\n");
+
+    // Editors often add a final newline, which plots don't end with
+    let expected = match expected.strip_suffix('\n') {
+        Some(e) if !actual.ends_with('\n') => e,
+        _ => &expected,
+    };
+
+    match snapshot_diff(expected, actual) {
+        None => Ok(()),
+        Some(diff) => Err(format!("Snapshot '{path}' does not match:\n{diff}\nSet {UPDATE_SNAPSHOTS_VAR}=1 to update it.")),
+    }
+}
+
+/// Panics if a plot string doesn't match the snapshot file at `path`.
+///
+/// If the `UPDATE_SNAPSHOTS` environment variable is set, the snapshot is written instead.
+/// Relative paths are from the current directory, which for `cargo test` is the package root.
+pub fn assert_snapshot(actual: &str, path: &str) {
+    if let Err(message) = check_snapshot(actual, path, var(UPDATE_SNAPSHOTS_VAR).is_ok()) {
+        panic!("{message}");
+    }
+}
+
+/// Asserts that a plot's `as_string()` output matches the snapshot file at a path.
+///
+/// On a mismatch, panics with a diff of the lines which differ.
+/// Run with `UPDATE_SNAPSHOTS=1` to create or update the snapshot.
+///
+/// # Example
+/// ```no_run
+/// use cgrustplot::{assert_plot_snapshot, plots::function_plot::function_plot};
+///
+/// let f = |x: f64| x * x;
+/// assert_plot_snapshot!(function_plot(&f).set_size((30, 8)), "tests/snapshots/parabola.txt");
+/// ```
+#[macro_export]
+macro_rules! assert_plot_snapshot {
+    ($plot:expr, $path:expr $(,)?) => {{
+        #[allow(unused_imports)]
+        use $crate::plots::traits::Plot as _;
+        $crate::helper::snapshot::assert_snapshot(&($plot).as_string(), $path)
+    }};
+}
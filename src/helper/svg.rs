@@ -0,0 +1,429 @@
+//! Helper file for drawing plots as SVG images.
+//! 
+//! Unlike `rendering`, which draws the text of a plot, an `SvgCanvas` draws the
+//! plotted data itself (lines, points, and filled rectangles) in the plot's own coordinates.
+//! Axes are labeled with the same ticks as the text axes from `helper::axes`.
+//! 
+//! Plot-specific implementations are avalible in each builder type, as `as_svg` and `save_svg`.
+//! 
+//! # Example
+//! ```
+//! use cgrustplot::helper::{svg::SvgCanvas, axes::AxesConfig, color::Color};
+//! 
+//! let mut canvas = SvgCanvas::new((30, 10), ((0., 1.), (0., 1.)));
+//! canvas.polyline(&[(0., 0.), (0.5, 1.), (1., 0.)], Color::RED);
+//! canvas.circle((0.5, 0.5), Color::BLUE);
+//! 
+//! let svg = canvas.finish(Some(&AxesConfig::default()), Some("Title"));
+//! assert!(svg.starts_with("<svg"));
+//! ```
+
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use crate::helper::{
+    axes::{axis_ticks, AxesConfig, LegendPosition},
+    color::Color,
+    scale::Scale,
+};
+
+/// Width of one charachter of a text plot, in SVG units.
+const COL_WIDTH: f64 = 10.;
+
+/// Height of one line of a text plot, in SVG units.
+const ROW_HEIGHT: f64 = 20.;
+
+/// Size of all text.
+const FONT_SIZE: f64 = 12.;
+
+/// Approximate width of one charachter of monospace text.
+const CHAR_WIDTH: f64 = 0.6 * FONT_SIZE;
+
+/// Space around the edges of the image, and between its parts.
+const MARGIN: f64 = 10.;
+
+/// Length of the tick marks on the axes.
+const TICK_LENGTH: f64 = 4.;
+
+/// Radius of a scatter point.
+const POINT_RADIUS: f64 = 2.;
+
+/// Stroke of the gridlines, which are light and dotted.
+const GRID_STYLE: &str = r#"stroke="lightgray" stroke-dasharray="2,2""#;
+
+/// Stroke of reference lines, which are dashed and darker than the grid.
+const REF_LINE_STYLE: &str = r#"stroke="gray" stroke-dasharray="6,3""#;
+
+/// Stands in for the clip path's id until the rest of the image is known. Text is escaped, so it can't contain this.
+const CLIP_ID: &str = "<clip-id>";
+
+/// The CSS color for a terminal color, where `Color::Default` is black.
+/// 
+/// # Examples
+/// ```
+/// use cgrustplot::helper::{svg::css_color, color::Color};
+/// assert_eq!(css_color(Color::TrueColor(1, 2, 3)), "rgb(1,2,3)");
+/// assert_eq!(css_color(Color::Default), "black");
+/// ```
+pub fn css_color(color: Color) -> String {
+    match color.to_rgb() {
+        Some((r, g, b)) => format!("rgb({r},{g},{b})"),
+        None => "black".to_string(),
+    }
+}
+
+/// Escapes text to be placed inside of an SVG element.
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// A plot area which data can be drawn onto, in the plot's own coordinates.
+/// 
+/// The plot area has the same proportions as a text plot of the same size.
+/// Anything drawn outside of the domain and range is clipped.
+pub struct SvgCanvas {
+    size: (u32, u32),
+    domain_and_range: ((f64, f64), (f64, f64)),
+    elements: Vec<String>,
+    legend: Option<(Vec<(Color, String)>, LegendPosition)>,
+    caption: Option<String>,
+}
+
+impl SvgCanvas {
+    /// Creates an empty canvas, for a plot of `size` charachters over `domain_and_range`.
+    pub fn new(size: (u32, u32), domain_and_range: ((f64, f64), (f64, f64))) -> Self {
+        SvgCanvas {
+            size,
+            domain_and_range,
+            elements: Vec::new(),
+            legend: None,
+            caption: None,
+        }
+    }
+
+    fn width(&self) -> f64 {
+        self.size.0 as f64 * COL_WIDTH
+    }
+
+    fn height(&self) -> f64 {
+        self.size.1 as f64 * ROW_HEIGHT
+    }
+
+    /// Converts a point in plot coordinates to SVG coordinates within the plot area.
+    fn to_svg(&self, p: (f64, f64)) -> (f64, f64) {
+        let ((x0, x1), (y0, y1)) = self.domain_and_range;
+        (
+            (p.0 - x0) / (x1 - x0) * self.width(),
+            (y1 - p.1) / (y1 - y0) * self.height(),
+        )
+    }
+
+    /// Draws a line through each point in order.
+    /// 
+    /// The line is broken wherever a point is not finite, such as at an asymptote.
+    pub fn polyline(&mut self, points: &[(f64, f64)], color: Color) {
+        for segment in points.split(|p| !p.0.is_finite() || !p.1.is_finite()) {
+            if segment.len() < 2 {
+                continue;
+            }
+
+            let coords = segment
+                .iter()
+                .map(|p| {
+                    let (x, y) = self.to_svg(*p);
+                    format!("{x:.2},{y:.2}")
+                })
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            self.elements.push(format!(r#"<polyline points="{coords}" fill="none" stroke="{}" stroke-width="1.5"/>"#, css_color(color)));
+        }
+    }
+
+    /// Draws a single point.
+    pub fn circle(&mut self, p: (f64, f64), color: Color) {
+        if !p.0.is_finite() || !p.1.is_finite() {
+            return;
+        }
+
+        let (x, y) = self.to_svg(p);
+        self.elements.push(format!(r#"<circle cx="{x:.2}" cy="{y:.2}" r="{POINT_RADIUS}" fill="{}"/>"#, css_color(color)));
+    }
+
+    /// Fills the rectangle between two opposite corners.
+    pub fn rect(&mut self, corner_1: (f64, f64), corner_2: (f64, f64), color: Color) {
+        let (ax, ay) = self.to_svg(corner_1);
+        let (bx, by) = self.to_svg(corner_2);
+
+        self.elements.push(format!(
+            r#"<rect x="{:.2}" y="{:.2}" width="{:.2}" height="{:.2}" fill="{}"/>"#,
+            ax.min(bx), ay.min(by), (ax - bx).abs(), (ay - by).abs(), css_color(color)
+        ));
+    }
+
+    /// Fills a table of cells spread evenly over the whole plot area, with row zero at the top.
+    /// 
+    /// Cells which are None are left empty. Neighbouring cells of the same color are drawn as a single rectangle.
+    pub fn cells(&mut self, cells: &[Vec<Option<Color>>]) {
+        let ((x0, x1), (y0, y1)) = self.domain_and_range;
+        let rows = cells.len();
+
+        for (r, row) in cells.iter().enumerate() {
+            let cols = row.len();
+            let x_at = |c: usize| x0 + c as f64 * (x1 - x0) / cols as f64;
+            let y_at = |r: usize| y1 - r as f64 * (y1 - y0) / rows as f64;
+
+            let mut start = 0;
+            for c in 1..=cols {
+                if c == cols || row[c] != row[start] {
+                    if let Some(color) = row[start] {
+                        self.rect((x_at(start), y_at(r)), (x_at(c), y_at(r + 1)), color);
+                    }
+                    start = c;
+                }
+            }
+        }
+    }
+
+    /// Fills the region where a predicate is true, sampled at the center of each cell of a grid.
+    pub fn region(&mut self, pred: impl Fn(f64, f64) -> bool, resolution: (u32, u32), color: Color) {
+        let ((x0, x1), (y0, y1)) = self.domain_and_range;
+        let (dx, dy) = ((x1 - x0) / resolution.0 as f64, (y1 - y0) / resolution.1 as f64);
+
+        let cells: Vec<Vec<Option<Color>>> = (0..resolution.1).rev().map(|j|
+            (0..resolution.0).map(|i|
+                pred(x0 + (i as f64 + 0.5) * dx, y0 + (j as f64 + 0.5) * dy).then_some(color)
+            ).collect()
+        ).collect();
+
+        self.cells(&cells);
+    }
+
+    /// Adds a legend with a colored marker next to each label.
+    pub fn legend(&mut self, entries: Vec<(Color, String)>, position: LegendPosition) {
+        self.legend = if entries.is_empty() {None} else {Some((entries, position))};
+    }
+
+    /// Adds a caption below the plot, its axes, and any legend.
+    pub fn caption(&mut self, caption: &str) {
+        self.caption = Some(caption.to_string());
+    }
+
+    /// Size of the legend's box.
+    fn legend_size(entries: &[(Color, String)]) -> (f64, f64) {
+        let label_len = entries.iter().map(|(_, l)| l.chars().count()).max().unwrap_or(0);
+        (3. * MARGIN + label_len as f64 * CHAR_WIDTH + 2. * CHAR_WIDTH, entries.len() as f64 * FONT_SIZE * 1.5 + MARGIN)
+    }
+
+    /// Draws the legend with its top left corner at (x, y).
+    fn legend_elements(entries: &[(Color, String)], (x, y): (f64, f64)) -> Vec<String> {
+        let (w, h) = Self::legend_size(entries);
+
+        let mut o = vec![format!(r#"<rect x="{x:.2}" y="{y:.2}" width="{w:.2}" height="{h:.2}" fill="white" stroke="black"/>"#)];
+
+        for (i, (color, label)) in entries.iter().enumerate() {
+            let row_y = y + MARGIN + (i as f64 + 0.5) * FONT_SIZE * 1.5;
+            o.push(format!(
+                r#"<line x1="{:.2}" y1="{row_y:.2}" x2="{:.2}" y2="{row_y:.2}" stroke="{}" stroke-width="3"/>"#,
+                x + MARGIN, x + MARGIN + 2. * CHAR_WIDTH, css_color(*color)
+            ));
+            o.push(format!(
+                r#"<text x="{:.2}" y="{:.2}">{}</text>"#,
+                x + 2. * MARGIN + 2. * CHAR_WIDTH, row_y + 0.35 * FONT_SIZE, escape_xml(label)
+            ));
+        }
+
+        o
+    }
+
+    /// The ticks on the horizontal axis, and whether their labels are drawn vertically because neighbouring labels would overlap.
+    fn x_ticks(&self, config: &AxesConfig) -> (Vec<(f64, String)>, bool) {
+        let ticks = axis_ticks(self.size.0 as usize, self.domain_and_range.0, false, config.x_scale, &config.x_ticks);
+
+        let vertical = ticks.windows(2).any(|t| {
+            let space = self.to_svg((t[1].0, 0.)).0 - self.to_svg((t[0].0, 0.)).0;
+            (t[0].1.chars().count() + t[1].1.chars().count()) as f64 * 0.5 * CHAR_WIDTH + CHAR_WIDTH > space
+        });
+
+        (ticks, vertical)
+    }
+
+    /// The ticks on the secondary axis, as (SVG y coordinate, label), which are empty without a secondary axis.
+    fn secondary_ticks(&self, config: &AxesConfig) -> Vec<(f64, String)> {
+        match config.secondary_range {
+            Some(r) => axis_ticks(self.size.1 as usize, r, true, Scale::Linear, &config.secondary_ticks)
+                .into_iter()
+                .map(|(value, label)| ((r.1 - value) / (r.1 - r.0) * self.height(), label))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    /// Draws gridlines at the ticks, and reference lines at the values set in the config, to go underneath the data.
+    fn background_elements(&self, config: &AxesConfig) -> Vec<String> {
+        let (w, h) = (self.width(), self.height());
+        let mut o = Vec::new();
+
+        let vertical_line = |x: f64, style: &str| format!(r#"<line x1="{x:.2}" y1="0" x2="{x:.2}" y2="{h:.2}" {style}/>"#);
+        let horizontal_line = |y: f64, style: &str| format!(r#"<line x1="0" y1="{y:.2}" x2="{w:.2}" y2="{y:.2}" {style}/>"#);
+
+        if config.grid {
+            for (value, _) in self.x_ticks(config).0 {
+                o.push(vertical_line(self.to_svg((value, 0.)).0, GRID_STYLE));
+            }
+            for (value, _) in axis_ticks(self.size.1 as usize, self.domain_and_range.1, true, config.y_scale, &config.y_ticks) {
+                o.push(horizontal_line(self.to_svg((0., value)).1, GRID_STYLE));
+            }
+        }
+
+        // Lines which aren't on the scale are left out
+        for v in config.x_lines.iter().map(|v| config.x_scale.forward(*v)).filter(|v| v.is_finite()) {
+            o.push(vertical_line(self.to_svg((v, 0.)).0, REF_LINE_STYLE));
+        }
+        for v in config.y_lines.iter().map(|v| config.y_scale.forward(*v)).filter(|v| v.is_finite()) {
+            o.push(horizontal_line(self.to_svg((0., v)).1, REF_LINE_STYLE));
+        }
+
+        o
+    }
+
+    /// Draws the axes, tick marks, and labels around the plot area.
+    fn axes_elements(&self, config: &AxesConfig) -> Vec<String> {
+        let (w, h) = (self.width(), self.height());
+        let color = css_color(config.color);
+
+        let mut o = vec![
+            format!(r#"<line x1="0" y1="{h:.2}" x2="{w:.2}" y2="{h:.2}" stroke="{color}"/>"#),
+            format!(r#"<line x1="0" y1="0" x2="0" y2="{h:.2}" stroke="{color}"/>"#),
+        ];
+
+        let (x_ticks, vertical) = self.x_ticks(config);
+        for (value, label) in x_ticks {
+            let x = self.to_svg((value, 0.)).0;
+            o.push(format!(r#"<line x1="{x:.2}" y1="{h:.2}" x2="{x:.2}" y2="{:.2}" stroke="{color}"/>"#, h + TICK_LENGTH));
+            if vertical {
+                let (lx, ly) = (x - 0.35 * FONT_SIZE, h + 2. * TICK_LENGTH);
+                o.push(format!(r#"<text x="{lx:.2}" y="{ly:.2}" transform="rotate(90 {lx:.2} {ly:.2})" fill="{color}">{}</text>"#, escape_xml(&label)));
+            } else {
+                o.push(format!(r#"<text x="{x:.2}" y="{:.2}" text-anchor="middle" fill="{color}">{}</text>"#, h + TICK_LENGTH + FONT_SIZE, escape_xml(&label)));
+            }
+        }
+
+        for (value, label) in axis_ticks(self.size.1 as usize, self.domain_and_range.1, true, config.y_scale, &config.y_ticks) {
+            let y = self.to_svg((0., value)).1;
+            o.push(format!(r#"<line x1="{:.2}" y1="{y:.2}" x2="0" y2="{y:.2}" stroke="{color}"/>"#, -TICK_LENGTH));
+            o.push(format!(r#"<text x="{:.2}" y="{:.2}" text-anchor="end" fill="{color}">{}</text>"#, -2. * TICK_LENGTH, y + 0.35 * FONT_SIZE, escape_xml(&label)));
+        }
+
+        if config.secondary_range.is_some() {
+            o.push(format!(r#"<line x1="{w:.2}" y1="0" x2="{w:.2}" y2="{h:.2}" stroke="{color}"/>"#));
+            for (y, label) in self.secondary_ticks(config) {
+                o.push(format!(r#"<line x1="{w:.2}" y1="{y:.2}" x2="{:.2}" y2="{y:.2}" stroke="{color}"/>"#, w + TICK_LENGTH));
+                o.push(format!(r#"<text x="{:.2}" y="{:.2}" fill="{color}">{}</text>"#, w + 2. * TICK_LENGTH, y + 0.35 * FONT_SIZE, escape_xml(&label)));
+            }
+        }
+
+        o
+    }
+
+    /// Returns the full SVG image, with optional axes and title.
+    pub fn finish(&self, axes: Option<&AxesConfig>, title: Option<&str>) -> String {
+        let (w, h) = (self.width(), self.height());
+
+        // Space needed for the axes labels
+        let y_label_len = match axes {
+            Some(config) => axis_ticks(self.size.1 as usize, self.domain_and_range.1, true, config.y_scale, &config.y_ticks).iter().map(|t| t.1.chars().count()).max().unwrap_or(0),
+            None => 0,
+        };
+        let y_title = axes.and_then(|config| config.y_label.as_deref());
+        let x_title = axes.and_then(|config| config.x_label.as_deref());
+        let secondary_label_len = axes.map(|config| self.secondary_ticks(config).iter().map(|t| t.1.chars().count()).max().unwrap_or(0)).unwrap_or(0);
+        let secondary_title = axes.filter(|config| config.secondary_range.is_some()).and_then(|config| config.secondary_label.as_deref());
+
+        let ticks_left = if axes.is_some() {y_label_len as f64 * CHAR_WIDTH + 2. * TICK_LENGTH} else {0.};
+        let left = MARGIN + ticks_left + if y_title.is_some() {FONT_SIZE * 1.5} else {0.};
+        let ticks_right = if axes.is_some_and(|config| config.secondary_range.is_some()) {secondary_label_len as f64 * CHAR_WIDTH + 2. * TICK_LENGTH} else {0.};
+        let axes_right = ticks_right + if secondary_title.is_some() {FONT_SIZE * 1.5} else {0.};
+        let ticks_bottom = match axes.map(|config| self.x_ticks(config)) {
+            Some((ticks, true)) => 2. * TICK_LENGTH + ticks.iter().map(|t| t.1.chars().count()).max().unwrap_or(0) as f64 * CHAR_WIDTH,
+            Some((_, false)) => TICK_LENGTH + FONT_SIZE * 1.5,
+            None => 0.,
+        };
+        let axes_bottom = ticks_bottom + if x_title.is_some() {FONT_SIZE * 1.5} else {0.};
+        let top = MARGIN + if title.is_some() {FONT_SIZE * 2.} else {0.};
+
+        // Space needed for a legend outside of the plot
+        let (mut right_extra, mut bottom_extra) = (0., 0.);
+        let mut legend = Vec::new();
+        if let Some((entries, position)) = &self.legend {
+            let (lw, lh) = Self::legend_size(entries);
+            let corner = match position {
+                LegendPosition::TopLeft => (MARGIN, MARGIN),
+                LegendPosition::TopRight => (w - lw - MARGIN, MARGIN),
+                LegendPosition::BottomLeft => (MARGIN, h - lh - MARGIN),
+                LegendPosition::BottomRight => (w - lw - MARGIN, h - lh - MARGIN),
+                LegendPosition::Right => (w + axes_right + MARGIN, 0.),
+                LegendPosition::Below => (0., h + axes_bottom + MARGIN),
+            };
+            if !position.is_inside() {
+                if *position == LegendPosition::Right {right_extra = lw + MARGIN} else {bottom_extra = lh + MARGIN}
+            }
+            legend = Self::legend_elements(entries, corner);
+        }
+
+        let total_w = left + w + axes_right + right_extra + MARGIN;
+        let caption_lines: Vec<&str> = self.caption.iter().flat_map(|c| c.lines()).collect();
+        let caption_top = top + h + axes_bottom + bottom_extra;
+
+        let total_h = caption_top + caption_lines.len() as f64 * FONT_SIZE * 1.5 + MARGIN;
+
+        let mut o = vec![
+            format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{total_w:.0}" height="{total_h:.0}" viewBox="0 0 {total_w:.2} {total_h:.2}" font-family="monospace" font-size="{FONT_SIZE}">"#),
+            r#"<rect width="100%" height="100%" fill="white"/>"#.to_string(),
+        ];
+
+        if let Some(t) = title {
+            o.push(format!(r#"<text x="{MARGIN}" y="{:.2}">{}</text>"#, MARGIN + FONT_SIZE, escape_xml(t)));
+        }
+
+        o.push(format!(r#"<clipPath id="{CLIP_ID}"><rect width="{w:.2}" height="{h:.2}"/></clipPath>"#));
+        o.push(format!(r#"<g transform="translate({left:.2},{top:.2})">"#));
+        o.push(format!(r#"<g clip-path="url(#{CLIP_ID})">"#));
+        if let Some(config) = axes {
+            o.extend(self.background_elements(config));
+        }
+        o.extend(self.elements.iter().cloned());
+        o.push("</g>".to_string());
+
+        if let Some(config) = axes {
+            o.extend(self.axes_elements(config));
+        }
+
+        // Axis titles, with the y label reading upwards
+        if let Some(label) = x_title {
+            o.push(format!(r#"<text x="{:.2}" y="{:.2}" text-anchor="middle">{}</text>"#, w / 2., h + ticks_bottom + FONT_SIZE, escape_xml(label)));
+        }
+        if let Some(label) = y_title {
+            let (lx, ly) = (-ticks_left - 0.5 * FONT_SIZE, h / 2.);
+            o.push(format!(r#"<text x="{lx:.2}" y="{ly:.2}" text-anchor="middle" transform="rotate(-90 {lx:.2} {ly:.2})">{}</text>"#, escape_xml(label)));
+        }
+        if let Some(label) = secondary_title {
+            let (lx, ly) = (w + ticks_right + 0.5 * FONT_SIZE, h / 2.);
+            o.push(format!(r#"<text x="{lx:.2}" y="{ly:.2}" text-anchor="middle" transform="rotate(90 {lx:.2} {ly:.2})">{}</text>"#, escape_xml(label)));
+        }
+
+        o.extend(legend);
+        o.push("</g>".to_string());
+
+        for (i, line) in caption_lines.iter().enumerate() {
+            o.push(format!(r#"<text x="{MARGIN}" y="{:.2}">{}</text>"#, caption_top + (i as f64 + 1.) * FONT_SIZE * 1.5, escape_xml(line)));
+        }
+
+        o.push("</svg>".to_string());
+
+        // The clip path's id is a hash of the image, so that different images inlined in one page don't share it
+        let svg = o.join("\n");
+        let mut hasher = DefaultHasher::new();
+        svg.hash(&mut hasher);
+        svg.replace(CLIP_ID, &format!("plot-area-{:016x}", hasher.finish()))
+    }
+}
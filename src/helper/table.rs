@@ -0,0 +1,200 @@
+//! Helper file for storing plot data without copying it
+//!
+//! Plots keep their input in one of these containers, which either borrow or own a
+//! `Vec`-based table, or (with the `ndarray` feature) borrow or own an ndarray.
+//! Rows are read one at a time, so large inputs are never copied as a whole.
+
+use std::borrow::Cow;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{CowArray, Ix2, Ix3};
+#[cfg(all(feature = "ndarray", feature = "animation"))]
+use ndarray::Ix4;
+
+/// A rectangular table of values, e.g. the data for an array plot.
+#[derive(Clone)]
+pub(crate) enum Table<'a, T: Clone> {
+    Rows(Cow<'a, [Vec<T>]>),
+    #[cfg(feature = "ndarray")]
+    Array(CowArray<'a, T, Ix2>),
+}
+
+impl<'a, T: Clone> Table<'a, T> {
+    /// Borrows the table, without copying it.
+    pub(crate) fn view(&self) -> Table<'_, T> {
+        match self {
+            Table::Rows(rows) => Table::Rows(Cow::Borrowed(rows)),
+            #[cfg(feature = "ndarray")]
+            Table::Array(arr) => Table::Array(arr.view().into()),
+        }
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        match self {
+            Table::Rows(rows) => rows.len(),
+            #[cfg(feature = "ndarray")]
+            Table::Array(arr) => arr.nrows(),
+        }
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        match self {
+            Table::Rows(rows) => rows.first().map_or(0, |r| r.len()),
+            #[cfg(feature = "ndarray")]
+            Table::Array(arr) => arr.ncols(),
+        }
+    }
+
+    /// The ith row. Only copied if the row isn't contiguous in memory.
+    pub(crate) fn row(&self, i: usize) -> Cow<'_, [T]> {
+        match self {
+            Table::Rows(rows) => Cow::Borrowed(&rows[i]),
+            #[cfg(feature = "ndarray")]
+            Table::Array(arr) => {
+                let row = arr.row(i);
+                match row.to_slice() {
+                    Some(s) => Cow::Borrowed(s),
+                    None => Cow::Owned(row.to_vec()),
+                }
+            }
+        }
+    }
+
+    pub(crate) fn rows(&self) -> Box<dyn Iterator<Item = Cow<'_, [T]>> + '_> {
+        Box::new((0..self.height()).map(|i| self.row(i)))
+    }
+
+    /// Every value in the table, row by row.
+    pub(crate) fn values(&self) -> Box<dyn Iterator<Item = &T> + '_> {
+        match self {
+            Table::Rows(rows) => Box::new(rows.iter().flatten()),
+            #[cfg(feature = "ndarray")]
+            Table::Array(arr) => Box::new(arr.iter()),
+        }
+    }
+}
+
+/// A list of (x, y) points, e.g. the data for a scatter or line plot.
+#[derive(Clone)]
+pub(crate) enum Points<'a, T: Clone> {
+    Pairs(Cow<'a, [(T, T)]>),
+    /// An array of shape (n, 2), where each row is a point.
+    #[cfg(feature = "ndarray")]
+    Array(CowArray<'a, T, Ix2>),
+}
+
+impl<'a, T: Clone> Points<'a, T> {
+    /// Borrows the points, without copying them.
+    pub(crate) fn view(&self) -> Points<'_, T> {
+        match self {
+            Points::Pairs(p) => Points::Pairs(Cow::Borrowed(p)),
+            #[cfg(feature = "ndarray")]
+            Points::Array(arr) => Points::Array(arr.view().into()),
+        }
+    }
+
+    pub(crate) fn iter(&self) -> Box<dyn Iterator<Item = (T, T)> + '_> {
+        match self {
+            Points::Pairs(p) => Box::new(p.iter().cloned()),
+            #[cfg(feature = "ndarray")]
+            Points::Array(arr) => Box::new(arr.rows().into_iter().map(|r| (r[0].clone(), r[1].clone()))),
+        }
+    }
+}
+
+/// A row of (R, G, B) pixels, borrowed when the image is stored as rows.
+pub(crate) type PixelRow<'a> = Cow<'a, [(u8, u8, u8)]>;
+
+/// A frame of an animation, stored as rows of (R, G, B) pixels.
+#[cfg(feature = "animation")]
+type FrameRows = Vec<Vec<(u8, u8, u8)>>;
+
+/// A rectangular table of (R, G, B) pixels.
+#[derive(Clone)]
+pub(crate) enum Pixels<'a> {
+    Rows(Cow<'a, [Vec<(u8, u8, u8)>]>),
+    /// An array of shape (height, width, 3).
+    #[cfg(feature = "ndarray")]
+    Array(CowArray<'a, u8, Ix3>),
+}
+
+impl<'a> Pixels<'a> {
+    /// Borrows the image, without copying it.
+    pub(crate) fn view(&self) -> Pixels<'_> {
+        match self {
+            Pixels::Rows(rows) => Pixels::Rows(Cow::Borrowed(rows)),
+            #[cfg(feature = "ndarray")]
+            Pixels::Array(arr) => Pixels::Array(arr.view().into()),
+        }
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        match self {
+            Pixels::Rows(rows) => rows.len(),
+            #[cfg(feature = "ndarray")]
+            Pixels::Array(arr) => arr.shape()[0],
+        }
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        match self {
+            Pixels::Rows(rows) => rows.first().map_or(0, |r| r.len()),
+            #[cfg(feature = "ndarray")]
+            Pixels::Array(arr) => arr.shape()[1],
+        }
+    }
+
+    /// The ith row of pixels. Rows of an ndarray are copied, as they are stored as separate channels.
+    pub(crate) fn row(&self, i: usize) -> PixelRow<'_> {
+        match self {
+            Pixels::Rows(rows) => Cow::Borrowed(&rows[i]),
+            #[cfg(feature = "ndarray")]
+            Pixels::Array(arr) => Cow::Owned(
+                arr.index_axis(ndarray::Axis(0), i).rows().into_iter().map(|p| (p[0], p[1], p[2])).collect()
+            ),
+        }
+    }
+
+    pub(crate) fn rows(&self) -> Box<dyn Iterator<Item = PixelRow<'_>> + '_> {
+        Box::new((0..self.height()).map(|i| self.row(i)))
+    }
+}
+
+/// A list of frames for an animation.
+#[cfg(feature = "animation")]
+#[derive(Clone)]
+pub(crate) enum Frames<'a> {
+    Rows(Cow<'a, [FrameRows]>),
+    /// An array of shape (frames, height, width, 3).
+    #[cfg(feature = "ndarray")]
+    Array(CowArray<'a, u8, Ix4>),
+}
+
+#[cfg(feature = "animation")]
+impl<'a> Frames<'a> {
+    /// Borrows the frames, without copying them.
+    pub(crate) fn view(&self) -> Frames<'_> {
+        match self {
+            Frames::Rows(frames) => Frames::Rows(Cow::Borrowed(frames)),
+            #[cfg(feature = "ndarray")]
+            Frames::Array(arr) => Frames::Array(arr.view().into()),
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        match self {
+            Frames::Rows(frames) => frames.len(),
+            #[cfg(feature = "ndarray")]
+            Frames::Array(arr) => arr.shape()[0],
+        }
+    }
+
+    /// The ith frame, borrowed from the animation.
+    pub(crate) fn frame(&self, i: usize) -> Pixels<'_> {
+        match self {
+            Frames::Rows(frames) => Pixels::Rows(Cow::Borrowed(&frames[i])),
+            #[cfg(feature = "ndarray")]
+            Frames::Array(arr) => Pixels::Array(arr.index_axis(ndarray::Axis(0), i).into()),
+        }
+    }
+}
@@ -0,0 +1,121 @@
+//! Helper file for fitting plots to the current terminal
+//!
+//! The terminal size is read with an ioctl on the standard output (on unix), falling back
+//! to the `COLUMNS` and `LINES` environment variables. If neither is available, plots
+//! keep their usual size.
+
+use crate::helper::axes::{axes_margin, labels_margin};
+
+/// Returns the (columns, lines) of the current terminal, if it can be determined.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::terminal::terminal_size;
+///
+/// if let Some((columns, lines)) = terminal_size() {
+///     println!("The terminal is {columns}x{lines} characters");
+/// }
+/// ```
+pub fn terminal_size() -> Option<(u32, u32)> {
+    ioctl_size().or_else(env_size)
+}
+
+/// Reads the terminal size from the `COLUMNS` and `LINES` environment variables.
+fn env_size() -> Option<(u32, u32)> {
+    let read = |name: &str| std::env::var(name).ok()?.trim().parse::<u32>().ok().filter(|n| *n > 0);
+    Some((read("COLUMNS")?, read("LINES")?))
+}
+
+/// Reads the terminal size with the TIOCGWINSZ ioctl, trying stdout, stderr, then stdin.
+#[cfg(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd", target_os = "dragonfly"))]
+fn ioctl_size() -> Option<(u32, u32)> {
+    use std::os::raw::{c_int, c_ulong};
+
+    #[repr(C)]
+    struct WinSize {
+        rows: u16,
+        columns: u16,
+        x_pixels: u16,
+        y_pixels: u16,
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    const TIOCGWINSZ: c_ulong = 0x5413;
+    #[cfg(not(any(target_os = "linux", target_os = "android")))]
+    const TIOCGWINSZ: c_ulong = 0x40087468;
+
+    extern "C" {
+        fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+    }
+
+    [1, 2, 0].into_iter().find_map(|fd| {
+        let mut size = WinSize {rows: 0, columns: 0, x_pixels: 0, y_pixels: 0};
+
+        // SAFETY: TIOCGWINSZ only writes a winsize struct to the pointer, which matches WinSize's layout.
+        let result = unsafe { ioctl(fd, TIOCGWINSZ, &mut size as *mut WinSize) };
+
+        (result == 0 && size.columns > 0 && size.rows > 0).then_some((size.columns as u32, size.rows as u32))
+    })
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android", target_os = "macos", target_os = "ios", target_os = "freebsd", target_os = "openbsd", target_os = "netbsd", target_os = "dragonfly")))]
+fn ioctl_size() -> Option<(u32, u32)> {
+    None
+}
+
+/// The largest plot size which fits in a terminal of the given (columns, lines),
+/// once axes and a title are added.
+///
+/// One line is left free for the shell prompt. The result is at least (1, 1).
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::terminal::fit_size;
+///
+/// assert_eq!(fit_size((80, 24), false, false), (80, 23));
+/// assert_eq!(fit_size((80, 24), true, true), (66, 20));
+/// ```
+pub fn fit_size(terminal: (u32, u32), axes: bool, title: bool) -> (u32, u32) {
+    let (margin_x, margin_y) = if axes {axes_margin()} else {(0, 0)};
+
+    (
+        terminal.0.saturating_sub(margin_x).max(1),
+        terminal.1.saturating_sub(margin_y + title as u32 + 1).max(1),
+    )
+}
+
+/// The plot size which fits the current terminal, if its size can be determined.
+///
+/// Also leaves room for every line of a caption, and for axis titles if there are axes.
+pub(crate) fn fit_terminal_size(axes: bool, title: bool, caption: Option<&str>, labels: (Option<&str>, Option<&str>)) -> Option<(u32, u32)> {
+    let caption_rows = caption.map_or(0, |c| c.lines().count() as u32);
+
+    terminal_size().map(|(cols, rows)| {
+        let fit = |(x, y): (u32, u32)| fit_size((cols.saturating_sub(x), rows.saturating_sub(y + caption_rows)), axes, title);
+        if !axes {
+            return fit((0, 0));
+        }
+
+        // Whether the y label fits vertically depends on the height it leaves
+        let size = fit(labels_margin(labels.0, labels.1, usize::MAX));
+        fit(labels_margin(labels.0, labels.1, size.1 as usize))
+    })
+}
+
+/// Escape codes which move the cursor back to the start of the last `lines` printed lines,
+/// and clear everything from there to the end of the screen.
+///
+/// # Examples
+/// ```
+/// use cgrustplot::helper::terminal::clear_lines;
+///
+/// assert_eq!(clear_lines(3), "\x1b[3A\r\x1b[J");
+/// assert_eq!(clear_lines(0), "\r\x1b[J");
+/// ```
+pub fn clear_lines(lines: usize) -> String {
+    if lines == 0 {
+        "\r\x1b[J".to_string()
+    } else {
+        format!("\x1b[{lines}A\r\x1b[J")
+    }
+}
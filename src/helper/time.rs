@@ -0,0 +1,212 @@
+//! Helper file for time axes, where values are Unix timestamps
+//!
+//! Timestamps are seconds since 1970-01-01 00:00:00 UTC, as an f64, so they can be
+//! plotted like any other number. `Ticks::Time` places ticks on calendar boundaries
+//! (whole seconds, minutes, hours, days, months, or years), and labels them with
+//! the shortest format which tells them apart.
+//!
+//! All times are in UTC.
+//!
+//! # Example
+//! ```
+//! use std::time::{Duration, UNIX_EPOCH};
+//! use cgrustplot::helper::time::{timestamp, format_time};
+//!
+//! let t = UNIX_EPOCH + Duration::from_secs(1_772_546_700);
+//!
+//! assert_eq!(timestamp(t), 1_772_546_700.);
+//! assert_eq!(format_time(timestamp(t)), "2026-03-03 14:05:00");
+//! ```
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const MINUTE: i64 = 60;
+const HOUR: i64 = 60 * MINUTE;
+const DAY: i64 = 24 * HOUR;
+
+const MONTH_NAMES: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+
+/// Converts a time to a Unix timestamp, in seconds. Times before 1970 are negative.
+pub fn timestamp(t: SystemTime) -> f64 {
+    match t.duration_since(UNIX_EPOCH) {
+        Ok(d) => d.as_secs_f64(),
+        Err(e) => -e.duration().as_secs_f64(),
+    }
+}
+
+/// Converts timestamped data to points, with each time as a Unix timestamp.
+///
+/// # Example
+/// ```
+/// use std::time::{Duration, UNIX_EPOCH};
+/// use cgrustplot::helper::time::time_points;
+///
+/// let data = [(UNIX_EPOCH, 1.), (UNIX_EPOCH + Duration::from_secs(60), 2.)];
+/// assert_eq!(time_points(&data), vec![(0., 1.), (60., 2.)]);
+/// ```
+pub fn time_points(data: &[(SystemTime, f64)]) -> Vec<(f64, f64)> {
+    data.iter().map(|(t, y)| (timestamp(*t), *y)).collect()
+}
+
+/// Days since 1970-01-01 of a (year, month, day) date, where months and days start at 1.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    // Years start in March, so the leap day is last
+    let y = if m <= 2 {y - 1} else {y};
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (m as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// The (year, month, day) date of a number of days since 1970-01-01.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 {mp + 3} else {mp - 9} as u32;
+    (yoe + era * 400 + (m <= 2) as i64, m, d)
+}
+
+/// The (year, month, day, seconds into the day) of a timestamp.
+fn split_time(t: i64) -> (i64, u32, u32, i64) {
+    let (y, m, d) = civil_from_days(t.div_euclid(DAY));
+    (y, m, d, t.rem_euclid(DAY))
+}
+
+/// Formats a timestamp as "YYYY-MM-DD hh:mm:ss", rounded down to the second.
+pub fn format_time(t: f64) -> String {
+    let (y, m, d, s) = split_time(t.floor() as i64);
+    format!("{y:04}-{m:02}-{d:02} {:02}:{:02}:{:02}", s / HOUR, s % HOUR / MINUTE, s % MINUTE)
+}
+
+/// Spacing between neighbouring time ticks.
+#[derive(Clone, Copy, Debug)]
+enum TimeStep {
+    /// A fixed number of seconds, counted from midnight.
+    Seconds(i64),
+    /// The given days of every month.
+    DaysOfMonth(&'static [u32]),
+    /// Every few months, counted from January.
+    Months(u32),
+    /// Every few years, counted from year 0.
+    Years(i64),
+}
+
+/// Every step time ticks can use, from smallest to largest.
+const TIME_STEPS: [TimeStep; 25] = [
+    TimeStep::Seconds(1),
+    TimeStep::Seconds(2),
+    TimeStep::Seconds(5),
+    TimeStep::Seconds(10),
+    TimeStep::Seconds(15),
+    TimeStep::Seconds(30),
+    TimeStep::Seconds(MINUTE),
+    TimeStep::Seconds(2 * MINUTE),
+    TimeStep::Seconds(5 * MINUTE),
+    TimeStep::Seconds(10 * MINUTE),
+    TimeStep::Seconds(15 * MINUTE),
+    TimeStep::Seconds(30 * MINUTE),
+    TimeStep::Seconds(HOUR),
+    TimeStep::Seconds(2 * HOUR),
+    TimeStep::Seconds(3 * HOUR),
+    TimeStep::Seconds(6 * HOUR),
+    TimeStep::Seconds(12 * HOUR),
+    TimeStep::Seconds(DAY),
+    TimeStep::Seconds(2 * DAY),
+    TimeStep::DaysOfMonth(&[1, 8, 15, 22]),
+    TimeStep::DaysOfMonth(&[1, 15]),
+    TimeStep::Months(1),
+    TimeStep::Months(2),
+    TimeStep::Months(3),
+    TimeStep::Months(6),
+];
+
+impl TimeStep {
+    /// The steps after `TIME_STEPS`, every 1, 2, or 5 times a power of 10 years.
+    fn years() -> impl Iterator<Item = TimeStep> {
+        (0..6).flat_map(|p| [1, 2, 5].map(|m| TimeStep::Years(m * 10i64.pow(p))))
+    }
+
+    /// Roughly how many seconds apart neighbouring ticks are.
+    fn seconds(&self) -> i64 {
+        match *self {
+            TimeStep::Seconds(s) => s,
+            TimeStep::DaysOfMonth(days) => 28 * DAY / days.len() as i64,
+            TimeStep::Months(k) => k as i64 * 28 * DAY,
+            TimeStep::Years(k) => k * 365 * DAY,
+        }
+    }
+
+    /// Every tick between two timestamps.
+    fn ticks(&self, from: i64, to: i64) -> Vec<i64> {
+        let (y0, _, _, _) = split_time(from);
+        let (y1, _, _, _) = split_time(to);
+        let month_starts = || (y0..=y1).flat_map(|y| (1..=12).map(move |m| (y, m)));
+
+        let o: Vec<i64> = match *self {
+            TimeStep::Seconds(s) => (from.div_euclid(s)..=to.div_euclid(s)).map(|k| k * s).collect(),
+            TimeStep::DaysOfMonth(days) => month_starts().flat_map(|(y, m)| days.iter().map(move |d| days_from_civil(y, m, *d) * DAY)).collect(),
+            TimeStep::Months(k) => month_starts().filter(|(_, m)| (m - 1) % k == 0).map(|(y, m)| days_from_civil(y, m, 1) * DAY).collect(),
+            TimeStep::Years(k) => (y0.div_euclid(k)..=y1.div_euclid(k)).map(|i| days_from_civil(i * k, 1, 1) * DAY).collect(),
+        };
+
+        o.into_iter().filter(|t| (from..=to).contains(t)).collect()
+    }
+
+    /// The label for a tick, showing the largest calendar unit which changes there.
+    fn label(&self, t: i64) -> String {
+        let (y, m, d, s) = split_time(t);
+        let month = MONTH_NAMES[m as usize - 1];
+
+        match *self {
+            TimeStep::Seconds(step) if step < MINUTE => format!("{:02}:{:02}:{:02}", s / HOUR, s % HOUR / MINUTE, s % MINUTE),
+            TimeStep::Seconds(step) if step < DAY && s != 0 => format!("{:02}:{:02}", s / HOUR, s % HOUR / MINUTE),
+            TimeStep::Years(_) => y.to_string(),
+            _ if m == 1 && d == 1 => y.to_string(),
+            TimeStep::Months(_) => month.to_string(),
+            _ => format!("{month} {d:02}"),
+        }
+    }
+}
+
+/// Picks calendar-aware ticks for an axis n charachters long, covering a range of Unix timestamps.
+///
+/// The smallest step is used whose labels all fit before the next tick, or with a free charachter
+/// between ticks on a vertical axis. `position` gives the charachter each timestamp falls in.
+///
+/// Returns the (timestamp, label) of each tick, which is empty if no step fits.
+pub(crate) fn time_ticks(n: usize, range: (f64, f64), vertical: bool, position: impl Fn(f64) -> usize) -> Vec<(f64, String)> {
+    if n == 0 || !(range.0.is_finite() && range.1.is_finite()) || range.0 >= range.1 {
+        return Vec::new();
+    }
+
+    let (from, to) = (range.0.ceil() as i64, range.1.floor() as i64);
+
+    for step in TIME_STEPS.into_iter().chain(TimeStep::years()) {
+        // Too many ticks to fit, even with no labels
+        if (to - from) / step.seconds() > n as i64 {
+            continue;
+        }
+
+        let ticks = step.ticks(from, to);
+        let labels: Vec<(f64, String)> = ticks.into_iter().map(|t| (t as f64, step.label(t))).collect();
+        let positions: Vec<usize> = labels.iter().map(|(t, _)| position(*t)).collect();
+
+        let fits = positions.windows(2).zip(labels.iter()).all(|(w, (_, l))| {
+            let room = w[1].saturating_sub(w[0]);
+            if vertical {room >= 2} else {room > l.chars().count()}
+        });
+
+        if !labels.is_empty() && fits {
+            return labels;
+        }
+    }
+
+    Vec::new()
+}
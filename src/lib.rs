@@ -5,11 +5,47 @@
 //! # Basic Syntax / Example Usage
 //! 
 //! Each plot file contains a public function by the name of that plot. For example,
-//! `pub fn array_plot<T>(data: &Vec<Vec<T>>) -> ArrayPlotBuilder<T>`
+//! `pub fn array_plot<'a, T>(data: impl Into<Cow<'a, [Vec<T>]>>) -> ArrayPlotBuilder<'a, T>`
 //! creates an ArrayPlotBuilder instance.
 //! 
+//! Data can be borrowed (`&Vec<_>`, `&[_]`) or owned (`Vec<_>`), and builders can also be collected from an iterator (e.g. `points.into_iter().collect::<LinePlotBuilder>()`). A builder which owns its data is `'static + Send`, so it can be returned from functions or sent to other threads.
+//! 
+//! With the `ndarray` cargo feature, array, scatter, line, image and animation plots can also be created directly from an `Array2`, `Array3<u8>` or `Array4<u8>` (or a view of one) with the `*_from_ndarray` constructors, e.g. `array_plot_from_ndarray(arr.view())`. The array is not copied into a `Vec` first.
+//! 
 //! You can then set options for it such as title, axes, output size, and more (depending on the type of plot). Finally, you can call .print() or .as_string() or .pyplot() to print it to the standard output, or return the plot as a string, or display an interactive window with matplotlib, respectively.
 //! 
+//! Instead of a fixed size, function, line, scatter, region and figure plots can be sized to fit the terminal with .set_fit_terminal(true). The terminal's dimensions are read when the plot is drawn, and room is left for the axes and title.
+//! 
+//! For data which changes over time, such as a training loss, `live_plot()` redraws a plot in place in the terminal instead of printing a new one each time. Its .update(plot) only redraws at the set refresh rate, so it can be called on every new value.
+//! 
+//! The crate also includes a `rustplot` command-line tool (`cargo install cgrustplot`) for plotting data from shell scripts, e.g. `cat data.csv | rustplot scatter --x 1 --y 3 --size 80x20`. It has subcommands for scatter, line, array, image, function and region plots, with functions and regions given as expressions like `--expr "x^2 + y^2 < 1"`. Run `rustplot --help` for all options.
+//! 
+//! To read plot data from CSV, TSV or whitespace-separated files, use `helper::data::data_reader()`. It detects the delimiter and header line, reads missing cells as NaN, and converts the table into (x, y) points, a matrix for array plots, or one series per value of a key column. Cells which aren't numbers give a `PlotError::Parse` with their line number.
+//! 
+//! For golden-file tests, `assert_plot_snapshot!(plot, "tests/snapshots/name.txt")` compares a plot's text output against a stored file, and on a mismatch shows which characters changed on each line. Run the tests with `UPDATE_SNAPSHOTS=1` to create or update the stored files.
+//! 
+//! Scatter, line and function plots can use a log or symmetric log scale on either axis, e.g. .set_y_scale(Scale::Log10) with `Scale` from `helper::scale`. The axes labels still show the original values. Points with non-positive values can't be shown on a log axis, so they are left out with a warning; `Scale::Symlog` works for data which crosses zero.
+//! 
+//! By default, axes ticks are evenly spaced and labelled with whatever value falls there, such as `0.5000 7.5000 14.500`. For round labels such as `0 5 10 15`, use .set_x_ticks(Ticks::Nice) and .set_y_ticks(Ticks::Nice) with `Ticks` from `helper::axes`, which place ticks at steps of 1, 2 or 5 times a power of 10.
+//! 
+//! Ticks can also be given by hand, such as .set_x_ticks(Ticks::custom([(0., "0"), (PI, "π")])), or label categories for string-keyed data: `categorical_points(&[("Mon", 3.), ("Tue", 5.)])` returns the points to plot along with their `Ticks::Categories`. Labels which don't fit next to each other are drawn vertically.
+//! 
+//! Axes can be titled with .set_x_label("Time (s)") and .set_y_label("Distance"). The x label is centered under the axis, and the y label runs down beside it, or sits above it on plots too short to fit it. A caption, such as a source or footnote, can be added below any plot with .set_caption(text).
+//! 
+//! For time-series data, use Unix timestamps (in seconds) as values, converting from `SystemTime` with `helper::time::time_points`, and .set_x_ticks(Ticks::Time). Ticks are placed on whole seconds, minutes, hours, days, months or years to suit the range, and labelled like `14:05`, `Mar 03` or `2026`, in UTC.
+//! 
+//! To make plots easier to read, .set_grid(true) draws dotted gridlines at every tick, and .set_x_lines(vec![0.]) and .set_y_lines(vec![0.]) draw dashed reference lines at the given values, such as the axes through the origin. Both are drawn underneath the data.
+//! 
+//! In a figure plot, calling .set_secondary_axis(true) after adding a series draws it against a second y axis on the right of the plot, with its own range, ticks and title (.set_secondary_range, .set_secondary_ticks and .set_secondary_label). This shows two quantities with different units over the same domain.
+//! 
+//! Plots can also be saved as text with .save(path), or rendered to an image with .as_image().save(path). Images are drawn with a built-in bitmap font, so no external programs are needed, and any colors in the plot are kept.
+//! 
+//! For crisp vector output, such as for embedding in HTML, most plots also have .as_svg() and .save_svg(path). These draw the plotted data itself (lines, points, and filled cells) rather than its text, with the same axes labels as the text plot.
+//! 
+//! If saving or displaying a plot fails, it is only logged with warn!(). To detect failures, use the try_ versions (e.g. .try_save(path), .try_save_svg(path), .try_pyplot()), which return a Result<(), PlotError>.
+//! 
+//! Optional dependencies are behind cargo features, all enabled by default: `image-export` (saving rendered images, via the `image` crate), `parallel` (multithreaded rendering, via `rayon`), `matplotlib` (the .pyplot() methods), `magick` (saving with ImageMagick) and `animation` (animation plots). For a lean, text-only build, use `default-features = false`; plots are then computed sequentially, with the same output.
+//! 
 //! For example:
 //! ```
 //! use cgrustplot::{
@@ -46,6 +82,25 @@
 //!       └┼──────┼──────┼──────┼────────
 //!        0.5000 7.5000 14.500 21.500   
 //! ```
+//! # Colors
+//! Plots are plain text by default. To color them with ANSI escape codes, use `set_color` on a plot (or on a single series of a figure), `set_axes_color` for the axes and their labels, or `set_colors` to spread colors across the shades of an array plot. Colors are defined in `cgrustplot::helper::color`, and can be any of the 16 standard terminal colors, the 256 extended colors, or a 24-bit truecolor.
+//! 
+//! Image plots can be displayed in full color with `set_render_mode(RenderMode::TrueColor)`, or `RenderMode::Ansi256` for terminals without 24-bit color.
+//! 
+//! To turn a table of numbers into a heatmap, `cgrustplot::helper::colormap` has the standard colormaps (`viridis`, `magma`, `inferno`, `plasma`, `coolwarm`, and `grayscale`) as well as custom gradients. `apply_colormap` converts a `Vec<Vec<f64>>` into an image for `image_plot`, and `Colormap::colors(n)` gives colors for `array_plot(..).set_colors(..)`.
+//! 
+//! ```
+//! use cgrustplot::{
+//!     plots::function_plot::function_plot,
+//!     helper::color::Color,
+//! };
+//! 
+//! function_plot(&|x: f64| x.sin())
+//!     .set_color(Color::RED)
+//!     .set_axes_color(Color::Ansi256(244))
+//!     .print();
+//! ```
+//! 
 //! # Plot Types
 //! In no particular order, here are some of the various types of plots that can be used
 //! (more plots than listed here may already be available. Check the /src/plots/ for a full list while in development).
@@ -65,6 +120,8 @@
 //! 
 //! `chars: Vec<String>` The character set to be used. Defaults are based on `rustplot::helper::charset::gradient_chars`, depending on the number of distinct values in the table.
 //! 
+//! `colors: Vec<Color>` Colors spread across the character set, from the lowest to the highest value. Default is uncolored.
+//! 
 //! `bins: Option<u32>` Only for plots of f64. It bins close-together datapoints and plots based on those. Not so much a plot option as much as a transformation, as it actually creates a new struct of `<u32>` instead of `<f64>`.
 //! 
 //! ### Example
@@ -192,16 +249,16 @@
 //! 
 //! Output:
 //! ```text
-//!       │⠔⡈⢀⠀⠂⠀⠀⠂⠀⠀⠀⠀⠀⠀⠀⡀⠀⠀⠂⢁⠀⠀⠂⠀⠀⠀⡀⠀⠀⠄
-//! 25.50 ┼⠀⠀⠀⠀⠀⠈⠀⠀⠀⠀⢀⠀⠀⠁⠐⠀⠁⡀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠐
-//!       │⠀⠀⠀⠄⠀⠄⠀⠠⠀⠀⡀⠈⠀⠈⠀⠀⠀⠀⠠⠀⠀⠀⠈⠀⠀⠀⠀⠀⠀⠀
-//! 19.50 ┼⠄⠀⠁⠀⠀⠀⠁⠀⠡⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠀⠀⠀⠀⠈⠀⠀⠐⠀⠀
-//!       │⠀⠂⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠀⠀⠀⠀⠀⠀⡀⠀⠀⠀⠀⠀⠀⠀⠂⠀⠀
-//! 13.50 ┼⢀⠀⠀⠀⠀⠁⠣⠀⠀⠀⠠⠄⠀⠀⠰⠀⠀⢂⠀⠀⠀⠀⠀⠁⠀⠀⣀⠀⠀⠀
-//!       │⠠⢀⠀⠀⠅⠠⠀⠀⠐⠀⠀⠀⠀⠀⠀⠀⠀⠁⠈⠀⠀⠀⠀⠠⠀⠐⠀⣀⠠⠠
-//! 7.500 ┼⠀⠀⠈⠂⠀⠀⢀⠡⠀⠀⠀⠀⠀⠀⠀⠠⠀⠀⠀⠀⡀⠠⠀⠀⠀⠀⠀⠀⠀⠀
-//!       │⠀⠐⠀⠀⠐⠀⠀⠀⠀⠀⣀⠀⠀⠀⠀⠀⠀⠀⠀⠄⠀⠀⢀⠄⠁⠄⠄⠀⠀⠀
-//! 1.500 ┼⠀⢀⡀⠄⠁⠀⢀⠀⠀⠀⠀⠀⠀⠈⠀⢀⠀⠀⠀⠀⠀⢀⠄⠀⠀⠁⠀⠀⠀⠀
+//!       │⠀⠈⠁⠂⡀⠀⠈⠀⠀⠀⠀⠀⠀⢀⠀⠈⠀⠀⠀⠀⠀⠈⠂⠀⠀⡀⠀⠀⠀⠀
+//! 25.50 ┼⠀⠠⠀⠀⠠⠀⠀⠀⠀⠀⠉⠀⠀⠀⠀⠀⠀⠀⠀⠂⠀⠀⠈⠂⡀⠂⠂⠀⠀⠀
+//!       │⠀⠀⢀⠄⠀⠀⠈⡐⠀⠀⠀⠀⠀⠀⠀⠐⠀⠀⠀⠀⠁⠐⠀⠀⠀⠀⠀⠀⠀⠀
+//! 19.50 ┼⠐⠈⠀⠀⡂⠐⠀⠀⠠⠀⠀⠀⠀⠀⠀⠀⠀⡀⢀⠀⠀⠀⠀⠐⠀⠠⠀⠉⠐⠐
+//!       │⠈⠀⠀⠀⠀⡀⡔⠀⠀⠀⠐⠂⠀⠀⠰⠀⠀⠌⠀⠀⠀⠀⠀⡀⠀⠀⠉⠀⠀⠀
+//! 13.50 ┼⠀⠄⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠈⠀⠀⠀⠀⠀⠀⠁⠀⠀⠀⠀⠀⠀⠀⠄⠀⠀
+//!       │⠂⠀⡀⠀⠀⠀⡀⠀⡐⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⢀⠀⠀⠀⠀⢀⠀⠀⠠⠀⠀
+//! 7.500 ┼⠀⠀⠀⠂⠀⠂⠀⠐⠀⠀⠁⢀⠀⢀⠀⠀⠀⠀⠐⠀⠀⠀⢀⠀⠀⠀⠀⠀⠀⠀
+//!       │⠀⠀⠀⠀⠀⢀⠀⠀⠀⠀⠈⠀⠀⡀⠠⠀⡀⠁⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠀⠠
+//! 1.500 ┼⠢⢁⠈⠀⠄⠀⠀⠄⠀⠀⠀⠀⠀⠀⠀⠁⠀⠀⠄⡈⠀⠀⠄⠀⠀⠀⠁⠀⠀⠂
 //!       └┼──────┼──────┼──────┼────────
 //!        1.0000 15.000 29.000 43.000   
 //! ```
@@ -251,6 +308,109 @@
 //!       └┼─────┼─────┼─────┼──────
 //!        -1.15 -0.57 0.000 0.576  
 //! ```
+//! 
+//! ## Figure Plot
+//! Filename: figure_plot.rs
+//! 
+//! Displays multiple series on one set of axes. Functions, scatter points, lines, and regions can be added with `add_function`, `add_scatter`, `add_line`, and `add_region`, and are drawn in the order they are added. Calling `set_label` after adding a series labels it in the legend.
+//! 
+//! ### Options
+//! `domain: (f64, f64)` Sets the domain shared by every series. Default is computed to contain all of the series.
+//! 
+//! `range: (f64, f64)` Sets the range shared by every series. Default is computed to contain all of the series.
+//! 
+//! `padding: f64` Pads a computed domain and range by some percentage.
+//! 
+//! `size: (u32, u32)`
+//! 
+//! `title: Option<&str>`
+//! 
+//! `axes: bool`
+//! 
+//! `legend: LegendPosition` Where to place the legend of labeled series. Can be inside any corner of the plot, or to the `Right` of or `Below` it. Default is `TopRight`.
+//! 
+//! ### Example
+//! Code:
+//! ```
+//! use cgrustplot::plots::figure_plot::figure_plot;
+//! 
+//! let data: Vec<(f64, f64)> = (0..20).map(|i| (0.5 * i as f64, (0.5 * i as f64).sqrt() + 0.2 * (i % 3) as f64)).collect();
+//! let fit = |x: f64| x.sqrt() + 0.2;
+//! 
+//! figure_plot()
+//!     .add_scatter(&data)
+//!     .add_function(&fit)
+//!     .set_domain((0., 10.))
+//!     .set_size((40, 10))
+//!     .print();
+//! ```
+//! 
+//! Output:
+//! ```text
+//!       │                                 _――――――
+//! 3.093 ┼                         _――――――‾  ● ●  
+//!       │                 _――――――‾●   ●          
+//! 2.286 ┼           _――――‾● ●   ●                
+//!       │       _――‾● ●                          
+//! 1.479 ┼   _――‾●                                
+//!       │ _‾  ●                                  
+//! 0.672 ┼‾                                       
+//!       │                                        
+//! -0.13 ┼●                                       
+//!       └┼─────┼─────┼─────┼─────┼─────┼─────────
+//!        0.125 1.625 3.125 4.625 6.125 7.625     
+//! ```
+//! 
+//! ## Subplot Grid
+//! Filename: subplot_grid.rs
+//! 
+//! Arranges several plots into rows and columns of a single output. Any plot can be added with `add`, or with a title above it using `add_titled`. The result can be printed, saved, or rendered as an image like any other plot.
+//! 
+//! ### Options
+//! `columns: u32` Number of plots in each row. Default is 2.
+//! 
+//! `gutter: (u32, u32)` Number of blank columns and rows between plots. Default is (4, 1).
+//! 
+//! `title: Option<&str>`
+//! 
+//! ### Example
+//! Code:
+//! ```
+//! use cgrustplot::plots::{
+//!     subplot_grid::subplot_grid,
+//!     function_plot::function_plot,
+//!     array_plot::array_plot,
+//! };
+//! 
+//! let f = |x: f64| x.sin();
+//! let g = |x: f64| x * x;
+//! let data = vec![vec![0, 1, 2, 3], vec![3, 2, 1, 0]];
+//! 
+//! subplot_grid()
+//!     .set_columns(2)
+//!     .set_title("Overview")
+//!     .add_titled(function_plot(&f).set_domain((-3., 3.)).set_size((20, 5)), "sin(x)")
+//!     .add_titled(function_plot(&g).set_domain((-1., 1.)).set_size((20, 5)), "x^2")
+//!     .add(&array_plot(&data))
+//!     .print();
+//! ```
+//! 
+//! Output:
+//! ```text
+//! Overview
+//! sin(x)                         x^2
+//! 0.949 ┼           _――――_       0.980 ┼ ‾_              _‾
+//!       │\         /      ‾_           │   \            /
+//! 0.000 ┼ ‾_      /         \    0.501 ┼    ‾_        _‾
+//!       │   ‾_  _‾                     │      ‾―_  _―‾
+//! -0.94 ┼     ‾‾                 0.022 ┼         ‾‾
+//!       └┼─────┼─────┼───────          └┼─────┼─────┼───────
+//!        -3.42 -1.26 0.900              -1.14 -0.42 0.300
+//! 
+//!       │ ░▓█
+//! 0.500 ┼█▓░
+//!       └┼───
+//! ```
 
 //! # Structure
 //! 
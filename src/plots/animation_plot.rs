@@ -11,12 +11,16 @@
 //! 
 
 use crate::{
+    helper::error::{PlotError, warn_on_err},
     helper::file::get_current_dir,
-    helper::file::save_image,
+    helper::file::try_save_pixels,
+    helper::table::Frames,
 };
+#[cfg(feature = "ndarray")]
+use ndarray::{CowArray, Ix4};
 use std::fmt;
-use log::warn;
 use std::{
+    borrow::Cow,
     process::Command,
     fs,
     path::Path,
@@ -58,7 +62,7 @@ impl fmt::Display for EncodingSpeed {
 /// 
 /// # Options
 /// 
-/// * `ani` - The inputted vector of frames.
+/// * `ani` - The inputted frames. Can be borrowed or owned.
 /// * `path` - The path to save the output video file to. Default is "./output.mp4".
 /// * `framerate` - The framerate of the output video.
 /// * `compression` - The CRF value for FFmpeg: higher is more compressed. Default is 23.
@@ -72,7 +76,7 @@ impl fmt::Display for EncodingSpeed {
 /// 
 #[derive(Clone)]
 pub struct AnimationPlotBuilder<'a> {
-    ani: &'a Vec<Vec<Vec<(u8, u8, u8)>>>,
+    ani: Frames<'a>,
     path: Option<String>,
     framerate: Option<u32>,
     compression: Option<u32>,
@@ -83,7 +87,7 @@ pub struct AnimationPlotBuilder<'a> {
 
 /// Internal struct representing built values.
 pub(crate) struct AnimationPlot<'a> {
-    ani: &'a Vec<Vec<Vec<(u8, u8, u8)>>>,
+    ani: Frames<'a>,
     path: String,
     framerate: u32,
     compression: u32,
@@ -94,8 +98,8 @@ pub(crate) struct AnimationPlot<'a> {
 }
 
 impl<'a> AnimationPlotBuilder<'a> {
-    /// Create an array plot from a table of data.
-    fn from(ani: &Vec<Vec<Vec<(u8, u8, u8)>>>) -> AnimationPlotBuilder {
+    /// Create an animation from a list of frames.
+    fn from(ani: Frames<'a>) -> Self {
         AnimationPlotBuilder {
             ani,
             path: None,
@@ -150,9 +154,9 @@ impl<'a> AnimationPlotBuilder<'a> {
         self
     }
 
-    fn build(&mut self) -> AnimationPlot {
+    fn build(&mut self) -> AnimationPlot<'_> {
         AnimationPlot {
-            ani: self.ani,
+            ani: self.ani.view(),
             path: self.path.clone().unwrap_or_else(|| get_current_dir() + &"output.mp4"),
             framerate: self.framerate.unwrap_or(30),
             compression: self.compression.unwrap_or(23),
@@ -167,6 +171,11 @@ impl<'a> AnimationPlotBuilder<'a> {
         self.build().save();
     }
 
+    /// Saves the animation, returning an error if any step fails (including FFmpeg exiting unsuccessfully).
+    pub fn try_save(&mut self) -> Result<(), PlotError> {
+        self.build().try_save()
+    }
+
     /// Instead of saving an animation from a Vec of images, it allows
     /// using the builder parameters to save arbitrary image files.
     /// Given a function which moves images to a temporary directory,
@@ -176,26 +185,37 @@ impl<'a> AnimationPlotBuilder<'a> {
     pub fn save_arbitrary_images(&mut self, image_mover: impl Fn(&str)) {
         self.build().save_arbitrary_images(image_mover);
     }
+
+    /// Same as `save_arbitrary_images`, but returns an error if any step fails.
+    pub fn try_save_arbitrary_images(&mut self, image_mover: impl Fn(&str)) -> Result<(), PlotError> {
+        self.build().try_save_arbitrary_images(image_mover)
+    }
+}
+
+impl<'a> FromIterator<Vec<Vec<(u8, u8, u8)>>> for AnimationPlotBuilder<'a> {
+    /// Collects frames into an animation which owns its data.
+    fn from_iter<I: IntoIterator<Item = Vec<Vec<(u8, u8, u8)>>>>(iter: I) -> Self {
+        AnimationPlotBuilder::from(Frames::Rows(Cow::Owned(iter.into_iter().collect())))
+    }
 }
 
 impl<'a> AnimationPlot<'a> {
-    fn create_temp_dir(&self) {
+    fn create_temp_dir(&self) -> Result<(), PlotError> {
         let dir_path = Path::new(&self.temp_dir);
-        fs::create_dir_all(dir_path).expect("Could not create temporary directory.");
+        fs::create_dir_all(dir_path).map_err(|source| PlotError::Io {path: self.temp_dir.clone(), source})
     }
 
-    fn save_images(&self) {
-        use rayon::prelude::*;
+    fn save_images(&self) -> Result<(), PlotError> {
+        use crate::helper::parallel::*;
 
-        self.ani
-        .par_iter()
-        .enumerate()
-        .for_each(|(i, img)|
-            save_image(&img, &(self.temp_dir.clone() + &i.to_string() + ".png"))
-        );
+        (0..self.ani.len())
+        .into_par_iter()
+        .try_for_each(|i|
+            try_save_pixels(&self.ani.frame(i), &(self.temp_dir.clone() + &i.to_string() + ".png"))
+        )
     }
 
-    fn run_ffmpeg_commands(&self) {
+    fn run_ffmpeg_commands(&self) -> Result<(), PlotError> {
         let input_path = self.temp_dir.clone() + "%d.png";
 
         let result = Command::new("ffmpeg")
@@ -217,36 +237,56 @@ impl<'a> AnimationPlot<'a> {
             .arg(self.path.clone())
             .status();
 
-        if let Ok(status) = result {
-            if !status.success() {
-                warn!("Failed to execute FFmpeg command: {status}");
-            }
-        } else {
-            warn!("Error status for FFmpeg command: {result:?}");
+        match result {
+            Ok(status) if status.success() => Ok(()),
+            Ok(status) => Err(PlotError::Command {program: "ffmpeg".to_string(), message: status.to_string()}),
+            Err(e) => Err(PlotError::Command {program: "ffmpeg".to_string(), message: e.to_string()}),
         }
-        
     }
 
-    fn delete_temporary_dir(&self) {
+    fn delete_temporary_dir(&self) -> Result<(), PlotError> {
         let dir_path = Path::new(&self.temp_dir);
         if dir_path.exists() {
-            fs::remove_dir_all(dir_path)
-                .expect("Failed to delete temp directory and its contents");
+            fs::remove_dir_all(dir_path).map_err(|source| PlotError::Io {path: self.temp_dir.clone(), source})?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the temporary directory if `remdir` is set, after saving has finished with `result`.
+    /// 
+    /// If saving failed, that error is returned rather than any error from deleting the directory.
+    fn clean_up(&self, result: Result<(), PlotError>) -> Result<(), PlotError> {
+        let cleanup = if self.remdir {self.delete_temporary_dir()} else {Ok(())};
+
+        if result.is_err() {
+            warn_on_err(cleanup);
+            return result;
         }
+        cleanup
     }
 
     pub fn save(&self) {
-        self.create_temp_dir();
-        self.save_images();
-        self.run_ffmpeg_commands();
-        if self.remdir {self.delete_temporary_dir()};
+        warn_on_err(self.try_save());
+    }
+
+    pub fn try_save(&self) -> Result<(), PlotError> {
+        self.create_temp_dir()?;
+        let result = self.save_images().and_then(|_| self.run_ffmpeg_commands());
+
+        // The temporary directory is removed even if encoding failed
+        self.clean_up(result)
     }
 
     pub fn save_arbitrary_images(&self, image_mover: impl Fn(&str)) {
-        self.create_temp_dir();
+        warn_on_err(self.try_save_arbitrary_images(image_mover));
+    }
+
+    pub fn try_save_arbitrary_images(&self, image_mover: impl Fn(&str)) -> Result<(), PlotError> {
+        self.create_temp_dir()?;
         image_mover(&self.temp_dir);
-        self.run_ffmpeg_commands();
-        if self.remdir {self.delete_temporary_dir()};
+        let result = self.run_ffmpeg_commands();
+
+        self.clean_up(result)
     }
 }
 
@@ -262,7 +302,7 @@ impl<'a> AnimationPlot<'a> {
 /// 
 /// # Options
 /// 
-/// * `ani` - The inputted vector of frames.
+/// * `ani` - The inputted frames. Can be borrowed or owned.
 /// * `path` - The path to save the output video file to. Default is "./output.mp4".
 /// * `framerate` - The framerate of the output video.
 /// * `compression` - The CRF value for FFmpeg: higher is more compressed. Default is 23.
@@ -273,6 +313,23 @@ impl<'a> AnimationPlot<'a> {
 /// 
 /// FFmpeg must be installed.
 /// 
-pub fn animation_plot<'a>(ani: &'a Vec<Vec<Vec<(u8, u8, u8)>>>) -> AnimationPlotBuilder<'a> {
-    AnimationPlotBuilder::from(ani)
+pub fn animation_plot<'a>(ani: impl Into<Cow<'a, [Vec<Vec<(u8, u8, u8)>>]>>) -> AnimationPlotBuilder<'a> {
+    AnimationPlotBuilder::from(Frames::Rows(ani.into()))
+}
+
+/// Creates a video or animation from a 4D ndarray of frames, without copying it.
+/// 
+/// Takes an owned `Array4<u8>` or a borrowed `ArrayView4<u8>` (e.g. from `.view()`) of shape (frames, height, width, 3),
+/// where the last axis is the (R, G, B) channels. All options are the same as for `animation_plot`.
+/// 
+/// # Notes
+/// 
+/// FFmpeg must be installed.
+/// 
+/// Panics if the last axis does not have length 3.
+#[cfg(feature = "ndarray")]
+pub fn animation_plot_from_ndarray<'a>(ani: impl Into<CowArray<'a, u8, Ix4>>) -> AnimationPlotBuilder<'a> {
+    let ani = ani.into();
+    assert_eq!(ani.shape()[3], 3, "Animations must be an array of shape (frames, height, width, 3)");
+    AnimationPlotBuilder::from(Frames::Array(ani))
 }
\ No newline at end of file
@@ -7,17 +7,23 @@
 //! * `bin_arr` - Bins a float table to a specific number of values.
 //! 
 
-use std::{collections::HashMap, fmt::Debug};
+use std::{borrow::Cow, collections::HashMap, fmt::Debug};
+
+#[cfg(feature = "ndarray")]
+use ndarray::{CowArray, Ix2};
 
 use crate::helper::{
     math::{*, non_nan_type::*},
     arrays::{bin_arr_bounded, distinct_in_table_non_nan},
     charset::{gradient_chars::*, NULL_STR},
-    axes::add_opt_axes_and_opt_titles,
-    mat_plot_lib::pyplot,
-    rendering::RenderableTextBuilder,
-    file::save_to_file,
+    color::{Color, paint_gradient},
+    axes::{add_opt_axes_and_opt_titles_and_opt_legend, add_opt_caption, AxesConfig, AxesOptions},
+    svg::SvgCanvas,
+    table::Table,
 };
+use crate::plots::traits::{impl_plot_outputs, impl_plot_options};
+#[cfg(feature = "matplotlib")]
+use crate::helper::{error::PlotError, mat_plot_lib::try_pyplot};
 
 /// Determines which ascii shading character set to use based on the number of unique characters.
 /// Acts as a default argument for ArrayPlots
@@ -52,94 +58,78 @@ pub fn bin_arr(data: &Vec<Vec<f64>>, bins: u32) -> Vec<Vec<u32>> {
 /// 
 /// # Options
 /// 
-/// * `data` - Input data representing the array.
+/// * `data` - Input data representing the array. Can be borrowed (`&Vec<Vec<T>>`, `&[Vec<T>]`) or owned (`Vec<Vec<T>>`).
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
 /// * `chars` - Charset to be used for plotting. Any set in `cgrustplot::helper::charset::gradient_chars` works. Default is computed.
+/// * `colors` - Colors spread across the charset, from lowest to highest value. Default is uncolored.
 /// 
 #[derive(Clone)]
 pub struct ArrayPlotBuilder<'a, T: PartialOrd + Copy> {
-    data: &'a Vec<Vec<T>>,
-    title: Option<&'a str>,
-    axes: Option<bool>,
+    data: Table<'a, T>,
+    options: AxesOptions<'a>,
     chars: Option<Vec<String>>,
+    colors: Option<Vec<Color>>,
 }
 
 /// Internal struct representing built values.
 struct ArrayPlot<'a, T: PartialOrd + Copy> {
-    data: &'a Vec<Vec<T>>,
+    data: Table<'a, T>,
     title: Option<&'a str>,
+    caption: Option<&'a str>,
     axes: bool,
+    axes_config: AxesConfig,
     chars: Vec<String>,
+    colors: &'a [Color],
 }
 
 impl<'a, T: PartialOrd + Copy + Debug> ArrayPlotBuilder<'a, T> {
     /// Create an array plot from a table of data.
-    fn from(data: &Vec<Vec<T>>) -> ArrayPlotBuilder<T> {
+    fn from(data: Table<'a, T>) -> Self {
         ArrayPlotBuilder {
-            data: data,
-            title: None,
-            axes: None,
+            data,
+            options: AxesOptions::default(),
             chars: None,
+            colors: None,
         }
     }
 
-    pub fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self {
-        self.title = Some(title);
-        self
-    }
-
-    pub fn set_axes(&mut self, do_axes: bool) -> &mut Self {
-        self.axes = Some(do_axes);
+    pub fn set_chars(&mut self, chars: Vec<String>) -> &mut Self {
+        self.chars = Some(chars);
         self
     }
 
-    pub fn set_chars(&mut self, chars: Vec<String>) -> &mut Self {
-        self.chars = Some(chars);
+    pub fn set_colors(&mut self, colors: Vec<Color>) -> &mut Self {
+        self.colors = Some(colors);
         self
     }
 
-    fn build(&self) -> ArrayPlot<T> {
+    fn build(&self) -> ArrayPlot<'_, T> {
         // chars could be a reference, but in case of default, self would need to be mutated
 
         ArrayPlot {
-            data: self.data,
-            title: self.title,
-            axes: self.axes.unwrap_or(true),
-            chars: self.chars.clone().unwrap_or_else(|| choose_character_set(distinct_in_table_non_nan(&self.data).len() as u32)),
+            data: self.data.view(),
+            title: self.options.title,
+            caption: self.options.caption,
+            axes: self.options.axes(),
+            axes_config: self.options.build_axes_config(),
+            chars: paint_gradient(
+                &self.chars.clone().unwrap_or_else(|| choose_character_set(distinct_in_table_non_nan(self.data.values()).len() as u32)),
+                self.colors.as_deref().unwrap_or(&[])
+            ),
+            colors: self.colors.as_deref().unwrap_or(&[]),
         }
     }
 
-    /// Returns the plotted data as a string
-    pub fn as_string(&self) -> String {
-        self.build().as_string()
-    }
-
-    /// Displays the plotted data with println
-    pub fn print(&self) {
-        self.build().print();
-    }
-
-    /// Saves the text content of a plot to a file
-    pub fn save(&self, path: &str) {
-        save_to_file(&self.build().as_string(), path);
-    }
-
-    /// Returns a rendered text builder to render a string
-    pub fn as_image(&self) -> RenderableTextBuilder {
-        RenderableTextBuilder::from(self.build().as_string())
-    }
-
-    /// Displays the plot's data using pyplot
-    pub fn pyplot(&self) {
-        self.build().pyplot(None);
-    }
-
-    /// Saves the plot's data using pyplot
-    pub fn save_pyplot(&self, path: &str) {
-        self.build().pyplot(Some(path));
-    }
-
     /// Returns the unformatted text content of a plot
     #[allow(dead_code)]
     pub(crate) fn plot(&self) -> String {
@@ -147,10 +137,21 @@ impl<'a, T: PartialOrd + Copy + Debug> ArrayPlotBuilder<'a, T> {
     }
 }
 
+impl<'a, T: PartialOrd + Copy + Debug> FromIterator<Vec<T>> for ArrayPlotBuilder<'a, T> {
+    /// Collects rows into an array plot which owns its data.
+    fn from_iter<I: IntoIterator<Item = Vec<T>>>(iter: I) -> Self {
+        ArrayPlotBuilder::from(Table::Rows(Cow::Owned(iter.into_iter().collect())))
+    }
+}
+
+impl_plot_outputs!(['a, T: PartialOrd + Copy + Debug] ArrayPlotBuilder<'a, T>: text, save, pyplot, svg);
+
+impl_plot_options!(['a, T: PartialOrd + Copy + Debug] ArrayPlotBuilder<'a, T>: title, axes);
+
 impl<'a, T: PartialOrd + Copy + Debug> ArrayPlot<'a, T> {
     fn plot(&self) -> String {
         // di is distinct non-NaN integers in the table
-        let mut di = distinct_in_table_non_nan(self.data);
+        let mut di = distinct_in_table_non_nan(self.data.values());
         di.sort_unstable();
         
         // Select di.len() unique (usually) characters
@@ -163,7 +164,7 @@ impl<'a, T: PartialOrd + Copy + Debug> ArrayPlot<'a, T> {
         let charmap: HashMap<NonNanWrapper<T>, &str> = di.into_iter().zip(ref_chars.into_iter()).collect();
 
         // Map each in table to corresponding char
-        self.data.iter().map(|i| {
+        self.data.rows().map(|i| {
             i.iter().map(|j| {
                 // If non-nan, get from charmap, else null character
                 if j == j {
@@ -175,16 +176,48 @@ impl<'a, T: PartialOrd + Copy + Debug> ArrayPlot<'a, T> {
     }
 
     fn as_string(&self) -> String {
-        add_opt_axes_and_opt_titles(&self.plot(), ((0., self.data[0].len() as f64), (0., self.data.len() as f64)), self.axes, self.title)
+        add_opt_caption(add_opt_axes_and_opt_titles_and_opt_legend(&self.plot(), ((0., self.data.width() as f64), (0., self.data.height() as f64)), self.axes.then_some(&self.axes_config), self.title, None), self.caption)
     }
 
-    fn print(&self) {
-        println!("{}", self.as_string());
+    #[cfg(feature = "matplotlib")]
+    fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
+        let command = format!("imshow({:?})", self.data.rows().collect::<Vec<_>>());
+        try_pyplot(&(command + &self.axes_config.pyplot_labels()), self.title, Some(self.axes), None, path)
     }
 
-    fn pyplot(&self, path: Option<&str>) {
-        let command = format!("imshow({:?})", self.data);
-        pyplot(&command, self.title, Some(self.axes), None, path);
+    fn svg(&self) -> String {
+        let mut di = distinct_in_table_non_nan(self.data.values());
+        di.sort_unstable();
+        let n = di.len();
+
+        // Map from every value to its position from lowest (0) to highest (1)
+        let shades: HashMap<NonNanWrapper<T>, f64> = di
+            .into_iter()
+            .enumerate()
+            .map(|(i, v)| (v, if n <= 1 {1.} else {i as f64 / (n - 1) as f64}))
+            .collect();
+
+        // Shades of gray from white to black, unless colors are set
+        let cells: Vec<Vec<Option<Color>>> = self.data.rows().map(|i| {
+            i.iter().map(|j| {
+                // NaN is left empty
+                let t = shades.get(&NonNanWrapper::from(*j))?;
+
+                Some(if self.colors.is_empty() {
+                    let v = (255. * (1. - t)).round() as u8;
+                    Color::TrueColor(v, v, v)
+                } else {
+                    self.colors[((t * self.colors.len() as f64) as usize).min(self.colors.len() - 1)]
+                })
+            }).collect()
+        }).collect();
+
+        let (width, height) = (self.data.width(), self.data.height());
+
+        let mut canvas = SvgCanvas::new((width as u32, height as u32), ((0., width as f64), (0., height as f64)));
+        canvas.cells(&cells);
+        if let Some(c) = self.caption {canvas.caption(c)}
+        canvas.finish(self.axes.then_some(&self.axes_config), self.title)
     }
 }
 
@@ -246,11 +279,42 @@ impl<'a, T: PartialOrd + Copy + Debug> ArrayPlot<'a, T> {
 /// 
 /// # Options
 /// 
-/// * `data` - Input data representing the array.
+/// * `data` - Input data representing the array. Can be borrowed (`&Vec<Vec<T>>`, `&[Vec<T>]`) or owned (`Vec<Vec<T>>`).
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
 /// * `chars` - Charset to be used for plotting. Any set in `cgrustplot::helper::charset::gradient_chars` works. Default is computed.
+/// * `colors` - Colors spread across the charset, from lowest to highest value. Default is uncolored.
+/// 
+pub fn array_plot<'a, T: PartialOrd + Copy + Debug>(data: impl Into<Cow<'a, [Vec<T>]>>) -> ArrayPlotBuilder<'a, T> {
+    ArrayPlotBuilder::from(Table::Rows(data.into()))
+}
+
+/// Displays a 2D ndarray with different shades, without copying it.
 /// 
-pub fn array_plot<T: PartialOrd + Copy + Debug>(data: &Vec<Vec<T>>) -> ArrayPlotBuilder<T> {
-    ArrayPlotBuilder::from(&data)
+/// Takes an owned `Array2` or a borrowed `ArrayView2` (e.g. from `.view()`), where each row of the array is a row of the plot.
+/// All options are the same as for `array_plot`.
+/// 
+/// # Example
+/// 
+/// ```
+/// # #[cfg(feature = "ndarray")] {
+/// use cgrustplot::plots::array_plot::array_plot_from_ndarray;
+/// use ndarray::Array2;
+/// 
+/// let data = Array2::from_shape_fn((3, 7), |(r, c)| (r + c) % 3);
+/// array_plot_from_ndarray(data.view()).print();
+/// # }
+/// ```
+#[cfg(feature = "ndarray")]
+pub fn array_plot_from_ndarray<'a, T: PartialOrd + Copy + Debug>(data: impl Into<CowArray<'a, T, Ix2>>) -> ArrayPlotBuilder<'a, T> {
+    ArrayPlotBuilder::from(Table::Array(data.into()))
 }
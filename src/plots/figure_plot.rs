@@ -0,0 +1,588 @@
+//! # Figure Plot
+//! Displays multiple series (functions, points, lines, and regions) on one set of axes.
+//!
+//! # Functions
+//!
+//! * `figure_plot` - Generates an empty FigurePlotBuilder, which series can be added to.
+//!
+//! # Notes
+//!
+//! Every series is drawn with the same domain and range, into a single grid of characters.
+//! Series are layered in the order they are added, so later series are drawn over earlier ones.
+//!
+//! A series can be given a label with `set_label`, which is shown in a legend.
+//!
+//! A series can be drawn against a secondary y axis on the right with `set_secondary_axis`,
+//! for showing two quantities with different units over the same domain.
+//!
+
+use log::warn;
+use num::ToPrimitive;
+
+use crate::{
+    helper::{
+        axes::{add_opt_axes_and_opt_titles_and_opt_legend, add_opt_caption, AxesConfig, AxesOptions, Ticks, LegendPosition},
+        charset::{subdiv_chars::{blocks_two_by_two, dots_one_by_one}, line_chars},
+        color::{Color, split_cells, visible_char, join_cells},
+        func_plot_domain::determine_plot_domain,
+        svg::SvgCanvas,
+        math::{max_always, min_always, pad_range, subdivide},
+    },
+    plots::{
+        function_plot::function_plot,
+        line_plot::line_plot,
+        region_plot::region_plot,
+        scatter_plot::{determine_char_set, scatter_plot},
+        traits::{impl_plot_outputs, impl_plot_options},
+    },
+};
+#[cfg(feature = "matplotlib")]
+use crate::helper::{error::PlotError, mat_plot_lib::try_pyplot};
+
+/// A single series to be drawn on a figure.
+#[derive(Clone)]
+enum Series<'a> {
+    Function(&'a dyn Fn(f64) -> f64),
+    Scatter(Vec<(f64, f64)>),
+    Line(Vec<(f64, f64)>),
+    Region(&'a dyn Fn(f64, f64) -> bool),
+}
+
+/// A series along with how it is displayed.
+#[derive(Clone)]
+struct FigureSeries<'a> {
+    series: Series<'a>,
+    label: Option<&'a str>,
+    color: Color,
+    secondary: bool,
+}
+
+impl<'a> FigureSeries<'a> {
+    /// Create an unlabeled and uncolored series.
+    fn from(series: Series<'a>) -> Self {
+        FigureSeries {
+            series,
+            label: None,
+            color: Color::Default,
+            secondary: false,
+        }
+    }
+}
+
+/// Builder for a Figure Plot
+/// Add series and set various options for plotting them together.
+///
+/// # Options
+///
+/// * `series` - The functions, points, lines, and regions to be plotted, along with their optional labels, colors, and axes.
+/// * `domain` - Specified domain to plot the series over. Default is computed from all series.
+/// * `range` - Specified range to display the series over. Default is computed from all series on the primary axis.
+/// * `secondary_range` - Specified range of the secondary axis. Default is computed from all series on the secondary axis, if there are any.
+/// * `padding` - Proportion of a computed domain and range to pad the plot with. Default is 0.1.
+/// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 20).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
+/// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
+/// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `secondary_ticks` - How ticks on the secondary axis are chosen. Default is Ticks::Even.
+/// * `secondary_label` - Title of the secondary axis. Default is None.
+/// * `legend` - Position of the legend of labeled series. Default is LegendPosition::TopRight.
+///
+#[derive(Clone)]
+pub struct FigurePlotBuilder<'a> {
+    series: Vec<FigureSeries<'a>>,
+    domain: Option<(f64, f64)>,
+    range: Option<(f64, f64)>,
+    secondary_range: Option<(f64, f64)>,
+    padding: Option<f64>,
+    options: AxesOptions<'a>,
+    secondary_ticks: Option<Ticks>,
+    secondary_label: Option<String>,
+    legend: Option<LegendPosition>,
+}
+
+/// Internal struct representing built values.
+struct FigurePlot<'a> {
+    series: &'a Vec<FigureSeries<'a>>,
+    domain_and_range: ((f64, f64), (f64, f64)),
+    secondary_range: Option<(f64, f64)>,
+    size: (u32, u32),
+    title: Option<&'a str>,
+    caption: Option<&'a str>,
+    axes: bool,
+    axes_config: AxesConfig,
+    legend: LegendPosition,
+}
+
+impl<'a> FigurePlotBuilder<'a> {
+    /// Create an empty figure.
+    fn new() -> Self {
+        FigurePlotBuilder {
+            series: Vec::new(),
+            domain: None,
+            range: None,
+            secondary_range: None,
+            padding: None,
+            options: AxesOptions::default(),
+            secondary_ticks: None,
+            secondary_label: None,
+            legend: None,
+        }
+    }
+
+    /// Adds the graph of a function to the figure.
+    pub fn add_function(&mut self, func: &'a impl Fn(f64) -> f64) -> &mut Self {
+        self.series.push(FigureSeries::from(Series::Function(func)));
+        self
+    }
+
+    /// Adds a set of points, drawn as a scatter plot, to the figure.
+    pub fn add_scatter<T: ToPrimitive>(&mut self, points: &[(T, T)]) -> &mut Self {
+        self.series.push(FigureSeries::from(Series::Scatter(as_float_points(points))));
+        self
+    }
+
+    /// Adds a set of points, drawn as a line plot, to the figure.
+    pub fn add_line<T: ToPrimitive>(&mut self, points: &[(T, T)]) -> &mut Self {
+        self.series.push(FigureSeries::from(Series::Line(as_float_points(points))));
+        self
+    }
+
+    /// Adds the region over which a predicate is true to the figure.
+    pub fn add_region(&mut self, pred: &'a impl Fn(f64, f64) -> bool) -> &mut Self {
+        self.series.push(FigureSeries::from(Series::Region(pred)));
+        self
+    }
+
+    /// Labels the most recently added series, to be shown in the legend.
+    pub fn set_label<'b: 'a>(&mut self, label: &'b str) -> &mut Self {
+        match self.series.last_mut() {
+            Some(s) => s.label = Some(label),
+            None => warn!("Cannot set the label '{label}' before any series have been added to the figure"),
+        }
+        self
+    }
+
+    /// Colors the most recently added series.
+    pub fn set_color(&mut self, color: Color) -> &mut Self {
+        match self.series.last_mut() {
+            Some(s) => s.color = color,
+            None => warn!("Cannot set a color before any series have been added to the figure"),
+        }
+        self
+    }
+
+    /// Draws the most recently added series against the secondary y axis, on the right of the plot.
+    pub fn set_secondary_axis(&mut self, secondary: bool) -> &mut Self {
+        match self.series.last_mut() {
+            Some(s) => s.secondary = secondary,
+            None => warn!("Cannot set the axis of a series before any series have been added to the figure"),
+        }
+        self
+    }
+
+    pub fn set_domain(&mut self, domain: (f64, f64)) -> &mut Self {
+        self.domain = Some(domain);
+        self
+    }
+
+    pub fn set_range(&mut self, range: (f64, f64)) -> &mut Self {
+        self.range = Some(range);
+        self
+    }
+
+    pub fn set_secondary_range(&mut self, range: (f64, f64)) -> &mut Self {
+        self.secondary_range = Some(range);
+        self
+    }
+
+    pub fn set_padding(&mut self, padding: f64) -> &mut Self {
+        self.padding = Some(padding);
+        self
+    }
+
+    pub fn set_secondary_ticks(&mut self, ticks: Ticks) -> &mut Self {
+        self.secondary_ticks = Some(ticks);
+        self
+    }
+
+    pub fn set_secondary_label(&mut self, label: &str) -> &mut Self {
+        self.secondary_label = Some(label.to_string());
+        self
+    }
+
+    pub fn set_legend(&mut self, position: LegendPosition) -> &mut Self {
+        self.legend = Some(position);
+        self
+    }
+
+    /// Determines a domain which contains every series
+    fn determine_domain(&self, size: (u32, u32)) -> (f64, f64) {
+        let mut bounds: Vec<f64> = Vec::new();
+
+        for FigureSeries {series: s, ..} in self.series.iter() {
+            match s {
+                Series::Function(f) => {
+                    let d = determine_plot_domain(f);
+                    bounds.push(d.0);
+                    bounds.push(d.1);
+                }
+                Series::Scatter(points) | Series::Line(points) => {
+                    bounds.extend(points.iter().map(|p| p.0));
+                }
+                Series::Region(_) => (),
+            }
+        }
+
+        if bounds.is_empty() {
+            // Same default as a region plot
+            return (0., size.0 as f64);
+        }
+
+        (min_always(&bounds, 0.), max_always(&bounds, 0.))
+    }
+
+    /// Determines a range which contains every series on the primary or secondary axis over the domain
+    fn determine_range(&self, size: (u32, u32), domain: (f64, f64), secondary: bool) -> (f64, f64) {
+        let mut bounds: Vec<f64> = Vec::new();
+
+        for FigureSeries {series: s, ..} in self.series.iter().filter(|s| s.secondary == secondary) {
+            match s {
+                Series::Function(f) => {
+                    bounds.extend(subdivide(domain.0, domain.1, size.0).into_iter().map(f).filter(|y| y.is_finite()));
+                }
+                Series::Scatter(points) | Series::Line(points) => {
+                    bounds.extend(points.iter().map(|p| p.1));
+                }
+                Series::Region(_) => (),
+            }
+        }
+
+        if bounds.is_empty() {
+            return (0., size.1 as f64);
+        }
+
+        (min_always(&bounds, 0.), max_always(&bounds, 0.))
+    }
+
+    fn build(&self) -> FigurePlot<'_> {
+        let size = self.options.size((60, 20));
+        let padding = self.padding.unwrap_or(0.1);
+
+        // Domain must go before range, as the default range is computed over the domain
+        let domain = self.domain.unwrap_or_else(|| pad_range(self.determine_domain(size), padding));
+        let range = self.range.unwrap_or_else(|| pad_range(self.determine_range(size, domain, false), padding));
+        let secondary_range = self.secondary_range.or_else(||
+            self.series.iter().any(|s| s.secondary).then(|| pad_range(self.determine_range(size, domain, true), padding))
+        );
+
+        FigurePlot {
+            series: &self.series,
+            domain_and_range: (domain, range),
+            secondary_range,
+            size,
+            title: self.options.title,
+            caption: self.options.caption,
+            axes: self.options.axes(),
+            axes_config: AxesConfig {secondary_range, secondary_ticks: self.secondary_ticks.clone().unwrap_or_default(), secondary_label: self.secondary_label.clone(), ..self.options.build_axes_config()},
+            legend: self.legend.unwrap_or(LegendPosition::TopRight),
+        }
+    }
+
+    /// Returns the unformatted text content of a plot
+    #[allow(dead_code)]
+    pub(crate) fn plot(&self) -> String {
+        self.build().plot()
+    }
+}
+
+impl_plot_outputs!(['a] FigurePlotBuilder<'a>: text, save, pyplot, svg);
+
+impl_plot_options!(['a] FigurePlotBuilder<'a>: title, axes, size);
+
+impl<'a> FigurePlot<'a> {
+    /// Determines the charachter set used to draw a set of points
+    fn scatter_chars(&self, points: &[(f64, f64)], secondary: bool) -> (Vec<char>, (u32, u32)) {
+        if points.is_empty() {
+            (dots_one_by_one(), (1, 1))
+        } else {
+            determine_char_set(points, self.axis_domain_and_range(secondary), self.size)
+        }
+    }
+
+    /// The domain and range a series is drawn over, which depends on whether it uses the secondary axis
+    fn axis_domain_and_range(&self, secondary: bool) -> ((f64, f64), (f64, f64)) {
+        match self.secondary_range {
+            Some(r) if secondary => (self.domain_and_range.0, r),
+            _ => self.domain_and_range,
+        }
+    }
+
+    /// Determines the charachter shown next to a series' label in the legend
+    fn legend_marker(&self, s: &FigureSeries) -> char {
+        match &s.series {
+            Series::Function(_) | Series::Line(_) => line_chars::FLAT_MED,
+            // The last charachter of a set is the one with every subpixel filled in
+            Series::Scatter(points) => *self.scatter_chars(points, s.secondary).0.last().unwrap(),
+            Series::Region(_) => *blocks_two_by_two().last().unwrap(),
+        }
+    }
+
+    /// Generates the (marker, label) entries of every labeled series
+    fn legend_entries(&self) -> Vec<(String, String)> {
+        self.series
+            .iter()
+            .filter_map(|s| s.label.map(|l| (s.color.paint(&self.legend_marker(s).to_string()), l.to_string())))
+            .collect()
+    }
+
+    /// Renders a single series as its own plot, along with the character used as the background.
+    fn plot_series(&self, s: &FigureSeries) -> (String, char) {
+        let (domain, range) = self.axis_domain_and_range(s.secondary);
+
+        match &s.series {
+            Series::Function(f) => (
+                function_plot(f)
+                    .set_domain(domain)
+                    .set_range(range)
+                    .set_domain_padding(0.)
+                    .set_range_padding(0.)
+                    .set_size(self.size)
+                    .plot(),
+                ' '
+            ),
+            Series::Line(points) => (
+                line_plot(points)
+                    .set_domain(domain)
+                    .set_range(range)
+                    .set_size(self.size)
+                    .plot(),
+                ' '
+            ),
+            Series::Scatter(points) => {
+                let chars = self.scatter_chars(points, s.secondary);
+                let blank = chars.0[0];
+
+                (
+                    scatter_plot(points)
+                        .set_range((domain, range))
+                        .set_size(self.size)
+                        .set_chars(chars)
+                        .plot(),
+                    blank
+                )
+            }
+            Series::Region(p) => (
+                region_plot(p)
+                    .set_domain_and_range((domain, range))
+                    .set_padding(0.)
+                    .set_size(self.size)
+                    .plot(),
+                ' '
+            ),
+        }
+    }
+
+    fn plot(&self) -> String {
+        let mut o = vec![vec![" ".to_string(); self.size.0 as usize]; self.size.1 as usize];
+
+        // Layer each series on top of the previous ones
+        for s in self.series.iter() {
+            let (layer, blank) = self.plot_series(s);
+
+            s.color.paint(&layer)
+                .split('\n')
+                .zip(o.iter_mut())
+                .for_each(|(line, row)|
+                    split_cells(line)
+                    .into_iter()
+                    .zip(row.iter_mut())
+                    .for_each(|(c, cell)| if visible_char(&c) != blank {*cell = c})
+                );
+        }
+
+        o.into_iter().map(|r| join_cells(&r)).collect::<Vec<String>>().join("\n")
+    }
+
+    fn as_string(&self) -> String {
+        let entries = self.legend_entries();
+
+        add_opt_caption(add_opt_axes_and_opt_titles_and_opt_legend(&self.plot(), self.domain_and_range, self.axes.then_some(&self.axes_config), self.title, Some((&entries, self.legend))), self.caption)
+    }
+
+    #[cfg(feature = "matplotlib")]
+    fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
+        let command = |FigureSeries {series: s, label, secondary, ..}: &FigureSeries| {
+            let (domain, range) = self.axis_domain_and_range(*secondary);
+            let label = match label {
+                Some(l) => format!(", label={l:?}"),
+                None => String::new(),
+            };
+
+            match s {
+            Series::Function(f) => {
+                let x_vals = subdivide(domain.0, domain.1, 10 * self.size.0);
+                let y_vals: Vec<f64> = x_vals.iter().map(|x| f(*x)).collect();
+                format!("plot({x_vals:?}, {y_vals:?}{label})")
+            }
+            Series::Scatter(points) => {
+                let x_vals: Vec<f64> = points.iter().map(|p| p.0).collect();
+                let y_vals: Vec<f64> = points.iter().map(|p| p.1).collect();
+                format!("scatter({x_vals:?}, {y_vals:?}{label})")
+            }
+            Series::Line(points) => {
+                let x_vals: Vec<f64> = points.iter().map(|p| p.0).collect();
+                let y_vals: Vec<f64> = points.iter().map(|p| p.1).collect();
+                format!("plot({x_vals:?}, {y_vals:?}{label})")
+            }
+            Series::Region(p) => {
+                let tab: Vec<Vec<u8>> = subdivide(range.0, range.1, 2 * self.size.1)
+                    .into_iter()
+                    .map(|y| subdivide(domain.0, domain.1, 2 * self.size.0).into_iter().map(|x| p(x, y) as u8).collect())
+                    .collect();
+                format!("imshow({tab:?}, extent=({}, {}, {}, {}), origin='lower', aspect='auto', alpha=0.5{label})", domain.0, domain.1, range.0, range.1)
+            }
+        }};
+
+        let has_legend = self.series.iter().any(|s| s.label.is_some());
+
+        let Some(secondary_range) = self.secondary_range else {
+            let mut commands: Vec<String> = self.series.iter().map(command).collect();
+            if has_legend {
+                commands.push("legend()".to_string());
+            }
+
+            // Each command is run as "plt.{command}"
+            return try_pyplot(&(commands.join("\nplt.") + &self.axes_config.pyplot_labels()), self.title, Some(self.axes), Some(self.domain_and_range), path);
+        };
+
+        // Series on the secondary axis are drawn on a twin of the primary axes, which then sets its own range
+        let (domain, range) = self.domain_and_range;
+        let mut commands: Vec<String> = self.series.iter().filter(|s| !s.secondary).map(command).collect();
+        commands.push(format!("xlim({domain:?})"));
+        commands.push(format!("ylim({range:?})") + &self.axes_config.pyplot_labels());
+        commands.push("twinx()".to_string());
+        commands.extend(self.series.iter().filter(|s| s.secondary).map(command));
+        commands.push(format!("ylim({secondary_range:?})"));
+        if let Some(l) = &self.axes_config.secondary_label {
+            commands.push(format!("ylabel({l:?})"));
+        }
+        if has_legend {
+            // A figure legend includes the series from both axes
+            commands.push("gcf().legend()".to_string());
+        }
+
+        try_pyplot(&commands.join("\nplt."), self.title, Some(self.axes), None, path)
+    }
+
+    fn svg(&self) -> String {
+        let (domain, range) = self.domain_and_range;
+        let mut canvas = SvgCanvas::new(self.size, self.domain_and_range);
+
+        // The canvas is in primary coordinates, so values on the secondary axis are mapped onto the primary range
+        let to_primary = |y: f64, secondary: bool| match self.secondary_range {
+            Some(r) if secondary => range.0 + (y - r.0) / (r.1 - r.0) * (range.1 - range.0),
+            _ => y,
+        };
+        let from_primary = |y: f64, secondary: bool| match self.secondary_range {
+            Some(r) if secondary => r.0 + (y - range.0) / (range.1 - range.0) * (r.1 - r.0),
+            _ => y,
+        };
+
+        // Layer each series on top of the previous ones
+        for &FigureSeries {series: ref s, color, secondary, ..} in self.series.iter() {
+            let map = |points: &[(f64, f64)]| -> Vec<(f64, f64)> {points.iter().map(|p| (p.0, to_primary(p.1, secondary))).collect()};
+
+            match s {
+                Series::Function(f) => {
+                    let points: Vec<(f64, f64)> = subdivide(domain.0, domain.1, 10 * self.size.0).into_iter().map(|x| (x, f(x))).collect();
+                    canvas.polyline(&map(&points), color);
+                }
+                Series::Scatter(points) => map(points).into_iter().for_each(|p| canvas.circle(p, color)),
+                Series::Line(points) => {
+                    let mut d = map(points);
+                    d.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Greater));
+                    canvas.polyline(&d, color);
+                }
+                Series::Region(p) => canvas.region(|x, y| p(x, from_primary(y, secondary)), (2 * self.size.0, 2 * self.size.1), color),
+            }
+        }
+
+        canvas.legend(self.series.iter().filter_map(|s| s.label.map(|l| (s.color, l.to_string()))).collect(), self.legend);
+        if let Some(c) = self.caption {canvas.caption(c)}
+        canvas.finish(self.axes.then_some(&self.axes_config), self.title)
+    }
+}
+
+/// Converts a list of numerical points to floats, with unrepresentable values as NaN.
+fn as_float_points<T: ToPrimitive>(points: &[(T, T)]) -> Vec<(f64, f64)> {
+    points.iter().map(|p| (p.0.to_f64().unwrap_or(f64::NAN), p.1.to_f64().unwrap_or(f64::NAN))).collect()
+}
+
+/// Displays multiple series (functions, points, lines, and regions) on one set of axes.
+///
+/// # Example
+///
+/// ```
+/// use cgrustplot::plots::figure_plot::figure_plot;
+///
+/// let data: Vec<(f64, f64)> = (0..20).map(|i| (0.5 * i as f64, (0.5 * i as f64).sqrt() + 0.2 * (i % 3) as f64)).collect();
+/// let fit = |x: f64| x.sqrt() + 0.2;
+///
+/// figure_plot()
+///     .add_scatter(&data)
+///     .add_function(&fit)
+///     .set_domain((0., 10.))
+///     .set_size((40, 10))
+///     .print();
+///
+/// // Standard Output:
+/// //       │                                 _――――――
+/// // 3.093 ┼                         _――――――‾  ● ●  
+/// //       │                 _――――――‾●   ●          
+/// // 2.286 ┼           _――――‾● ●   ●                
+/// //       │       _――‾● ●                          
+/// // 1.479 ┼   _――‾●                                
+/// //       │ _‾  ●                                  
+/// // 0.672 ┼‾                                       
+/// //       │                                        
+/// // -0.13 ┼●                                       
+/// //       └┼─────┼─────┼─────┼─────┼─────┼─────────
+/// //        0.125 1.625 3.125 4.625 6.125 7.625     
+/// ```
+///
+/// # Options
+///
+/// * `series` - The functions, points, lines, and regions to be plotted, along with their optional labels, colors, and axes.
+/// * `domain` - Specified domain to plot the series over. Default is computed from all series.
+/// * `range` - Specified range to display the series over. Default is computed from all series on the primary axis.
+/// * `secondary_range` - Specified range of the secondary axis. Default is computed from all series on the secondary axis, if there are any.
+/// * `padding` - Proportion of a computed domain and range to pad the plot with. Default is 0.1.
+/// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 20).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
+/// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
+/// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `secondary_ticks` - How ticks on the secondary axis are chosen. Default is Ticks::Even.
+/// * `secondary_label` - Title of the secondary axis. Default is None.
+/// * `legend` - Position of the legend of labeled series. Default is LegendPosition::TopRight.
+///
+pub fn figure_plot<'a>() -> FigurePlotBuilder<'a> {
+    FigurePlotBuilder::new()
+}
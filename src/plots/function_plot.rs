@@ -7,17 +7,21 @@
 //! * `as_float_func` - Creates a float-valued function (Fn(f64) -> f64) from a generalal numerical function.
 //! 
 
+use log::warn;
 use num::{FromPrimitive, ToPrimitive};
 
 use crate::helper::{
-    axes::add_opt_axes_and_opt_titles,
+    axes::{add_opt_axes_and_opt_titles_and_opt_legend, add_opt_caption, AxesConfig, AxesOptions},
     charset::{line_chars::*, NULL_CHR},
+    color::Color,
     func_plot_domain::determine_plot_domain,
-    mat_plot_lib::pyplot,
+    svg::SvgCanvas,
     math::{max_always, min_always, pad_range, subdivide},
-    file::save_to_file,
-    rendering::RenderableTextBuilder,
+    scale::Scale,
 };
+use crate::plots::traits::{impl_plot_outputs, impl_plot_options};
+#[cfg(feature = "matplotlib")]
+use crate::helper::{error::PlotError, mat_plot_lib::try_pyplot};
 
 /// Builder for a Function Plot
 /// Set various options for plotting the function.
@@ -30,11 +34,26 @@ use crate::helper::{
 /// * `domain_padding` - Proportion of the width of the domain to be padded with. Default is 0.1.
 /// * `range_padding` - Proportion of the height of the range to be padded with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 10).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted graph. Default is Color::Default.
+/// * `x_scale` - Scale of the horizontal axis. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis. Default is Scale::Linear.
 /// 
 /// # Notes
 /// 
+/// On a log scale, only positive values are drawn. The domain and range are always given in the function's own values.
+/// 
 /// Use `.precompute()` to generate and save values to minimize future function calls when plotting.
 /// 
 #[derive(Clone)]
@@ -44,9 +63,8 @@ pub struct FuncPlotBuilder<'a> {
     range: Option<(f64, f64)>,
     domain_padding: Option<f64>,
     range_padding: Option<f64>,
-    size: Option<(u32, u32)>,
-    title: Option<&'a str>,
-    axes: Option<bool>,
+    options: AxesOptions<'a>,
+    color: Option<Color>,
     precomputed: Option<Vec<(f64, f64)>>,
 }
 
@@ -56,7 +74,11 @@ struct FuncPlot<'a> {
     domain_and_range: ((f64, f64), (f64, f64)),
     size: (u32, u32),
     title: Option<&'a str>,
+    caption: Option<&'a str>,
     axes: bool,
+    axes_config: AxesConfig,
+    color: Color,
+    scales: (Scale, Scale),
     precomputed: &'a Option<Vec<(f64, f64)>>
 }
 
@@ -69,9 +91,8 @@ impl<'a> FuncPlotBuilder<'a> {
             range: None,
             domain_padding: None,
             range_padding: None,
-            size: None,
-            title: None,
-            axes: None,
+            options: AxesOptions::default(),
+            color: None,
             precomputed: None,
         }
     }
@@ -96,18 +117,8 @@ impl<'a> FuncPlotBuilder<'a> {
         self
     }
 
-    pub fn set_size(&mut self, size: (u32, u32)) -> &mut Self {
-        self.size = Some(size);
-        self
-    }
-
-    pub fn set_title<'b : 'a>(&mut self, title: &'b str) -> &mut Self {
-        self.title = Some(title);
-        self
-    }
-
-    pub fn set_axes(&mut self, do_axes: bool) -> &mut Self {
-        self.axes = Some(do_axes);
+    pub fn set_color(&mut self, color: Color) -> &mut Self {
+        self.color = Some(color);
         self
     }
 
@@ -133,7 +144,8 @@ impl<'a> FuncPlotBuilder<'a> {
         }
     }
 
-    fn determine_range(&self, resolution: u32, domain: (f64, f64)) -> (f64, f64) {
+    /// Determines the range in scaled space, from values over a domain in scaled space.
+    fn determine_range(&self, resolution: u32, domain: (f64, f64), scales: (Scale, Scale)) -> (f64, f64) {
         let y_vals: Vec<f64>;
         
         match &self.precomputed {
@@ -146,21 +158,44 @@ impl<'a> FuncPlotBuilder<'a> {
             None => {
                 y_vals = subdivide(domain.0, domain.1, resolution)
                     .into_iter()
-                    .map(|i| (self.func)(i))
+                    .map(|i| (self.func)(scales.0.inverse(i)))
                     .collect();
             }
         }
 
+        if scales.1.is_log() && y_vals.iter().any(|y| *y <= 0.) {
+            warn!("The function has non-positive values, which are left out on a {:?} scale", scales.1);
+        }
+
+        let y_vals: Vec<f64> = y_vals.into_iter().map(|y| scales.1.forward(y)).collect();
+
         (min_always(&y_vals,0.), max_always(&y_vals,0.))
     }
+
+    /// Determines the domain in scaled space.
+    /// 
+    /// On a log scale, only the positive part of the computed domain is used.
+    fn determine_domain(&self, scale: Scale) -> (f64, f64) {
+        let domain = determine_plot_domain(&*self.func);
+
+        if !scale.is_log() {
+            return (scale.forward(domain.0), scale.forward(domain.1));
+        }
+
+        let high = if domain.1 > 0. {domain.1} else {10.};
+        let low = if domain.0 > 0. {domain.0} else {high / 1000.};
+        (scale.forward(low), scale.forward(high))
+    }
     
     // It is reccomended to precompute for expensive functions before building
     fn build(&self) -> FuncPlot {
-        let size = self.size.unwrap_or((60, 10));
+        let size = self.options.size((60, 10));
         let resolution = size.0;
 
-        let domain = self.domain.unwrap_or_else(|| determine_plot_domain(&*self.func));
-        let range = self.range.unwrap_or_else(|| self.determine_range(resolution, domain));
+        let scales = self.options.scales();
+
+        let domain = self.domain.and_then(|d| scales.0.forward_range(d, "domain")).unwrap_or_else(|| self.determine_domain(scales.0));
+        let range = self.range.and_then(|r| scales.1.forward_range(r, "range")).unwrap_or_else(|| self.determine_range(resolution, domain, scales));
 
         // With padding
         let domain = pad_range(domain, self.domain_padding.unwrap_or(0.1));
@@ -170,42 +205,16 @@ impl<'a> FuncPlotBuilder<'a> {
             func: self.func.clone(),
             domain_and_range: (domain, range),
             size: size,
-            title: self.title,
-            axes: self.axes.unwrap_or(true),
+            title: self.options.title,
+            caption: self.options.caption,
+            axes: self.options.axes(),
+            axes_config: self.options.build_axes_config(),
+            color: self.color.unwrap_or_default(),
+            scales,
             precomputed: &self.precomputed,
         }
     }
 
-    /// Returns the plotted data as a string
-    pub fn as_string(&self) -> String {
-        self.build().as_string()
-    }
-
-    /// Displays the plotted data with println
-    pub fn print(&self) {
-        self.build().print();
-    }
-
-    /// Saves the text content of a plot to a file
-    pub fn save(&self, path: &str) {
-        save_to_file(&self.build().as_string(), path);
-    }
-
-    /// Returns a rendered text builder to render a string
-    pub fn as_image(&self) -> RenderableTextBuilder {
-        RenderableTextBuilder::from(self.build().as_string())
-    }
-
-    /// Displays the plot's data using pyplot
-    pub fn pyplot(&self) {
-        self.build().pyplot(None);
-    }
-
-    /// Saves the plot's data using pyplot
-    pub fn save_pyplot(&self, path: &str) {
-        self.build().pyplot(Some(path));
-    }
-
     /// Returns the unformatted text content of a plot
     #[allow(dead_code)]
     pub(crate) fn plot(&self) -> String {
@@ -213,9 +222,18 @@ impl<'a> FuncPlotBuilder<'a> {
     }
 }
 
+impl_plot_outputs!(['a] FuncPlotBuilder<'a>: text, save, pyplot, svg);
+
+impl_plot_options!(['a] FuncPlotBuilder<'a>: title, axes, size, scale);
+
 impl<'a> FuncPlot<'a> {
+    /// Evaluates the function at a point in scaled space, giving a value in scaled space.
+    fn eval(&self, x: f64) -> f64 {
+        self.scales.1.forward((self.func)(self.scales.0.inverse(x)))
+    }
+
     fn plot(&self) -> String {
-        use rayon::prelude::*;
+        use crate::helper::parallel::*;
 
         // charachters per unit
         let cpux = self.size.0 as f64 / (self.domain_and_range.0.1 - self.domain_and_range.0.0);
@@ -226,7 +244,7 @@ impl<'a> FuncPlot<'a> {
         // xc_vals includes one extra padding value on each side for derivative checks
         let xc_vals: Vec<i32> = (-1..(1 + self.size.0 as i32)).collect();
         let xu_vals: Vec<f64> = xc_vals.iter().map(|xc| ctux(*xc)).collect();
-        let yu_vals: Vec<f64> = xu_vals.iter().map(|xu| (self.func)(*xu)).collect();
+        let yu_vals: Vec<f64> = xu_vals.iter().map(|xu| self.eval(*xu)).collect();
         let yc_vals: Vec<i32> = yu_vals.iter().map(|yu| utcy(*yu)).collect();
 
         let mut o = (0..self.size.1).map(|_| (0..self.size.0).map(|_| ' ').collect::<Vec<char>>()).collect::<Vec<Vec<char>>>();
@@ -234,8 +252,15 @@ impl<'a> FuncPlot<'a> {
         let mut set_o_char = |x: i32, y: i32, c: char| if 0 <= x && x < self.size.0 as i32 && 0 <= y && y < self.size.1 as i32 {o[y as usize][x as usize] = c};
 
         for i in 0..self.size.0 as i32 {
+            // Undefined values, such as non-positive values on a log scale, aren't drawn
+            if yu_vals[(i + 1) as usize].is_nan() {
+                continue;
+            }
+
             let xc = xc_vals[(i + 1) as usize];
-            let (ycl, yc, ycr) = (yc_vals[i as usize], yc_vals[(i + 1) as usize], yc_vals[(i + 2) as usize]);
+            let yc = yc_vals[(i + 1) as usize];
+            let neighbor = |j: usize| if yu_vals[j].is_nan() {yc} else {yc_vals[j]};
+            let (ycl, ycr) = (neighbor(i as usize), neighbor((i + 2) as usize));
 
             let rycl = yc - ycl;
             let rycr = yc - ycr;
@@ -269,34 +294,39 @@ impl<'a> FuncPlot<'a> {
             set_o_char(xc, yc, chr);
         }
 
-        o.into_par_iter().map(|l| l.into_iter().collect::<String>()).collect::<Vec<String>>().join("\n")
+        self.color.paint(&o.into_par_iter().map(|l| l.into_iter().collect::<String>()).collect::<Vec<String>>().join("\n"))
     }
 
     fn as_string(&self) -> String {
-        add_opt_axes_and_opt_titles(&self.plot(), self.domain_and_range, self.axes, self.title)
+        add_opt_caption(add_opt_axes_and_opt_titles_and_opt_legend(&self.plot(), self.domain_and_range, self.axes.then_some(&self.axes_config), self.title, None), self.caption)
     }
 
-    fn print(&self) {
-        println!("{}", self.as_string());
+    /// Points along the function in scaled space, for outputs which draw a continuous line.
+    fn sample_points(&self) -> Vec<(f64, f64)> {
+        match self.precomputed {
+            Some(vals) => vals.iter().map(|p| (self.scales.0.forward(p.0), self.scales.1.forward(p.1))).collect(),
+            None => subdivide(self.domain_and_range.0.0, self.domain_and_range.0.1, 10 * self.size.0)
+                .into_iter()
+                .map(|x| (x, self.eval(x)))
+                .collect(),
+        }
     }
 
-    fn pyplot(&self, path: Option<&str>) {
-        let x_vals: Vec<f64>;
-        let y_vals: Vec<f64>;
+    #[cfg(feature = "matplotlib")]
+    fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
+        let (xs, ys) = self.scales;
+        let (x_vals, y_vals): (Vec<f64>, Vec<f64>) = self.sample_points().into_iter().map(|p| (xs.inverse(p.0), ys.inverse(p.1))).unzip();
 
-        match self.precomputed {
-            Some(vals) => {
-                x_vals = vals.iter().map(|p| p.0).collect();
-                y_vals = vals.iter().map(|p| p.1).collect();
-            }
-            None => {
-                x_vals = subdivide(self.domain_and_range.0.0, self.domain_and_range.0.1, 10 * self.size.0);
-                y_vals = x_vals.iter().map(|x| (self.func)(*x)).collect();
-            }
-        }
+        let command = format!("plot({x_vals:?}, {y_vals:?})\nplt.{}\nplt.{}", xs.pyplot_command("x"), ys.pyplot_command("y"));
+        let range = (xs.inverse_range(self.domain_and_range.0), ys.inverse_range(self.domain_and_range.1));
+        try_pyplot(&(command + &self.axes_config.pyplot_labels()), self.title, Some(self.axes), Some(range), path)
+    }
 
-        let command = format!("plot({x_vals:?}, {y_vals:?})");
-        pyplot(&command, self.title, Some(self.axes), Some(self.domain_and_range), path);
+    fn svg(&self) -> String {
+        let mut canvas = SvgCanvas::new(self.size, self.domain_and_range);
+        canvas.polyline(&self.sample_points(), self.color);
+        if let Some(c) = self.caption {canvas.caption(c)}
+        canvas.finish(self.axes.then_some(&self.axes_config), self.title)
     }
 }
 
@@ -336,11 +366,26 @@ impl<'a> FuncPlot<'a> {
 /// * `domain_padding` - Proportion of the width of the domain to be padded with. Default is 0.1.
 /// * `range_padding` - Proportion of the height of the range to be padded with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 10).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted graph. Default is Color::Default.
+/// * `x_scale` - Scale of the horizontal axis. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis. Default is Scale::Linear.
 /// 
 /// # Notes
 /// 
+/// On a log scale, only positive values are drawn. The domain and range are always given in the function's own values.
+/// 
 /// Use `.precompute()` to generate and save values to minimize future function calls when plotting.
 /// 
 pub fn function_plot<'a>(func: &'a impl Fn(f64) -> f64) -> FuncPlotBuilder<'a> {
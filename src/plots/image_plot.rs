@@ -6,16 +6,38 @@
 //! * `image_plot` - Generates an ImagePlotBuilder from a table of RGB.
 //! * `convert_from_hsv` - Converts an HSV table to an RGB table.
 //! 
+//! # Notes
+//! 
+//! Text output is monochrome by default. Use `set_render_mode` to display the image
+//! in color in terminals which support it.
+//! 
+
+use std::borrow::Cow;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{CowArray, Ix3};
 
 use crate::{
     helper::{
-        file::{get_current_dir, save_image, save_to_file},
-        mat_plot_lib::pyplot,
-        rendering::RenderableTextBuilder,
+        axes::add_title,
+        color::{Color, join_cells, rgb_to_ansi256, RESET},
+        error::PlotError,
+        file::get_current_dir,
+        svg::SvgCanvas,
+        table::Pixels,
+    },
+    plots::{
+        array_plot::array_plot,
+        traits::{impl_plot_outputs, Plot},
     },
-    plots::array_plot::array_plot,
 };
-use rayon::prelude::*;
+use crate::helper::parallel::*;
+#[cfg(feature = "image-export")]
+use crate::helper::file::try_save_pixels;
+#[cfg(any(feature = "image-export", feature = "matplotlib"))]
+use crate::helper::error::warn_on_err;
+#[cfg(feature = "matplotlib")]
+use crate::helper::mat_plot_lib::try_pyplot;
 
 fn hsv_to_rgb(hsv: (u8, u8, u8)) -> (u8, u8, u8) {
     let (h, s, v) = hsv;
@@ -44,32 +66,52 @@ fn hsv_to_rgb(hsv: (u8, u8, u8)) -> (u8, u8, u8) {
     (r, g, b)
 }
 
+/// How an image is displayed as text.
+/// 
+/// # Variants
+/// 
+/// * `Monochrome` - Shades each pixel by its brightness. Works everywhere.
+/// * `TrueColor` - Draws two pixels per charachter with '▀', using 24-bit foreground and background colors.
+/// * `Ansi256` - The same as `TrueColor`, but with the closest of the 256 extended colors, for terminals without 24-bit color.
+/// 
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum RenderMode {
+    #[default]
+    Monochrome,
+    TrueColor,
+    Ansi256,
+}
+
 /// Builder for an Image Plot
 /// Set various options for the image.
 /// 
 /// # Options
 /// 
-/// * `img` - Input table of (R, G, B) representing the image.
+/// * `img` - Input table of (R, G, B) representing the image. Can be borrowed or owned.
 /// * `path` - Path to save the image to. Default is "./output.png".
+/// * `render_mode` - How the image is displayed as text. Default is RenderMode::Monochrome.
 /// 
 #[derive(Clone)]
 pub struct ImagePlotBuilder<'a> {
-    img: &'a Vec<Vec<(u8, u8, u8)>>,
+    img: Pixels<'a>,
     path: Option<String>,
+    render_mode: Option<RenderMode>,
 }
 
 /// Internal struct representing built values.
 struct ImagePlot<'a> {
-    img: &'a Vec<Vec<(u8, u8, u8)>>,
+    img: Pixels<'a>,
     path: String,
+    render_mode: RenderMode,
 }
 
 impl<'a> ImagePlotBuilder<'a> {
-    /// Create an array plot from a table of data.
-    fn from<'b: 'a>(img: &'b Vec<Vec<(u8, u8, u8)>>) -> Self {
+    /// Create an image plot from a table of pixels.
+    fn from(img: Pixels<'a>) -> Self {
         ImagePlotBuilder {
             img,
             path: None,
+            render_mode: None,
         }
     }
 
@@ -91,46 +133,39 @@ impl<'a> ImagePlotBuilder<'a> {
         self
     }
 
-    fn build(&self) -> ImagePlot {
+    pub fn set_render_mode(&mut self, render_mode: RenderMode) -> &mut Self {
+        self.render_mode = Some(render_mode);
+        self
+    }
+
+    fn build(&self) -> ImagePlot<'_> {
         ImagePlot {
-            img: self.img,
+            img: self.img.view(),
             path: self.path.clone().unwrap_or_else(|| get_current_dir() + &"output.png"),
+            render_mode: self.render_mode.unwrap_or_default(),
         }
     }
 
-    /// Returns a monochrome text render as a string
-    pub fn as_string(&self) -> String {
-        self.build().as_string()
-    }
-
-    /// Displays a monochrome text render with println
-    pub fn print(&self) {
-        self.build().print();
+    /// Saves a text render to a file
+    pub fn save_as_text(&self, path: &str) {
+        Plot::save(self, path);
     }
 
-    /// Saves a monochrome text render to a file
-    pub fn save_as_text(&self, path: &str) {
-        save_to_file(&self.build().as_string(), path);
+    /// Saves the text content of a plot to a file, returning an error if it fails
+    pub fn try_save_as_text(&self, path: &str) -> Result<(), PlotError> {
+        Plot::try_save(self, path)
     }
 
     /// Saves the image to a file.
+    #[cfg(feature = "image-export")]
     pub fn save(&self) {
-        self.build().save();
+        warn_on_err(self.try_save());
     }
 
-    /// Returns a rendered text builder to render a string
-    pub fn as_image(&self) -> RenderableTextBuilder {
-        RenderableTextBuilder::from(self.build().as_string())
-    }
-
-    /// Displays the plot's data using pyplot
-    pub fn pyplot(&self) {
-        self.build().pyplot(None);
-    }
-
-    /// Saves the plot's data using pyplot
-    pub fn save_pyplot(&self, path: &str) {
-        self.build().pyplot(Some(path));
+    /// Saves the image to a file, returning an error if it fails.
+    #[cfg(feature = "image-export")]
+    pub fn try_save(&self) -> Result<(), PlotError> {
+        self.build().save()
     }
 
     /// Returns the unformatted text content of a plot
@@ -140,30 +175,77 @@ impl<'a> ImagePlotBuilder<'a> {
     }
 }
 
+impl<'a> FromIterator<Vec<(u8, u8, u8)>> for ImagePlotBuilder<'a> {
+    /// Collects rows of pixels into an image plot which owns its data.
+    fn from_iter<I: IntoIterator<Item = Vec<(u8, u8, u8)>>>(iter: I) -> Self {
+        ImagePlotBuilder::from(Pixels::Rows(Cow::Owned(iter.into_iter().collect())))
+    }
+}
+
+impl_plot_outputs!(['a] ImagePlotBuilder<'a>: text, pyplot, svg);
+
 impl<'a> ImagePlot<'a> {
     fn plot(&self) -> String {
-        let brightnesses: Vec<Vec<u32>> = self.img.par_iter().map(|row| row.iter().map(|p| p.0 as u32 + p.1 as u32 + p.2 as u32).collect()).collect();
-        array_plot(&brightnesses)
-        .set_axes(false)
-        .set_title(&self.path)
-        .as_string()
+        match self.render_mode {
+            RenderMode::Monochrome => {
+                let brightnesses: Vec<Vec<u32>> = (0..self.img.height()).into_par_iter().map(|i| self.img.row(i).iter().map(|p| p.0 as u32 + p.1 as u32 + p.2 as u32).collect()).collect();
+                array_plot(&brightnesses)
+                .set_axes(false)
+                .set_title(&self.path)
+                .as_string()
+            }
+            RenderMode::TrueColor => add_title(&self.color_plot(|(r, g, b)| Color::TrueColor(r, g, b)), self.path.clone()),
+            RenderMode::Ansi256 => add_title(&self.color_plot(|p| Color::Ansi256(rgb_to_ansi256(p))), self.path.clone()),
+        }
+    }
+
+    /// Draws each pair of rows as a single row of '▀', with the upper pixel as
+    /// the foreground color and the lower pixel as the background color.
+    fn color_plot(&self, to_color: impl Fn((u8, u8, u8)) -> Color + Sync) -> String {
+        (0..self.img.height().div_ceil(2))
+        .into_par_iter()
+        .map(|j| {
+            let rows: Vec<Cow<[(u8, u8, u8)]>> = (2 * j..(2 * j + 2).min(self.img.height())).map(|i| self.img.row(i)).collect();
+            let cells: Vec<String> = (0..rows[0].len()).map(|i| {
+                let fg = to_color(rows[0][i]).fg_code();
+                // An image with an odd height has no lower pixel for the last row, and a ragged one may not either
+                let bg = rows.get(1).and_then(|r| r.get(i)).map(|p| to_color(*p).bg_code()).unwrap_or_default();
+                format!("{fg}{bg}▀{RESET}")
+            }).collect();
+
+            join_cells(&cells)
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
     }
 
     fn as_string(&self) -> String {
         self.plot()
     }
 
-    fn print(&self) {
-        println!("{}", self.as_string());
+    #[cfg(feature = "matplotlib")]
+    fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
+        let command = format!("imshow(np.array({:?}))", self.img.rows().collect::<Vec<_>>());
+        try_pyplot(&command, None, None, None, path)
     }
 
-    fn pyplot(&self, path: Option<&str>) {
-        let command = format!("imshow(np.array({:?}))", self.img);
-        pyplot(&command, None, None, None, path);
+    fn svg(&self) -> String {
+        let (width, height) = (self.img.width(), self.img.height());
+
+        let cells: Vec<Vec<Option<Color>>> = self.img
+            .rows()
+            .map(|row| row.iter().map(|&(r, g, b)| Some(Color::TrueColor(r, g, b))).collect())
+            .collect();
+
+        // Each charachter of the text plot is two pixels tall, so pixels are square (except for an odd height)
+        let mut canvas = SvgCanvas::new((width as u32, height.div_ceil(2) as u32), ((0., width as f64), (0., height as f64)));
+        canvas.cells(&cells);
+        canvas.finish(None, Some(&self.path))
     }
 
-    fn save(&self) {
-        save_image(&self.img, &self.path);
+    #[cfg(feature = "image-export")]
+    fn save(&self) -> Result<(), PlotError> {
+        try_save_pixels(&self.img, &self.path)
     }
 }
 
@@ -172,20 +254,53 @@ impl<'a> ImagePlot<'a> {
 /// # Example
 /// 
 /// ```
-/// use cgrustplot::plots::image_plot::image_plot;
+/// use cgrustplot::plots::image_plot::{image_plot, RenderMode};
 /// 
 /// let image: Vec<Vec<(u8, u8, u8)>> = (0..1080).map(|r| (0..1920).map(|c| (0.01 * r as f64).sin() * (0.01 * c as f64).sin()).map(|x| (127. * (1. + x)) as u8).map(|x| (x, x, x)).collect()).collect();
+/// # #[cfg(feature = "image-export")]
 /// image_plot(&image).set_rel_path("testoutput/doctest_image_plot.png").save();
 /// 
+/// // Display a smaller version in the terminal, in color
+/// let small: Vec<Vec<(u8, u8, u8)>> = (0..20).map(|r| (0..40).map(|c| ((6 * c) as u8, (12 * r) as u8, 128)).collect()).collect();
+/// image_plot(&small).set_render_mode(RenderMode::TrueColor).print();
+/// 
 /// ```
 /// 
 /// # Options
 /// 
-/// * `img` - Input table of (R, G, B) representing the image.
+/// * `img` - Input table of (R, G, B) representing the image. Can be borrowed or owned.
 /// * `path` - Path to save the image to. Default is "./output.png".
+/// * `render_mode` - How the image is displayed as text. Default is RenderMode::Monochrome.
+/// 
+pub fn image_plot<'a>(img: impl Into<Cow<'a, [Vec<(u8, u8, u8)>]>>) -> ImagePlotBuilder<'a> {
+    ImagePlotBuilder::from(Pixels::Rows(img.into()))
+}
+
+/// Creates an image from a 3D ndarray of pixels, without copying it.
+/// 
+/// Takes an owned `Array3<u8>` or a borrowed `ArrayView3<u8>` (e.g. from `.view()`) of shape (height, width, 3),
+/// where the last axis is the (R, G, B) channels. All options are the same as for `image_plot`.
+/// 
+/// # Example
+/// 
+/// ```
+/// # #[cfg(all(feature = "ndarray", feature = "image-export"))] {
+/// use cgrustplot::plots::image_plot::image_plot_from_ndarray;
+/// use ndarray::Array3;
+/// 
+/// let image = Array3::from_shape_fn((20, 40, 3), |(r, c, ch)| [(6 * c) as u8, (12 * r) as u8, 128][ch]);
+/// image_plot_from_ndarray(image.view()).set_rel_path("testoutput/doctest_image_plot_ndarray.png").save();
+/// # }
+/// ```
+/// 
+/// # Notes
 /// 
-pub fn image_plot<'a>(img: &'a Vec<Vec<(u8, u8, u8)>>) -> ImagePlotBuilder<'a> {
-    ImagePlotBuilder::from(img)
+/// Panics if the last axis does not have length 3.
+#[cfg(feature = "ndarray")]
+pub fn image_plot_from_ndarray<'a>(img: impl Into<CowArray<'a, u8, Ix3>>) -> ImagePlotBuilder<'a> {
+    let img = img.into();
+    assert_eq!(img.shape()[2], 3, "Images must be an array of shape (height, width, 3)");
+    ImagePlotBuilder::from(Pixels::Array(img))
 }
 
 /// Converts a HSV image (represented as a table of (H, S, V)) to an RGB image.
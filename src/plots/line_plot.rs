@@ -6,16 +6,27 @@
 //! * `line_plot` - Generates a LinePlotBuilder from some data.
 //! 
 
+use std::borrow::Cow;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{CowArray, Ix2};
+
 use crate::{
     helper::{
         math::{pad_range, max_always, min_always},
-        axes::add_opt_axes_and_opt_titles,
-        mat_plot_lib::pyplot,
-        file::save_to_file,
-        rendering::RenderableTextBuilder,
+        axes::{add_opt_axes_and_opt_titles_and_opt_legend, add_opt_caption, AxesConfig, AxesOptions},
+        color::Color,
+        svg::SvgCanvas,
+        scale::{scale_points, warn_unscaled_points, Scale},
+        table::Points,
+    },
+    plots::{
+        function_plot::function_plot,
+        traits::{impl_plot_outputs, impl_plot_options},
     },
-    plots::function_plot::function_plot,
 };
+#[cfg(feature = "matplotlib")]
+use crate::helper::{error::PlotError, mat_plot_lib::try_pyplot};
 
 fn binary_search_closest(v: &Vec<f64>, el: f64) -> (usize, usize) {
     let mut l: usize = 0;
@@ -46,48 +57,63 @@ fn binary_search_closest(v: &Vec<f64>, el: f64) -> (usize, usize) {
 /// 
 /// # Options
 ///  
-/// * `data` - Input data of a list of points.
+/// * `data` - Input data of a list of points. Can be borrowed (`&Vec<(f64, f64)>`, `&[(f64, f64)]`) or owned (`Vec<(f64, f64)>`).
 /// * `domain` - Specified domain to plot the data over. Default is computed.
 /// * `range` - Specified range to display the data over. Default is computed.
 /// * `domain_padding` - Proportion of the width of the domain to be padded with. Default is 0.1.
 /// * `range_padding` - Proportion of the height of the range to be padded with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 10).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted line. Default is Color::Default.
+/// * `x_scale` - Scale of the horizontal axis. Points which can't be shown on it are left out. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis. Points which can't be shown on it are left out. Default is Scale::Linear.
 ///  
 #[derive(Clone)]
 pub struct LinePlotBuilder<'a> {
-    data: &'a Vec<(f64, f64)>,
+    data: Points<'a, f64>,
     domain: Option<(f64, f64)>,
     range: Option<(f64, f64)>,
     domain_padding: Option<f64>,
     range_padding: Option<f64>,
-    size: Option<(u32, u32)>,
-    title: Option<&'a str>,
-    axes: Option<bool>,
+    options: AxesOptions<'a>,
+    color: Option<Color>,
 }
 
 /// Internal struct representing built values.
 struct LinePlot<'a> {
-    data: &'a Vec<(f64, f64)>,
+    data: Points<'a, f64>,
     domain_and_range: ((f64, f64), (f64, f64)),
     size: (u32, u32),
     title: Option<&'a str>,
+    caption: Option<&'a str>,
     axes: bool,
+    axes_config: AxesConfig,
+    color: Color,
+    scales: (Scale, Scale),
 }
 
 impl<'a> LinePlotBuilder<'a> {
-    /// Create an array plot from a table of data.
-    fn from<'b: 'a>(data: &'b Vec<(f64, f64)>) -> LinePlotBuilder<'a> {
+    /// Create a line plot from a list of points.
+    fn from(data: Points<'a, f64>) -> LinePlotBuilder<'a> {
         LinePlotBuilder {
             data,
             domain: None,
             range: None,
             domain_padding: None,
             range_padding: None,
-            size: None,
-            title: None,
-            axes: None,
+            options: AxesOptions::default(),
+            color: None,
         }
     }
 
@@ -111,81 +137,50 @@ impl<'a> LinePlotBuilder<'a> {
         self
     }
 
-    pub fn set_size(&mut self, size: (u32, u32)) -> &mut Self {
-        self.size = Some(size);
-        self
-    }
-
-    pub fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self {
-        self.title = Some(title);
+    pub fn set_color(&mut self, color: Color) -> &mut Self {
+        self.color = Some(color);
         self
     }
 
-    pub fn set_axes(&mut self, do_axes: bool) -> &mut Self {
-        self.axes = Some(do_axes);
-        self
-    }
+    fn build(&self) -> LinePlot<'_> {
+        // Everything is computed in scaled space
+        let scales = self.options.scales();
+        let points = || scale_points(self.data.iter(), scales);
+        warn_unscaled_points(self.data.iter(), scales);
 
-    fn build(&self) -> LinePlot {
-        let domain = self.domain.unwrap_or_else(||
+        let domain = self.domain.and_then(|d| scales.0.forward_range(d, "domain")).unwrap_or_else(||
             pad_range(
-                self.domain.unwrap_or_else(||(
-                    min_always(&self.data.iter().map(|p| p.0).collect(), 0.),
-                    max_always(&self.data.iter().map(|p| p.0).collect(), 0.),
-                )),
+                (
+                    min_always(&points().map(|p| p.0).collect(), 0.),
+                    max_always(&points().map(|p| p.0).collect(), 0.),
+                ),
                 self.domain_padding.unwrap_or(0.1),
             )
         );
 
-        let range = self.range.unwrap_or_else(||
+        let range = self.range.and_then(|r| scales.1.forward_range(r, "range")).unwrap_or_else(||
             pad_range(
-                self.range.unwrap_or_else(||(
-                    min_always(&self.data.iter().map(|p| p.1).collect(), 0.),
-                    max_always(&self.data.iter().map(|p| p.1).collect(), 0.),
-                )),
+                (
+                    min_always(&points().map(|p| p.1).collect(), 0.),
+                    max_always(&points().map(|p| p.1).collect(), 0.),
+                ),
                 self.range_padding.unwrap_or(0.1),
             )
         );
         
         LinePlot {
-            data: self.data,
+            data: self.data.view(),
             domain_and_range: (domain, range),
-            size: self.size.unwrap_or((60, 10)),
-            title: self.title,
-            axes: self.axes.unwrap_or(true),
+            size: self.options.size((60, 10)),
+            title: self.options.title,
+            caption: self.options.caption,
+            axes: self.options.axes(),
+            axes_config: self.options.build_axes_config(),
+            color: self.color.unwrap_or_default(),
+            scales,
         }
     }
 
-    /// Returns the plotted data as a string
-    pub fn as_string(&self) -> String {
-        self.build().as_string()
-    }
-
-    /// Displays the plotted data with println
-    pub fn print(&self) {
-        self.build().print();
-    }
-
-    /// Saves the text content of a plot to a file
-    pub fn save(&self, path: &str) {
-        save_to_file(&self.build().as_string(), path);
-    }
-
-    /// Returns a rendered text builder to render a string
-    pub fn as_image(&self) -> RenderableTextBuilder {
-        RenderableTextBuilder::from(self.build().as_string())
-    }
-
-    /// Displays the plot's data using pyplot
-    pub fn pyplot(&self) {
-        self.build().pyplot(None);
-    }
-
-    /// Saves the plot's data using pyplot
-    pub fn save_pyplot(&self, path: &str) {
-        self.build().pyplot(Some(path));
-    }
-
     /// Returns the unformatted text content of a plot
     #[allow(dead_code)]
     pub(crate) fn plot(&self) -> String {
@@ -193,12 +188,29 @@ impl<'a> LinePlotBuilder<'a> {
     }
 }
 
+impl<'a> FromIterator<(f64, f64)> for LinePlotBuilder<'a> {
+    /// Collects points into a line plot which owns its data.
+    fn from_iter<I: IntoIterator<Item = (f64, f64)>>(iter: I) -> Self {
+        LinePlotBuilder::from(Points::Pairs(Cow::Owned(iter.into_iter().collect())))
+    }
+}
+
+impl_plot_outputs!(['a] LinePlotBuilder<'a>: text, save, pyplot, svg);
+
+impl_plot_options!(['a] LinePlotBuilder<'a>: title, axes, size, scale);
+
 impl<'a> LinePlot<'a> {
+    /// The points in scaled space.
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        scale_points(self.data.iter(), self.scales)
+    }
+
     pub fn plot(&self) -> String {
-        let mut d = self.data.clone();
+        let mut d: Vec<(f64, f64)> = self.points().collect();
         d.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Greater));
 
         let f = move |x: f64| {
+            if d.is_empty() {return f64::NAN}
             if d[0].0 > x {return d[0].1}
             if d[d.len() - 1].0 < x {return d[d.len() - 1].1}
             // if d[0].0 > x || x > d[d.len() - 1].0 {return f64::NAN;}
@@ -222,7 +234,8 @@ impl<'a> LinePlot<'a> {
             .set_domain_padding(0.)
             .set_range_padding(0.)
             .set_axes(self.axes)
-            .set_size(self.size);
+            .set_size(self.size)
+            .set_color(self.color);
 
         if let Some(title) = self.title {
             plot.set_title(title);
@@ -232,19 +245,29 @@ impl<'a> LinePlot<'a> {
     }
 
     pub fn as_string(&self) -> String {
-        add_opt_axes_and_opt_titles(&self.plot(), self.domain_and_range, self.axes, self.title)
+        add_opt_caption(add_opt_axes_and_opt_titles_and_opt_legend(&self.plot(), self.domain_and_range, self.axes.then_some(&self.axes_config), self.title, None), self.caption)
     }
 
-    pub fn print(&self) {
-        println!("{}", self.as_string());
-    }
-
-    fn pyplot(&self, path: Option<&str>) {
+    #[cfg(feature = "matplotlib")]
+    fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
         let x_vals: Vec<f64> = self.data.iter().map(|p| p.0).collect();
         let y_vals: Vec<f64> = self.data.iter().map(|p| p.1).collect();
 
-        let command = format!("plot({x_vals:?}, {y_vals:?})");
-        pyplot(&command, self.title, Some(self.axes), Some(self.domain_and_range), path);
+        let (xs, ys) = self.scales;
+        let command = format!("plot({x_vals:?}, {y_vals:?})\nplt.{}\nplt.{}", xs.pyplot_command("x"), ys.pyplot_command("y"));
+        let range = (xs.inverse_range(self.domain_and_range.0), ys.inverse_range(self.domain_and_range.1));
+        try_pyplot(&(command + &self.axes_config.pyplot_labels()), self.title, Some(self.axes), Some(range), path)
+    }
+
+    fn svg(&self) -> String {
+        // Points are connected from left to right, the same as the text plot
+        let mut d: Vec<(f64, f64)> = self.points().collect();
+        d.sort_unstable_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Greater));
+
+        let mut canvas = SvgCanvas::new(self.size, self.domain_and_range);
+        canvas.polyline(&d, self.color);
+        if let Some(c) = self.caption {canvas.caption(c)}
+        canvas.finish(self.axes.then_some(&self.axes_config), self.title)
     }
 }
 
@@ -275,15 +298,55 @@ impl<'a> LinePlot<'a> {
 /// 
 /// # Options
 ///  
-/// * `data` - Input data of a list of points.
+/// * `data` - Input data of a list of points. Can be borrowed (`&Vec<(f64, f64)>`, `&[(f64, f64)]`) or owned (`Vec<(f64, f64)>`).
 /// * `domain` - Specified domain to plot the data over. Default is computed.
 /// * `range` - Specified range to display the data over. Default is computed.
 /// * `domain_padding` - Proportion of the width of the domain to be padded with. Default is 0.1.
 /// * `range_padding` - Proportion of the height of the range to be padded with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 10).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted line. Default is Color::Default.
+/// * `x_scale` - Scale of the horizontal axis. Points which can't be shown on it are left out. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis. Points which can't be shown on it are left out. Default is Scale::Linear.
 ///  
-pub fn line_plot<'a>(data: &'a Vec<(f64, f64)>) -> LinePlotBuilder<'a> {
-    LinePlotBuilder::from(data)
+pub fn line_plot<'a>(data: impl Into<Cow<'a, [(f64, f64)]>>) -> LinePlotBuilder<'a> {
+    LinePlotBuilder::from(Points::Pairs(data.into()))
+}
+
+/// Displays a line graph of the rows of a 2D ndarray, without copying it.
+/// 
+/// Takes an owned `Array2` or a borrowed `ArrayView2` (e.g. from `.view()`) of shape (n, 2), where each row is an (x, y) point.
+/// All options are the same as for `line_plot`.
+/// 
+/// # Example
+/// 
+/// ```
+/// # #[cfg(feature = "ndarray")] {
+/// use cgrustplot::plots::line_plot::line_plot_from_ndarray;
+/// use ndarray::Array2;
+/// 
+/// let points = Array2::from_shape_fn((50, 2), |(i, j)| if j == 0 {i as f64} else {(i as f64).sqrt()});
+/// line_plot_from_ndarray(points.view()).set_size((30, 10)).print();
+/// # }
+/// ```
+/// 
+/// # Notes
+/// 
+/// Panics if the array does not have 2 columns.
+#[cfg(feature = "ndarray")]
+pub fn line_plot_from_ndarray<'a>(data: impl Into<CowArray<'a, f64, Ix2>>) -> LinePlotBuilder<'a> {
+    let data = data.into();
+    assert_eq!(data.ncols(), 2, "Points must be an array of shape (n, 2)");
+    LinePlotBuilder::from(Points::Array(data))
 }
\ No newline at end of file
@@ -0,0 +1,167 @@
+//! # Live Plot
+//! Redraws a plot in place in the terminal, for data which changes over time.
+//!
+//! # Functions
+//!
+//! * `live_plot` - Generates a LivePlot which draws to the standard output.
+//! * `live_plot_to` - Generates a LivePlot which draws to any writer.
+//!
+//! # Notes
+//!
+//! Each draw moves the cursor back up over the previous plot and clears it with escape codes,
+//! so the scrollback only ever holds one copy of the plot. Anything else printed between
+//! draws will be drawn over. Lines which are wider than the terminal wrap, which is accounted
+//! for if the terminal size is known; `set_fit_terminal(true)` avoids wrapping entirely.
+//!
+
+use std::{
+    io::{stdout, Stdout, Write},
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+use crate::{
+    helper::{
+        color::visible_len,
+        error::{PlotError, warn_on_err},
+        terminal::{clear_lines, terminal_size},
+    },
+    plots::traits::Plot,
+};
+
+/// A handle to a plot drawn in the terminal, which can be redrawn in place.
+///
+/// # Options
+///
+/// * `refresh_rate` - Most times per second that `update` redraws the plot. A rate of 0 or less redraws on every update. Default is 10.
+///
+pub struct LivePlot<W: Write = Stdout> {
+    out: W,
+    refresh_rate: Option<f64>,
+    last_draw: Option<Instant>,
+    lines: usize,
+}
+
+impl<W: Write> LivePlot<W> {
+    /// Create a live plot which hasn't been drawn yet.
+    fn new(out: W) -> Self {
+        LivePlot {
+            out,
+            refresh_rate: None,
+            last_draw: None,
+            lines: 0,
+        }
+    }
+
+    pub fn set_refresh_rate(&mut self, per_second: f64) -> &mut Self {
+        self.refresh_rate = Some(per_second);
+        self
+    }
+
+    /// Time between redraws.
+    ///
+    /// A rate which isn't positive (including NaN) doesn't throttle redraws at all,
+    /// and one too small to represent waits forever.
+    fn interval(&self) -> Duration {
+        match self.refresh_rate.unwrap_or(10.) {
+            r if r > 0. => Duration::try_from_secs_f64(1. / r).unwrap_or(Duration::MAX),
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Whether enough time has passed since the last draw for `update` to redraw the plot.
+    pub fn is_due(&self) -> bool {
+        self.last_draw.is_none_or(|t| t.elapsed() >= self.interval())
+    }
+
+    /// Sleeps until the plot is due to be redrawn.
+    pub fn wait(&self) {
+        if let Some(t) = self.last_draw {
+            sleep(self.interval().saturating_sub(t.elapsed()));
+        }
+    }
+
+    /// Redraws the plot if it is due, returning whether it was drawn.
+    ///
+    /// The plot is only rendered if it is drawn, so this can be called as often as new data arrives.
+    pub fn update<P: Plot + ?Sized>(&mut self, plot: &P) -> bool {
+        if !self.is_due() {
+            return false;
+        }
+        self.draw(plot);
+        true
+    }
+
+    /// Redraws the plot if it is due, returning whether it was drawn, or an error if writing fails
+    pub fn try_update<P: Plot + ?Sized>(&mut self, plot: &P) -> Result<bool, PlotError> {
+        if !self.is_due() {
+            return Ok(false);
+        }
+        self.try_draw(plot)?;
+        Ok(true)
+    }
+
+    /// Redraws the plot immediately, such as for the final value of the data.
+    pub fn draw<P: Plot + ?Sized>(&mut self, plot: &P) {
+        warn_on_err(self.try_draw(plot));
+    }
+
+    /// Redraws the plot immediately, returning an error if writing fails
+    pub fn try_draw<P: Plot + ?Sized>(&mut self, plot: &P) -> Result<(), PlotError> {
+        let s = plot.as_string();
+
+        // The first draw has nothing to clear
+        let clear = if self.last_draw.is_some() {clear_lines(self.lines)} else {String::new()};
+
+        writeln!(self.out, "{clear}{s}")
+            .and_then(|_| self.out.flush())
+            .map_err(|source| PlotError::Io {path: "<live plot output>".to_string(), source})?;
+
+        self.lines = screen_lines(&s, terminal_size().map(|t| t.0));
+        self.last_draw = Some(Instant::now());
+        Ok(())
+    }
+}
+
+/// Number of lines a string takes up when printed, including lines which wrap past the terminal width.
+fn screen_lines(s: &str, width: Option<u32>) -> usize {
+    s.split('\n')
+    .map(|line| match width {
+        Some(w) if w > 0 => visible_len(line).div_ceil(w as usize).max(1),
+        _ => 1,
+    })
+    .sum()
+}
+
+/// Generates a LivePlot which draws to the standard output.
+///
+/// # Example
+///
+/// ```
+/// use cgrustplot::plots::{live_plot::live_plot, line_plot::line_plot};
+///
+/// let mut live = live_plot();
+/// live.set_refresh_rate(100.);
+///
+/// let mut loss: Vec<(f64, f64)> = Vec::new();
+///
+/// for step in 0..5 {
+///     loss.push((step as f64, 1. / (step as f64 + 1.)));
+///
+///     live.wait();
+///     live.draw(line_plot(&loss).set_size((30, 8)).set_title("loss"));
+/// }
+/// ```
+///
+/// # Options
+///
+/// * `refresh_rate` - Most times per second that `update` redraws the plot. A rate of 0 or less redraws on every update. Default is 10.
+///
+pub fn live_plot() -> LivePlot<Stdout> {
+    LivePlot::new(stdout())
+}
+
+/// Generates a LivePlot which draws to any writer, such as standard error.
+pub fn live_plot_to<W: Write>(out: W) -> LivePlot<W> {
+    LivePlot::new(out)
+}
@@ -12,8 +12,16 @@
 //! * `function_plot`
 //! * `line_plot`
 //! * `image_plot`
-//! * `animation_plot`
+//! * `animation_plot` - Requires the `animation` feature.
 //! * `region_plot`
+//! * `figure_plot`
+//! * `subplot_grid`
+//! * `live_plot` - Redraws any plot in place in the terminal.
+//! 
+//! # Traits
+//! 
+//! Every builder implements the shared traits in `traits`, so plots of different
+//! types can be stored and used together (e.g. as `Vec<Box<dyn Plot>>`).
 //! 
 
 pub mod array_plot;
@@ -21,5 +29,10 @@ pub mod scatter_plot;
 pub mod function_plot;
 pub mod line_plot;
 pub mod image_plot;
+#[cfg(feature = "animation")]
 pub mod animation_plot;
-pub mod region_plot;
\ No newline at end of file
+pub mod region_plot;
+pub mod figure_plot;
+pub mod subplot_grid;
+pub mod live_plot;
+pub mod traits;
\ No newline at end of file
@@ -10,13 +10,15 @@
 
 
 use crate::helper::{
-    axes::add_opt_axes_and_opt_titles,
+    axes::{add_opt_axes_and_opt_titles_and_opt_legend, add_opt_caption, AxesConfig, AxesOptions},
     charset::subdiv_chars::blocks_two_by_two,
+    color::Color,
     math::{bin_to_u8, pad_range, subdivide},
-    mat_plot_lib::pyplot,
-    file::save_to_file,
-    rendering::RenderableTextBuilder,
+    svg::SvgCanvas,
 };
+use crate::plots::traits::{impl_plot_outputs, impl_plot_options};
+#[cfg(feature = "matplotlib")]
+use crate::helper::{error::PlotError, mat_plot_lib::try_pyplot};
 
 /// Builder for a Region Plot
 /// Set various options for plotting the region.
@@ -27,17 +29,27 @@ use crate::helper::{
 /// * `domain_and_range` - Domain and range over which to plot the region. Default is ((0, size.0), (0, size.1)).
 /// * `padding` - Proportion of domain and range to pad the plot with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 30).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted region. Default is Color::Default.
 /// 
 #[derive(Clone)]
 pub struct RegionPlotBuilder<'a> {
     pred: Box<&'a dyn Fn(f64, f64) -> bool>,
     domain_and_range: Option<((f64, f64), (f64, f64))>,
     padding: Option<f64>,
-    size: Option<(u32, u32)>,
-    title: Option<&'a str>,
-    axes: Option<bool>,
+    options: AxesOptions<'a>,
+    color: Option<Color>,
 }
 
 /// Internal struct representing built values.
@@ -46,7 +58,10 @@ struct RegionPlot<'a> {
     domain_and_range: ((f64, f64), (f64, f64)),
     size: (u32, u32),
     title: Option<&'a str>,
+    caption: Option<&'a str>,
     axes: bool,
+    axes_config: AxesConfig,
+    color: Color,
 }
 
 impl<'a> RegionPlotBuilder<'a> {
@@ -56,9 +71,8 @@ impl<'a> RegionPlotBuilder<'a> {
             pred: Box::new(pred),
             domain_and_range: None,
             padding: None,
-            size: None,
-            title: None,
-            axes: None,
+            options: AxesOptions::default(),
+            color: None,
         }
     }
 
@@ -72,24 +86,14 @@ impl<'a> RegionPlotBuilder<'a> {
         self
     }
 
-    pub fn set_size(&mut self, size: (u32, u32)) -> &mut Self {
-        self.size = Some(size);
-        self
-    }
-
-    pub fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self {
-        self.title = Some(title);
-        self
-    }
-
-    pub fn set_axes(&mut self, do_axes: bool) -> &mut Self {
-        self.axes = Some(do_axes);
+    pub fn set_color(&mut self, color: Color) -> &mut Self {
+        self.color = Some(color);
         self
     }
 
     fn build(&self) -> RegionPlot<'a> {
         // Padding must go before range, as default arg for range is based on padding
-        let size = self.size.unwrap_or((60, 30));
+        let size = self.options.size((60, 30));
         let padding = self.padding.unwrap_or(0.1);
         let domain_and_range = self.domain_and_range.unwrap_or_else(|| ((0., 0.,), (size.0 as f64, size.1 as f64)));
 
@@ -100,41 +104,14 @@ impl<'a> RegionPlotBuilder<'a> {
             pred: self.pred.clone(),
             domain_and_range: domain_and_range,
             size: size,
-            title: self.title,
-            axes: self.axes.unwrap_or(true),
+            title: self.options.title,
+            caption: self.options.caption,
+            axes: self.options.axes(),
+            axes_config: self.options.build_axes_config(),
+            color: self.color.unwrap_or_default(),
         }
     }
 
-    /// Returns the plotted data as a string
-    pub fn as_string(&self) -> String {
-        self.build().as_string()
-    }
-
-    /// Displays the plotted data with println
-    pub fn print(&self) {
-        self.build().print();
-    }
-
-    /// Saves the text content of a plot to a file
-    pub fn save(&self, path: &str) {
-        save_to_file(&self.build().as_string(), path);
-    }
-
-    /// Returns a rendered text builder to render a string
-    pub fn as_image(&self) -> RenderableTextBuilder {
-        RenderableTextBuilder::from(self.build().as_string())
-    }
-
-    /// Displays the plot's data using pyplot
-    pub fn pyplot(&self) {
-        self.build().pyplot(None);
-    }
-
-    /// Saves the plot's data using pyplot
-    pub fn save_pyplot(&self, path: &str) {
-        self.build().pyplot(Some(path));
-    }
-
     /// Returns the unformatted text content of a plot
     #[allow(dead_code)]
     pub(crate) fn plot(&self) -> String {
@@ -142,15 +119,20 @@ impl<'a> RegionPlotBuilder<'a> {
     }
 }
 
+impl_plot_outputs!(['a] RegionPlotBuilder<'a>: text, save, pyplot, svg);
+
+impl_plot_options!(['a] RegionPlotBuilder<'a>: title, axes, size);
+
 impl<'a> RegionPlot<'a> {
    fn plot(&self) -> String {
         let y_values = subdivide(self.domain_and_range.1.0, self.domain_and_range.1.1, self.size.1);
         let x_values = subdivide(self.domain_and_range.0.0, self.domain_and_range.0.1, self.size.0);
 
-        let y_diff = 0.5 * (self.domain_and_range.1.1 - self.domain_and_range.1.0) / (self.size.1 - 1) as f64;
-        let x_diff = 0.5 * (self.domain_and_range.0.1 - self.domain_and_range.0.0) / (self.size.0 - 1) as f64;
+        // Half of the distance between samples. A single sample is treated as if there were two.
+        let y_diff = 0.5 * (self.domain_and_range.1.1 - self.domain_and_range.1.0) / (self.size.1.max(2) - 1) as f64;
+        let x_diff = 0.5 * (self.domain_and_range.0.1 - self.domain_and_range.0.0) / (self.size.0.max(2) - 1) as f64;
         
-        y_values
+        let o = y_values
         .into_iter()
         .map(|y|
             x_values.clone()
@@ -165,18 +147,17 @@ impl<'a> RegionPlot<'a> {
         )
         .rev()
         .collect::<Vec<String>>()
-        .join("\n")
-    }
+        .join("\n");
 
-    pub fn as_string(&self) -> String {
-        add_opt_axes_and_opt_titles(&self.plot(), self.domain_and_range, self.axes, self.title)
+        self.color.paint(&o)
     }
 
-    pub fn print(&self) {
-        println!("{}", self.as_string());
+    pub fn as_string(&self) -> String {
+        add_opt_caption(add_opt_axes_and_opt_titles_and_opt_legend(&self.plot(), self.domain_and_range, self.axes.then_some(&self.axes_config), self.title, None), self.caption)
     }
 
-    pub fn pyplot(&self, path: Option<&str>) {
+    #[cfg(feature = "matplotlib")]
+    pub fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
         let y_values = subdivide(self.domain_and_range.1.0, self.domain_and_range.1.1, 2 * self.size.1);
         let x_values = subdivide(self.domain_and_range.0.0, self.domain_and_range.0.1, 2 * self.size.0);
         
@@ -194,7 +175,14 @@ impl<'a> RegionPlot<'a> {
         
         let command = format!("imshow({:?})", tab);
 
-        pyplot(&command, self.title, Some(self.axes), None, path);
+        try_pyplot(&(command + &self.axes_config.pyplot_labels()), self.title, Some(self.axes), None, path)
+    }
+
+    fn svg(&self) -> String {
+        let mut canvas = SvgCanvas::new(self.size, self.domain_and_range);
+        canvas.region(*self.pred, (2 * self.size.0, 2 * self.size.1), self.color);
+        if let Some(c) = self.caption {canvas.caption(c)}
+        canvas.finish(self.axes.then_some(&self.axes_config), self.title)
     }
 }
 
@@ -249,8 +237,19 @@ impl<'a> RegionPlot<'a> {
 /// * `domain_and_range` - Domain and range over which to plot the region. Default is ((0, size.0), (0, size.1)).
 /// * `padding` - Proportion of domain and range to pad the plot with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 10). Default is (60, 30).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted region. Default is Color::Default.
 /// 
 pub fn region_plot<'a>(pred: &'a impl Fn(f64, f64) -> bool) -> RegionPlotBuilder<'a> {
     RegionPlotBuilder::from(pred)
@@ -7,52 +7,68 @@
 //! * `list_as_points` - Enumerates a list to generate 2D points. (e.g. [8, 3, 4, 6] -> [(0, 8), (1, 3), (2, 4), (3, 6)]).
 //! 
 
+use std::borrow::Cow;
+
+#[cfg(feature = "ndarray")]
+use ndarray::{CowArray, Ix2};
 use num::ToPrimitive;
-use rayon::prelude::*;
+use crate::helper::parallel::*;
 
 use crate::helper::{
-    arrays::{padded_vec_to, table_indices_to_counts, transpose_table},
-    axes::add_opt_axes_and_opt_titles,
+    arrays::{padded_vec_to, point_counts, points_to_f64, table_indices_to_counts, transpose_table},
+    axes::{add_opt_axes_and_opt_titles_and_opt_legend, add_opt_caption, AxesConfig, AxesOptions},
     charset::subdiv_chars::*,
-    mat_plot_lib::pyplot,
-    math::{bin_to_u8, ciel_div, max_always, min_always, pad_range},
-    file::save_to_file,
-    rendering::RenderableTextBuilder,
+    color::Color,
+    svg::SvgCanvas,
+    math::{bin_to_u8, ciel_div, max_always, pad_range},
+    scale::{scale_points, warn_unscaled_points, Scale},
+    table::Points,
 };
+use crate::plots::traits::{impl_plot_outputs, impl_plot_options};
+#[cfg(feature = "matplotlib")]
+use crate::helper::{error::PlotError, mat_plot_lib::try_pyplot};
+
+/// Extends (min, max) bounds to include a value, ignoring NaN
+fn extend_bounds(bounds: Option<(f64, f64)>, v: f64) -> Option<(f64, f64)> {
+    if v.is_nan() {return bounds}
+    Some(bounds.map_or((v, v), |(low, high)| (low.min(v), high.max(v))))
+}
 
 /// Pads a range by a ratio of it's width
-fn pad_point_range(points: &Vec<(f64, f64)>, padding: f64) -> ((f64, f64), (f64, f64)) {
+fn pad_point_range(points: impl Iterator<Item = (f64, f64)>, padding: f64) -> ((f64, f64), (f64, f64)) {
+    let (x, y) = points.fold((None, None), |(x, y), p| (extend_bounds(x, p.0), extend_bounds(y, p.1)));
     (
-        pad_range((min_always(&points.iter().map(|i| i.0).collect(), 0.),
-            max_always(&points.iter().map(|i| i.0).collect(), 0.)), padding),
-        pad_range((min_always(&points.iter().map(|i| i.1).collect(), 0.),
-            max_always(&points.iter().map(|i| i.1).collect(), 0.)), padding)
+        pad_range(x.unwrap_or((0., 0.)), padding),
+        pad_range(y.unwrap_or((0., 0.)), padding)
     )
 }
 
-pub(crate) fn padded_point_range<T: PartialOrd + Copy + ToPrimitive>(points: &Vec<(T, T)>, padding: f64) -> ((f64, f64), (f64, f64)) {
+pub(crate) fn padded_point_range<T: PartialOrd + Copy + ToPrimitive>(points: impl Iterator<Item = (T, T)>, padding: f64) -> ((f64, f64), (f64, f64)) {
     pad_point_range(
-        &points
-            .iter()
-            .map(|t|
-                (match t.0.to_f64() {Some(val) => val, None => 0.},
-                match t.1.to_f64() {Some(val) => val, None => 0.})
-            ).collect::<Vec<(f64, f64)>>(),
+        points.map(|t|
+            (match t.0.to_f64() {Some(val) => val, None => 0.},
+            match t.1.to_f64() {Some(val) => val, None => 0.})
+        ),
         padding
     )
 }
 
-pub(crate) fn determine_char_set<T: ToPrimitive + PartialEq>(points: &Vec<(T, T)>, range: ((f64, f64), (f64, f64)), size: (u32, u32)) -> (Vec<char>, (u32, u32)) {   
-    let pts: Vec<&(T, T)> = points.iter().filter(|i| i.0 == i.0 && i.1 == i.1).collect();
+pub(crate) fn determine_char_set<T: ToPrimitive + PartialEq>(points: &[(T, T)], range: ((f64, f64), (f64, f64)), size: (u32, u32)) -> (Vec<char>, (u32, u32)) {   
+    char_set_for_counts(table_indices_to_counts(points, range, size))
+}
 
-    let v: Vec<f64> = table_indices_to_counts(&points, range, size).into_iter().flatten().map(|i| i as f64).collect();
+/// Determines the charset from the number of points in each cell of the plot
+fn char_set_for_counts(counts: Vec<Vec<u32>>) -> (Vec<char>, (u32, u32)) {
+    // Every non-NaN point is counted somewhere in the table
+    let num_points = counts.iter().flatten().sum::<u32>() as usize;
+    let v: Vec<f64> = counts.into_iter().flatten().map(|i| i as f64).collect();
 
     let mean_v: f64 = v.iter().sum::<f64>() / v.len() as f64;
     let max_v: f64 = max_always(&v, 0.);
 
-    if mean_v <= 1. && max_v * ciel_div(pts.len(), 20) as f64 <= 2. {
+    if mean_v <= 1. && max_v * ciel_div(num_points, 20) as f64 <= 2. {
         (dots_one_by_one(), (1, 1))
-    } else if mean_v <= 1.5 || max_v * ciel_div(pts.len(), 10) as f64 <= 4. {
+    } else if mean_v <= 1.5 || max_v * ciel_div(num_points, 10) as f64 <= 4. {
         (blocks_two_by_two(), (2, 2))
     } else {
         (dots_two_by_four(), (2, 4))
@@ -115,45 +131,60 @@ pub(crate) fn bool_arr_plot_string_custom_charset(arr: &Vec<Vec<bool>>, range: (
 /// 
 /// # Options
 /// 
-/// * `data` - Input points.
+/// * `data` - Input points. Can be borrowed (`&Vec<(T, T)>`, `&[(T, T)]`) or owned (`Vec<(T, T)>`).
 /// * `domain_and_range` - Domain and range over which to plot the region. Default is computed.
 /// * `padding` - Proportion of domain and range to pad the plot with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 30).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted points. Default is Color::Default.
 /// * `chars` - Charset to be used for plotting. Any set in `cgrustplot::helper::charset::subdiv_chars` works. Default is computed.
+/// * `x_scale` - Scale of the horizontal axis. Points which can't be shown on it are left out. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis. Points which can't be shown on it are left out. Default is Scale::Linear.
 /// 
 #[derive(Clone)]
 pub struct ScatterPlotBuilder<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> {
-    data: &'a Vec<(T, T)>,
+    data: Points<'a, T>,
     domain_and_range: Option<((f64, f64), (f64, f64))>,
     padding: Option<f64>,
-    size: Option<(u32, u32)>,
-    title: Option<&'a str>,
-    axes: Option<bool>,
+    options: AxesOptions<'a>,
+    color: Option<Color>,
     chars: Option<(Vec<char>, (u32, u32))>,
 }
 
 /// Internal struct representing built values.
 struct ScatterPlot<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> {
-    data: &'a Vec<(T, T)>,
+    data: Points<'a, T>,
     domain_and_range: ((f64, f64), (f64, f64)),
     size: (u32, u32),
     title: Option<&'a str>,
+    caption: Option<&'a str>,
     axes: bool,
+    axes_config: AxesConfig,
+    color: Color,
     chars: (Vec<char>, (u32, u32)),
+    scales: (Scale, Scale),
 }
 
 impl<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> ScatterPlotBuilder<'a, T> {
-    /// Create an array plot from a table of data.
-    fn from<'b: 'a>(data: &'b Vec<(T, T)>) -> Self {
+    /// Create a scatter plot from a list of points.
+    fn from(data: Points<'a, T>) -> Self {
         ScatterPlotBuilder {
-            data: data,
+            data,
             domain_and_range: None,
             padding: None,
-            size: None,
-            title: None,
-            axes: None,
+            options: AxesOptions::default(),
+            color: None,
             chars: None,
         }
     }
@@ -168,18 +199,8 @@ impl<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> ScatterPlotBuilde
         self
     }
 
-    pub fn set_size(&mut self, size: (u32, u32)) -> &mut Self {
-        self.size = Some(size);
-        self
-    }
-
-    pub fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self {
-        self.title = Some(title);
-        self
-    }
-
-    pub fn set_axes(&mut self, do_axes: bool) -> &mut Self {
-        self.axes = Some(do_axes);
+    pub fn set_color(&mut self, color: Color) -> &mut Self {
+        self.color = Some(color);
         self
     }
 
@@ -192,88 +213,97 @@ impl<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> ScatterPlotBuilde
         self
     }
 
-    fn build(&self) -> ScatterPlot<T> {
+    fn build(&self) -> ScatterPlot<'_, T> {
         // Padding must go before range, as default arg for range is based on padding
         let padding = self.padding.unwrap_or(0.1);
-        let domain_and_range = self.domain_and_range.unwrap_or_else(|| padded_point_range(&self.data, padding));
-        let size = self.size.unwrap_or((60, 30));
-        let chars = self.chars.clone().unwrap_or_else(|| determine_char_set(&self.data, domain_and_range, size));  // Cloned value is moved into built variant, so the clone would be needed anyway
+
+        // Everything is computed in scaled space
+        let scales = self.options.scales();
+        let points = || scale_points(points_to_f64(self.data.iter()), scales);
+        warn_unscaled_points(points_to_f64(self.data.iter()), scales);
+
+        let domain_and_range = self.domain_and_range
+            .and_then(|(d, r)| Some((scales.0.forward_range(d, "domain")?, scales.1.forward_range(r, "range")?)))
+            .unwrap_or_else(|| padded_point_range(points(), padding));
+        let size = self.options.size((60, 30));
+        let chars = self.chars.clone().unwrap_or_else(|| char_set_for_counts(point_counts(points(), domain_and_range, size)));  // Cloned value is moved into built variant, so the clone would be needed anyway
         
         ScatterPlot {
-            data: self.data,
+            data: self.data.view(),
             domain_and_range,
             size: size,
-            title: self.title,
-            axes: self.axes.unwrap_or(true),
+            title: self.options.title,
+            caption: self.options.caption,
+            axes: self.options.axes(),
+            axes_config: self.options.build_axes_config(),
+            color: self.color.unwrap_or_default(),
             chars: chars,
+            scales,
         }
     }
 
-    /// Returns the plotted data as a string
-    pub fn as_string(&self) -> String {
-        self.build().as_string()
-    }
-
-    /// Displays the plotted data with println
-    pub fn print(&self) {
-        self.build().print();
-    }
-
-    /// Saves the text content of a plot to a file
-    pub fn save(&self, path: &str) {
-        save_to_file(&self.build().as_string(), path);
-    }
-
-    /// Returns a rendered text builder to render a string
-    pub fn as_image(&self) -> RenderableTextBuilder {
-        RenderableTextBuilder::from(self.build().as_string())
-    }
-
-    /// Displays the plot's data using pyplot
-    pub fn pyplot(&self) {
-        self.build().pyplot(None);
-    }
-
-    /// Saves the plot's data using pyplot
-    pub fn save_pyplot(&self, path: &str) {
-        self.build().pyplot(Some(path));
-    }
-
     /// Returns the unformatted text content of a plot
     #[allow(dead_code)]
     pub(crate) fn plot(&self) -> String {
         self.build().plot()
     }
+}
 
+impl<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> FromIterator<(T, T)> for ScatterPlotBuilder<'a, T> {
+    /// Collects points into a scatter plot which owns its data.
+    fn from_iter<I: IntoIterator<Item = (T, T)>>(iter: I) -> Self {
+        ScatterPlotBuilder::from(Points::Pairs(Cow::Owned(iter.into_iter().collect())))
+    }
 }
 
+impl_plot_outputs!(['a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug] ScatterPlotBuilder<'a, T>: text, save, pyplot, svg);
+
+impl_plot_options!(['a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug] ScatterPlotBuilder<'a, T>: title, axes, size, scale);
+
 impl<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> ScatterPlot<'a, T> {
+    /// The points in scaled space.
+    fn points(&self) -> impl Iterator<Item = (f64, f64)> + '_ {
+        scale_points(points_to_f64(self.data.iter()), self.scales)
+    }
+
     fn plot(&self) -> String {
-        let bool_arr: Vec<Vec<bool>> = table_indices_to_counts(&self.data, self.domain_and_range, (self.size.0 * self.chars.1.0, self.size.1 * self.chars.1.1))
+        let bool_arr: Vec<Vec<bool>> = point_counts(self.points(), self.domain_and_range, (self.size.0 * self.chars.1.0, self.size.1 * self.chars.1.1))
             .into_par_iter()
             .map(|i| 
                 i.into_iter()
                 .map(|j| j != 0)
                 .collect()
-            ).collect();
+            )
+            .rev() // Row zero of the counts is the lowest y value, which should be displayed at the bottom
+            .collect();
 
-        bool_arr_plot_string_custom_charset(&bool_arr, (self.size.0 * self.chars.1.0, self.size.1 * self.chars.1.1), self.chars.clone())
+        self.color.paint(&bool_arr_plot_string_custom_charset(&bool_arr, (self.size.0 * self.chars.1.0, self.size.1 * self.chars.1.1), self.chars.clone()))
     }
 
     fn as_string(&self) -> String {
-        add_opt_axes_and_opt_titles(&self.plot(), self.domain_and_range, self.axes, self.title)
-    }
-
-    fn print(&self) {
-        println!("{}", self.as_string());
+        add_opt_caption(add_opt_axes_and_opt_titles_and_opt_legend(&self.plot(), self.domain_and_range, self.axes.then_some(&self.axes_config), self.title, None), self.caption)
     }
 
-    fn pyplot(&self, path: Option<&str>) {
+    #[cfg(feature = "matplotlib")]
+    fn pyplot(&self, path: Option<&str>) -> Result<(), PlotError> {
         let x_data: Vec<T> = self.data.iter().map(|p| p.0).collect();
         let y_data: Vec<T> = self.data.iter().map(|p| p.1).collect();
-        let command = format!("scatter({x_data:?}, {y_data:?})");
+        let (xs, ys) = self.scales;
+        let command = format!("scatter({x_data:?}, {y_data:?})\nplt.{}\nplt.{}", xs.pyplot_command("x"), ys.pyplot_command("y"));
+        let range = (xs.inverse_range(self.domain_and_range.0), ys.inverse_range(self.domain_and_range.1));
+
+        try_pyplot(&(command + &self.axes_config.pyplot_labels()), self.title, Some(self.axes), Some(range), path)
+    }
+
+    fn svg(&self) -> String {
+        let mut canvas = SvgCanvas::new(self.size, self.domain_and_range);
+
+        for p in self.points() {
+            canvas.circle(p, self.color);
+        }
 
-        pyplot(&command, self.title, Some(self.axes), Some(self.domain_and_range), path);
+        if let Some(c) = self.caption {canvas.caption(c)}
+        canvas.finish(self.axes.then_some(&self.axes_config), self.title)
     }
 }
 
@@ -288,32 +318,72 @@ impl<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug> ScatterPlot<'a, T
 /// scatter_plot(&points).set_size((30, 10)).print();
 /// 
 /// // Standard Output:
-/// //       │  ●                           
+/// //       │                           ●  
 /// // 7.360 ┼                              
 /// //       │                              
 /// // 5.440 ┼                              
-/// //       │              ●  ●            
-/// // 3.520 ┼                              
+/// //       │                              
+/// // 3.520 ┼              ●  ●            
 /// //       │                              
 /// // 1.600 ┼                              
 /// //       │                              
-/// // -0.32 ┼                           ●  
+/// // -0.32 ┼  ●                           
 /// //       └┼──────┼──────┼──────┼────────
 /// //        -0.160 0.4000 0.9600 1.5200   
 /// ```
 /// 
 /// # Options
 /// 
-/// * `data` - Input points.
+/// * `data` - Input points. Can be borrowed (`&Vec<(T, T)>`, `&[(T, T)]`) or owned (`Vec<(T, T)>`).
 /// * `domain_and_range` - Domain and range over which to plot the region. Default is computed.
 /// * `padding` - Proportion of domain and range to pad the plot with. Default is 0.1.
 /// * `size` - Dimensions (in characters) of the outputted plot. Default is (60, 30).
+/// * `fit_terminal` - Whether to size the plot to fit the current terminal, in place of `size`. Default is false.
 /// * `title` - Optional title for the plot. Default is None.
+/// * `caption` - Optional caption below the plot, such as a source or footnote. Default is None.
 /// * `axes` - Whether or not to display axes and axes labels. Default is true.
+/// * `axes_color` - Color of the axes and axes labels. Default is Color::Default.
+/// * `x_ticks` - How ticks on the horizontal axis are chosen. Default is Ticks::Even.
+/// * `y_ticks` - How ticks on the vertical axis are chosen. Default is Ticks::Even.
+/// * `x_label` - Title of the horizontal axis. Default is None.
+/// * `y_label` - Title of the vertical axis. Default is None.
+/// * `grid` - Whether to draw dotted gridlines behind the plot at every tick. Default is false.
+/// * `x_lines` - Values to draw vertical reference lines behind the plot at. Default is none.
+/// * `y_lines` - Values to draw horizontal reference lines behind the plot at. Default is none.
+/// * `color` - Color of the plotted points. Default is Color::Default.
 /// * `chars` - Charset to be used for plotting. Any set in `cgrustplot::helper::charset::subdiv_chars` works. Default is computed.
+/// * `x_scale` - Scale of the horizontal axis. Points which can't be shown on it are left out. Default is Scale::Linear.
+/// * `y_scale` - Scale of the vertical axis. Points which can't be shown on it are left out. Default is Scale::Linear.
+/// 
+pub fn scatter_plot<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug>(points: impl Into<Cow<'a, [(T, T)]>>) -> ScatterPlotBuilder<'a, T> {
+    ScatterPlotBuilder::from(Points::Pairs(points.into()))
+}
+
+/// Displays a scatter plot of the rows of a 2D ndarray, without copying it.
+/// 
+/// Takes an owned `Array2` or a borrowed `ArrayView2` (e.g. from `.view()`) of shape (n, 2), where each row is an (x, y) point.
+/// All options are the same as for `scatter_plot`.
+/// 
+/// # Example
+/// 
+/// ```
+/// # #[cfg(feature = "ndarray")] {
+/// use cgrustplot::plots::scatter_plot::scatter_plot_from_ndarray;
+/// use ndarray::Array2;
+/// 
+/// let points = Array2::from_shape_fn((100, 2), |(i, j)| if j == 0 {i as f64} else {(0.1 * i as f64).sin()});
+/// scatter_plot_from_ndarray(points.view()).set_size((30, 10)).print();
+/// # }
+/// ```
+/// 
+/// # Notes
 /// 
-pub fn scatter_plot<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug>(points: &'a Vec<(T, T)>) -> ScatterPlotBuilder<'a, T> {
-    ScatterPlotBuilder::from(points)
+/// Panics if the array does not have 2 columns.
+#[cfg(feature = "ndarray")]
+pub fn scatter_plot_from_ndarray<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug>(points: impl Into<CowArray<'a, T, Ix2>>) -> ScatterPlotBuilder<'a, T> {
+    let points = points.into();
+    assert_eq!(points.ncols(), 2, "Points must be an array of shape (n, 2)");
+    ScatterPlotBuilder::from(Points::Array(points))
 }
 
 /// Enumerates a list to generate 2D points.
@@ -325,6 +395,6 @@ pub fn scatter_plot<'a, T: PartialOrd + Copy + ToPrimitive + std::fmt::Debug>(po
 /// let list = vec![8, 3, 4, 6];
 /// assert_eq!(list_as_points(&list), vec![(0., 8.), (1., 3.), (2., 4.), (3., 6.)]);
 /// ``````
-pub fn list_as_points<T: ToPrimitive>(points: &Vec<T>) -> Vec<(f64, f64)> {
+pub fn list_as_points<T: ToPrimitive>(points: &[T]) -> Vec<(f64, f64)> {
     points.iter().enumerate().map(|(i, p)| (i as f64, p.to_f64().unwrap())).collect()
 }
\ No newline at end of file
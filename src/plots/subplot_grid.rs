@@ -0,0 +1,195 @@
+//! # Subplot Grid
+//! Arranges several plots into rows and columns of a single output.
+//!
+//! # Functions
+//!
+//! * `subplot_grid` - Generates an empty SubplotGridBuilder, which plots can be added to.
+//!
+//! # Notes
+//!
+//! Any plot implementing `traits::Plot` can be added. Each plot is rendered when it is added,
+//! so later changes to the original builder will not be shown in the grid.
+//!
+
+use crate::{
+    helper::{
+        axes::{add_opt_axes_and_opt_titles, add_opt_caption, AxesOptions},
+        color::visible_len,
+    },
+    plots::traits::{impl_plot_outputs, impl_plot_options, Plot},
+};
+
+/// Builder for a Subplot Grid
+/// Add plots and set various options for arranging them.
+///
+/// # Options
+///
+/// * `cells` - The rendered plots, along with their optional titles.
+/// * `columns` - Number of plots in each row. Default is 2.
+/// * `gutter` - Number of blank (columns, rows) between each plot. Default is (4, 1).
+/// * `title` - Optional title for the whole grid. Default is None.
+/// * `caption` - Optional caption below the whole grid. Default is None.
+///
+#[derive(Clone)]
+pub struct SubplotGridBuilder<'a> {
+    cells: Vec<String>,
+    columns: Option<u32>,
+    gutter: Option<(u32, u32)>,
+    options: AxesOptions<'a>,
+}
+
+/// Internal struct representing built values.
+struct SubplotGrid<'a> {
+    cells: &'a Vec<String>,
+    columns: u32,
+    gutter: (u32, u32),
+    title: Option<&'a str>,
+    caption: Option<&'a str>,
+}
+
+impl<'a> SubplotGridBuilder<'a> {
+    /// Create an empty grid.
+    fn new() -> Self {
+        SubplotGridBuilder {
+            cells: Vec::new(),
+            columns: None,
+            gutter: None,
+            options: AxesOptions::default(),
+        }
+    }
+
+    /// Adds a plot to the next cell of the grid.
+    pub fn add<P: Plot + ?Sized>(&mut self, plot: &P) -> &mut Self {
+        self.cells.push(plot.as_string());
+        self
+    }
+
+    /// Adds a plot with a title to the next cell of the grid.
+    pub fn add_titled<P: Plot + ?Sized>(&mut self, plot: &P, title: &str) -> &mut Self {
+        self.cells.push(format!("{title}\n{}", plot.as_string()));
+        self
+    }
+
+    pub fn set_columns(&mut self, columns: u32) -> &mut Self {
+        self.columns = Some(columns);
+        self
+    }
+
+    pub fn set_gutter(&mut self, gutter: (u32, u32)) -> &mut Self {
+        self.gutter = Some(gutter);
+        self
+    }
+
+    fn build(&self) -> SubplotGrid<'_> {
+        SubplotGrid {
+            cells: &self.cells,
+            columns: self.columns.unwrap_or(2).max(1),
+            gutter: self.gutter.unwrap_or((4, 1)),
+            title: self.options.title,
+            caption: self.options.caption,
+        }
+    }
+}
+
+impl_plot_outputs!(['a] SubplotGridBuilder<'a>: text, save);
+
+impl_plot_options!(['a] SubplotGridBuilder<'a>: title);
+
+impl<'a> SubplotGrid<'a> {
+    fn plot(&self) -> String {
+        let columns = self.columns as usize;
+        let cells: Vec<Vec<&str>> = self.cells.iter().map(|c| c.split('\n').collect()).collect();
+
+        // Width of each column is the width of its widest plot
+        let widths: Vec<usize> = (0..columns).map(|c|
+            cells
+            .iter()
+            .skip(c)
+            .step_by(columns)
+            .flat_map(|cell| cell.iter().map(|line| visible_len(line)))
+            .max()
+            .unwrap_or(0)
+        ).collect();
+
+        let h_gutter = " ".repeat(self.gutter.0 as usize);
+
+        cells
+        .chunks(columns)
+        .map(|row| {
+            // Height of each row is the height of its tallest plot
+            let height = row.iter().map(|cell| cell.len()).max().unwrap_or(0);
+
+            (0..height).map(|i|
+                row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, w)| {
+                    let line = cell.get(i).copied().unwrap_or("");
+                    line.to_string() + &" ".repeat(w - visible_len(line))
+                })
+                .collect::<Vec<String>>()
+                .join(&h_gutter)
+                .trim_end()
+                .to_string()
+            ).collect::<Vec<String>>()
+            .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join(&"\n".repeat(self.gutter.1 as usize + 1))
+    }
+
+    fn as_string(&self) -> String {
+        add_opt_caption(add_opt_axes_and_opt_titles(&self.plot(), ((0., 0.), (0., 0.)), false, self.title), self.caption)
+    }
+}
+
+/// Arranges several plots into rows and columns of a single output.
+///
+/// # Example
+///
+/// ```
+/// use cgrustplot::plots::{
+///     subplot_grid::subplot_grid,
+///     function_plot::function_plot,
+///     array_plot::array_plot,
+/// };
+///
+/// let f = |x: f64| x.sin();
+/// let g = |x: f64| x * x;
+/// let data = vec![vec![0, 1, 2, 3], vec![3, 2, 1, 0]];
+///
+/// subplot_grid()
+///     .set_columns(2)
+///     .set_title("Overview")
+///     .add_titled(function_plot(&f).set_domain((-3., 3.)).set_size((20, 5)), "sin(x)")
+///     .add_titled(function_plot(&g).set_domain((-1., 1.)).set_size((20, 5)), "x^2")
+///     .add(&array_plot(&data))
+///     .print();
+///
+/// // Standard Output:
+/// // Overview
+/// // sin(x)                         x^2
+/// // 0.949 ┼           _――――_       0.980 ┼ ‾_              _‾
+/// //       │\         /      ‾_           │   \            /
+/// // 0.000 ┼ ‾_      /         \    0.501 ┼    ‾_        _‾
+/// //       │   ‾_  _‾                     │      ‾―_  _―‾
+/// // -0.94 ┼     ‾‾                 0.022 ┼         ‾‾
+/// //       └┼─────┼─────┼───────          └┼─────┼─────┼───────
+/// //        -3.42 -1.26 0.900              -1.14 -0.42 0.300
+/// //
+/// //       │ ░▓█
+/// // 0.500 ┼█▓░
+/// //       └┼───
+/// ```
+///
+/// # Options
+///
+/// * `cells` - The rendered plots, along with their optional titles.
+/// * `columns` - Number of plots in each row. Default is 2.
+/// * `gutter` - Number of blank (columns, rows) between each plot. Default is (4, 1).
+/// * `title` - Optional title for the whole grid. Default is None.
+/// * `caption` - Optional caption below the whole grid. Default is None.
+///
+pub fn subplot_grid<'a>() -> SubplotGridBuilder<'a> {
+    SubplotGridBuilder::new()
+}
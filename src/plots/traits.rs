@@ -0,0 +1,479 @@
+//! # Plot Traits
+//! Shared interfaces implemented by the plot builders.
+//!
+//! Every builder still has its own inherent `as_string`, `print`, `save`, etc.,
+//! so existing code does not need to import anything. These traits are for when
+//! you want to treat different kinds of plots the same way, such as keeping them
+//! in a `Vec<Box<dyn Plot>>` or writing a function which works for any plot.
+//!
+//! Each output is only written once, as a default method of these traits. The
+//! inherent methods are generated by `impl_plot_outputs!`, and just call them.
+//!
+//! # Traits
+//!
+//! * `Plot` - Output methods for anything which can be rendered as text.
+//! * `PyPlot` - Output methods for anything which can be displayed with matplotlib. Requires the `matplotlib` feature.
+//! * `SvgPlot` - Output methods for anything which can be drawn as an SVG image.
+//! * `TitleOption`, `AxesOption`, `SizeOption`, `ScaleOption` - Common builder options.
+//!   These are sealed: they can be used, but not implemented, outside of this crate,
+//!   so that new options can be added to them without breaking anything.
+//!
+//! # Example
+//!
+//! ```
+//! use cgrustplot::plots::{
+//!     array_plot::array_plot,
+//!     function_plot::function_plot,
+//!     traits::Plot,
+//! };
+//!
+//! let f = |x: f64| x.sin();
+//! let data = vec![vec![0, 1], vec![1, 0]];
+//!
+//! let plots: Vec<Box<dyn Plot + '_>> = vec![
+//!     Box::new(function_plot(&f).set_size((30, 10)).clone()),
+//!     Box::new(array_plot(&data)),
+//! ];
+//!
+//! for plot in plots.iter() {
+//!     plot.print();
+//! }
+//! ```
+//!
+
+use crate::helper::{
+    axes::Ticks,
+    color::Color,
+    error::PlotError,
+    file::{save_to_file, try_save_to_file},
+    rendering::RenderableTextBuilder,
+    scale::Scale,
+};
+#[cfg(feature = "matplotlib")]
+use crate::helper::error::warn_on_err;
+
+/// Output methods shared by every plot which can be rendered as text.
+///
+/// Only `as_string` needs to be implemented. All other outputs are
+/// generated from it, so a new output target only needs to be added here.
+pub trait Plot {
+    /// Returns the plotted data as a string
+    fn as_string(&self) -> String;
+
+    /// Displays the plotted data with println
+    fn print(&self) {
+        println!("{}", self.as_string());
+    }
+
+    /// Saves the text content of a plot to a file
+    fn save(&self, path: &str) {
+        save_to_file(&self.as_string(), path);
+    }
+
+    /// Saves the text content of a plot to a file, returning an error if it fails
+    fn try_save(&self, path: &str) -> Result<(), PlotError> {
+        try_save_to_file(&self.as_string(), path)
+    }
+
+    /// Returns a rendered text builder to render a string
+    fn as_image(&self) -> RenderableTextBuilder<'static> {
+        RenderableTextBuilder::from(self.as_string())
+    }
+}
+
+/// Output methods shared by every plot which can be displayed with matplotlib.
+///
+/// Only the `try_` methods need to be implemented. The others call warn!() on failure.
+#[cfg(feature = "matplotlib")]
+pub trait PyPlot {
+    /// Displays the plot's data using pyplot, returning an error if it fails
+    fn try_pyplot(&self) -> Result<(), PlotError>;
+
+    /// Saves the plot's data using pyplot, returning an error if it fails
+    fn try_save_pyplot(&self, path: &str) -> Result<(), PlotError>;
+
+    /// Displays the plot's data using pyplot
+    fn pyplot(&self) {
+        warn_on_err(self.try_pyplot());
+    }
+
+    /// Saves the plot's data using pyplot
+    fn save_pyplot(&self, path: &str) {
+        warn_on_err(self.try_save_pyplot(path));
+    }
+}
+
+/// Output methods shared by every plot which can be drawn as an SVG image.
+pub trait SvgPlot {
+    /// Returns the plot as an SVG image
+    fn as_svg(&self) -> String;
+
+    /// Saves the plot as an SVG image
+    fn save_svg(&self, path: &str) {
+        save_to_file(&self.as_svg(), path);
+    }
+
+    /// Saves the plot as an SVG image, returning an error if it fails
+    fn try_save_svg(&self, path: &str) -> Result<(), PlotError> {
+        try_save_to_file(&self.as_svg(), path)
+    }
+}
+
+/// Keeps the option traits from being implemented outside of this crate.
+pub(crate) mod sealed {
+    pub trait Sealed {}
+}
+
+/// Builders which can display a title above the plot, and a caption below it.
+pub trait TitleOption<'a>: sealed::Sealed {
+    fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self;
+
+    fn set_caption<'b: 'a>(&mut self, caption: &'b str) -> &mut Self;
+}
+
+/// Builders which can display axes and axes labels.
+pub trait AxesOption: sealed::Sealed {
+    fn set_axes(&mut self, do_axes: bool) -> &mut Self;
+
+    fn set_axes_color(&mut self, color: Color) -> &mut Self;
+
+    fn set_x_ticks(&mut self, ticks: Ticks) -> &mut Self;
+
+    fn set_y_ticks(&mut self, ticks: Ticks) -> &mut Self;
+
+    fn set_x_label(&mut self, label: &str) -> &mut Self;
+
+    fn set_y_label(&mut self, label: &str) -> &mut Self;
+
+    fn set_grid(&mut self, grid: bool) -> &mut Self;
+
+    fn set_x_lines(&mut self, lines: Vec<f64>) -> &mut Self;
+
+    fn set_y_lines(&mut self, lines: Vec<f64>) -> &mut Self;
+}
+
+/// Builders whose output dimensions (in characters) can be set.
+pub trait SizeOption: sealed::Sealed {
+    fn set_size(&mut self, size: (u32, u32)) -> &mut Self;
+
+    /// Sizes the plot to fit the current terminal, in place of `set_size`.
+    fn set_fit_terminal(&mut self, fit: bool) -> &mut Self;
+}
+
+/// Builders whose axes can use a non-linear scale, such as a log scale.
+pub trait ScaleOption: sealed::Sealed {
+    fn set_x_scale(&mut self, scale: Scale) -> &mut Self;
+
+    fn set_y_scale(&mut self, scale: Scale) -> &mut Self;
+}
+
+impl<P: Plot + ?Sized> Plot for Box<P> {
+    fn as_string(&self) -> String {
+        (**self).as_string()
+    }
+}
+
+/// Implements the output traits for a builder, along with inherent methods which call them.
+///
+/// Takes the builder's generic parameters in brackets, the builder's type, and which groups of outputs it has:
+///
+/// * `text` - `Plot`, with `as_string`, `print`, and `as_image`.
+/// * `save` - `save` and `try_save`, from `Plot`. Left out by builders whose `save` writes something else.
+/// * `pyplot` - `PyPlot`, with `pyplot`, `save_pyplot`, and their `try_` versions.
+/// * `svg` - `SvgPlot`, with `as_svg`, `save_svg`, and `try_save_svg`.
+///
+/// The builder's `build()` must return a plot with an `as_string` method, and `pyplot` and `svg`
+/// methods for those groups. For example, `impl_plot_outputs!(['a] FuncPlotBuilder<'a>: text, save, pyplot, svg);`
+macro_rules! impl_plot_outputs {
+    ($gen:tt $builder:ty: $($group:ident),+) => {
+        $($crate::plots::traits::impl_plot_outputs!(@$group $gen $builder);)+
+    };
+
+    (@text [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::Plot for $builder {
+            fn as_string(&self) -> String {
+                self.build().as_string()
+            }
+        }
+
+        impl<$($gen)*> $builder {
+            /// Returns the plotted data as a string
+            pub fn as_string(&self) -> String {
+                $crate::plots::traits::Plot::as_string(self)
+            }
+
+            /// Displays the plotted data with println
+            pub fn print(&self) {
+                $crate::plots::traits::Plot::print(self)
+            }
+
+            /// Returns a rendered text builder to render a string
+            pub fn as_image(&self) -> $crate::helper::rendering::RenderableTextBuilder<'static> {
+                $crate::plots::traits::Plot::as_image(self)
+            }
+        }
+    };
+
+    (@save [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $builder {
+            /// Saves the text content of a plot to a file
+            pub fn save(&self, path: &str) {
+                $crate::plots::traits::Plot::save(self, path)
+            }
+
+            /// Saves the text content of a plot to a file, returning an error if it fails
+            pub fn try_save(&self, path: &str) -> Result<(), $crate::helper::error::PlotError> {
+                $crate::plots::traits::Plot::try_save(self, path)
+            }
+        }
+    };
+
+    (@pyplot [$($gen:tt)*] $builder:ty) => {
+        #[cfg(feature = "matplotlib")]
+        impl<$($gen)*> $crate::plots::traits::PyPlot for $builder {
+            fn try_pyplot(&self) -> Result<(), $crate::helper::error::PlotError> {
+                self.build().pyplot(None)
+            }
+
+            fn try_save_pyplot(&self, path: &str) -> Result<(), $crate::helper::error::PlotError> {
+                self.build().pyplot(Some(path))
+            }
+        }
+
+        #[cfg(feature = "matplotlib")]
+        impl<$($gen)*> $builder {
+            /// Displays the plot's data using pyplot
+            pub fn pyplot(&self) {
+                $crate::plots::traits::PyPlot::pyplot(self)
+            }
+
+            /// Saves the plot's data using pyplot
+            pub fn save_pyplot(&self, path: &str) {
+                $crate::plots::traits::PyPlot::save_pyplot(self, path)
+            }
+
+            /// Displays the plot's data using pyplot, returning an error if it fails
+            pub fn try_pyplot(&self) -> Result<(), $crate::helper::error::PlotError> {
+                $crate::plots::traits::PyPlot::try_pyplot(self)
+            }
+
+            /// Saves the plot's data using pyplot, returning an error if it fails
+            pub fn try_save_pyplot(&self, path: &str) -> Result<(), $crate::helper::error::PlotError> {
+                $crate::plots::traits::PyPlot::try_save_pyplot(self, path)
+            }
+        }
+    };
+
+    (@svg [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::SvgPlot for $builder {
+            fn as_svg(&self) -> String {
+                self.build().svg()
+            }
+        }
+
+        impl<$($gen)*> $builder {
+            /// Returns the plot as an SVG image
+            pub fn as_svg(&self) -> String {
+                $crate::plots::traits::SvgPlot::as_svg(self)
+            }
+
+            /// Saves the plot as an SVG image
+            pub fn save_svg(&self, path: &str) {
+                $crate::plots::traits::SvgPlot::save_svg(self, path)
+            }
+
+            /// Saves the plot as an SVG image, returning an error if it fails
+            pub fn try_save_svg(&self, path: &str) -> Result<(), $crate::helper::error::PlotError> {
+                $crate::plots::traits::SvgPlot::try_save_svg(self, path)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_plot_outputs;
+
+/// Implements the option traits for a builder, along with inherent setters which call them.
+///
+/// Takes the builder's generic parameters in brackets, the builder's type, and which groups of options it has:
+///
+/// * `title` - `TitleOption`, with `set_title` and `set_caption`.
+/// * `axes` - `AxesOption`, with `set_axes`, `set_axes_color`, the ticks, labels, gridlines, and reference lines.
+/// * `size` - `SizeOption`, with `set_size` and `set_fit_terminal`.
+/// * `scale` - `ScaleOption`, with `set_x_scale` and `set_y_scale`.
+///
+/// The options are stored in the builder's `options: AxesOptions<'a>` field, so the builder's lifetime must be `'a`.
+/// For example, `impl_plot_options!(['a] FuncPlotBuilder<'a>: title, axes, size, scale);`
+macro_rules! impl_plot_options {
+    ($gen:tt $builder:ty: $($group:ident),+) => {
+        $crate::plots::traits::impl_plot_options!(@sealed $gen $builder);
+        $($crate::plots::traits::impl_plot_options!(@$group $gen $builder);)+
+    };
+
+    (@sealed [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::sealed::Sealed for $builder {}
+    };
+
+    (@title [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::TitleOption<'a> for $builder {
+            fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self {
+                self.options.title = Some(title);
+                self
+            }
+
+            fn set_caption<'b: 'a>(&mut self, caption: &'b str) -> &mut Self {
+                self.options.caption = Some(caption);
+                self
+            }
+        }
+
+        impl<$($gen)*> $builder {
+            pub fn set_title<'b: 'a>(&mut self, title: &'b str) -> &mut Self {
+                $crate::plots::traits::TitleOption::set_title(self, title)
+            }
+
+            pub fn set_caption<'b: 'a>(&mut self, caption: &'b str) -> &mut Self {
+                $crate::plots::traits::TitleOption::set_caption(self, caption)
+            }
+        }
+    };
+
+    (@axes [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::AxesOption for $builder {
+            fn set_axes(&mut self, do_axes: bool) -> &mut Self {
+                self.options.axes = Some(do_axes);
+                self
+            }
+
+            fn set_axes_color(&mut self, color: $crate::helper::color::Color) -> &mut Self {
+                self.options.color = Some(color);
+                self
+            }
+
+            fn set_x_ticks(&mut self, ticks: $crate::helper::axes::Ticks) -> &mut Self {
+                self.options.x_ticks = Some(ticks);
+                self
+            }
+
+            fn set_y_ticks(&mut self, ticks: $crate::helper::axes::Ticks) -> &mut Self {
+                self.options.y_ticks = Some(ticks);
+                self
+            }
+
+            fn set_x_label(&mut self, label: &str) -> &mut Self {
+                self.options.x_label = Some(label.to_string());
+                self
+            }
+
+            fn set_y_label(&mut self, label: &str) -> &mut Self {
+                self.options.y_label = Some(label.to_string());
+                self
+            }
+
+            fn set_grid(&mut self, grid: bool) -> &mut Self {
+                self.options.grid = Some(grid);
+                self
+            }
+
+            fn set_x_lines(&mut self, lines: Vec<f64>) -> &mut Self {
+                self.options.x_lines = Some(lines);
+                self
+            }
+
+            fn set_y_lines(&mut self, lines: Vec<f64>) -> &mut Self {
+                self.options.y_lines = Some(lines);
+                self
+            }
+        }
+
+        impl<$($gen)*> $builder {
+            pub fn set_axes(&mut self, do_axes: bool) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_axes(self, do_axes)
+            }
+
+            pub fn set_axes_color(&mut self, color: $crate::helper::color::Color) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_axes_color(self, color)
+            }
+
+            pub fn set_x_ticks(&mut self, ticks: $crate::helper::axes::Ticks) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_x_ticks(self, ticks)
+            }
+
+            pub fn set_y_ticks(&mut self, ticks: $crate::helper::axes::Ticks) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_y_ticks(self, ticks)
+            }
+
+            pub fn set_x_label(&mut self, label: &str) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_x_label(self, label)
+            }
+
+            pub fn set_y_label(&mut self, label: &str) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_y_label(self, label)
+            }
+
+            pub fn set_grid(&mut self, grid: bool) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_grid(self, grid)
+            }
+
+            pub fn set_x_lines(&mut self, lines: Vec<f64>) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_x_lines(self, lines)
+            }
+
+            pub fn set_y_lines(&mut self, lines: Vec<f64>) -> &mut Self {
+                $crate::plots::traits::AxesOption::set_y_lines(self, lines)
+            }
+        }
+    };
+
+    (@size [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::SizeOption for $builder {
+            fn set_size(&mut self, size: (u32, u32)) -> &mut Self {
+                self.options.size = Some(size);
+                self
+            }
+
+            fn set_fit_terminal(&mut self, fit: bool) -> &mut Self {
+                self.options.fit_terminal = Some(fit);
+                self
+            }
+        }
+
+        impl<$($gen)*> $builder {
+            pub fn set_size(&mut self, size: (u32, u32)) -> &mut Self {
+                $crate::plots::traits::SizeOption::set_size(self, size)
+            }
+
+            /// Sizes the plot to fit the terminal it is printed in, leaving room for the axes and title.
+            /// 
+            /// If the terminal size can't be determined, `size` is used instead.
+            pub fn set_fit_terminal(&mut self, fit: bool) -> &mut Self {
+                $crate::plots::traits::SizeOption::set_fit_terminal(self, fit)
+            }
+        }
+    };
+
+    (@scale [$($gen:tt)*] $builder:ty) => {
+        impl<$($gen)*> $crate::plots::traits::ScaleOption for $builder {
+            fn set_x_scale(&mut self, scale: $crate::helper::scale::Scale) -> &mut Self {
+                self.options.x_scale = Some(scale.validated());
+                self
+            }
+
+            fn set_y_scale(&mut self, scale: $crate::helper::scale::Scale) -> &mut Self {
+                self.options.y_scale = Some(scale.validated());
+                self
+            }
+        }
+
+        impl<$($gen)*> $builder {
+            pub fn set_x_scale(&mut self, scale: $crate::helper::scale::Scale) -> &mut Self {
+                $crate::plots::traits::ScaleOption::set_x_scale(self, scale)
+            }
+
+            pub fn set_y_scale(&mut self, scale: $crate::helper::scale::Scale) -> &mut Self {
+                $crate::plots::traits::ScaleOption::set_y_scale(self, scale)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_plot_options;
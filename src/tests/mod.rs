@@ -7,7 +7,27 @@ mod test_axes;
 mod test_function_plot;
 mod test_line_plot;
 mod test_image_plot;
+#[cfg(feature = "animation")]
 mod test_animation_plot;
 mod test_region_plot;
+#[cfg(feature = "matplotlib")]
 mod test_mat_plot_lib;
-mod test_save_and_image;
\ No newline at end of file
+mod test_save_and_image;
+mod test_traits;
+mod test_figure_plot;
+mod test_subplot_grid;
+mod test_color;
+mod test_colormap;
+mod test_rendering;
+mod test_svg;
+mod test_error;
+mod test_inputs;
+#[cfg(feature = "ndarray")]
+mod test_ndarray;
+mod test_terminal;
+mod test_live_plot;
+mod test_expression;
+mod test_data;
+mod test_snapshot;
+mod test_scale;
+mod test_time;
\ No newline at end of file
@@ -1,5 +1,5 @@
 #[allow(unused_imports)] // imports are used, but doesn't detect it?
-use crate::helper::axes::*;
+use crate::helper::{axes::*, scale::Scale};
 
 #[allow(dead_code)]
 fn format_nums_test_general(nums: Vec<f64>, max_len: usize, expected: Option<Vec<&str>>) {
@@ -108,4 +108,272 @@ fn test_add_opt_axes_and_opt_titles_1() {
     let e = String::from("test\n");
 
     assert_eq!(l, e);
-}
\ No newline at end of file
+}
+
+#[test]
+fn add_legend_test_1() {
+    let plot = String::from("..........\n..........\n..........\n..........");
+    let entries = vec![("●".to_string(), "a".to_string()), ("―".to_string(), "bc".to_string())];
+
+    let l = add_legend(&plot, &entries, LegendPosition::TopRight);
+    let e = String::from("....┌────┐\n....│● a │\n....│― bc│\n....└────┘");
+
+    assert_eq!(l, e);
+}
+
+#[test]
+fn add_legend_test_2() {
+    // Legends which don't fit inside the plot are cut off
+    let plot = String::from("......\n......\n......");
+    let entries = vec![("●".to_string(), "abcdef".to_string())];
+
+    let l = add_legend(&plot, &entries, LegendPosition::BottomLeft);
+    let e = String::from("┌─────\n│● abc\n└─────");
+
+    assert_eq!(l, e);
+}
+
+#[test]
+fn add_legend_test_3() {
+    let plot = String::from("..\n..");
+    let entries = vec![("●".to_string(), "a".to_string())];
+
+    let r = add_legend(&plot, &entries, LegendPosition::Right);
+    let b = add_legend(&plot, &entries, LegendPosition::Below);
+
+    assert_eq!(r, "..  ┌───┐\n..  │● a│\n    └───┘");
+    assert_eq!(b, "..\n..\n┌───┐\n│● a│\n└───┘");
+}
+
+#[test]
+fn test_add_opt_axes_and_opt_titles_and_opt_legend_1() {
+    // No legend is added if there are no entries
+    let r = String::from("..");
+    let l = add_opt_axes_and_opt_titles_and_opt_legend(&r, ((0., 0.), (0., 0.)), None, Some("test"), Some((&[], LegendPosition::Below)));
+    let e = String::from("test\n..");
+
+    assert_eq!(l, e);
+}
+
+#[allow(dead_code)]
+fn assert_nice_ticks(n: usize, range: (f64, f64), vertical: bool) {
+    let ticks = axis_ticks(n, range, vertical, Scale::Linear, &Ticks::Nice);
+    println!("{ticks:?}");
+
+    let values: Vec<f64> = ticks.iter().map(|t| t.1.parse().unwrap()).collect();
+    let cell = (range.1 - range.0) / n as f64;
+
+    // Each tick is on the charachter its value falls in
+    ticks.iter().zip(values.iter()).for_each(|((pos, _), v)| assert!((pos - v).abs() <= 0.5 * cell + 1e-9));
+
+    // Values are evenly stepped by 1, 2, or 5 times a power of 10
+    if values.len() >= 2 {
+        let step = values[1] - values[0];
+        let mantissa = step / 10f64.powf(step.log10().floor());
+        assert!([1., 2., 5.].iter().any(|m| (m - mantissa).abs() < 1e-6), "step {step}");
+        values.windows(2).for_each(|w| assert!((w[1] - w[0] - step).abs() < 1e-6 * step));
+    }
+
+    if vertical {
+        assert!(ticks.iter().all(|t| t.1.len() <= 5));
+    }
+}
+
+#[test]
+fn nice_ticks_test_1() {
+    let ticks = axis_ticks(40, (0., 20.), false, Scale::Linear, &Ticks::Nice);
+    let labels: Vec<&str> = ticks.iter().map(|t| t.1.as_str()).collect();
+    assert_eq!(labels, vec!["0", "2", "4", "6", "8", "10", "12", "14", "16", "18", "20"]);
+
+    assert_nice_ticks(60, (-1.36, 3.2), false);
+    assert_nice_ticks(10, (-4.6, 0.3), true);
+    assert_nice_ticks(30, (0.0012, 0.0047), false);
+    assert_nice_ticks(8, (-250000., 910000.), true);
+    assert_nice_ticks(5, (0., 1.), false);
+}
+
+#[test]
+fn nice_ticks_test_2() {
+    // Labels still fit between ticks on the horizontal axis
+    let ticks = axis_ticks(30, (1e6, 1e6 + 7.), false, Scale::Linear, &Ticks::Nice);
+    println!("{ticks:?}");
+
+    let positions: Vec<usize> = ticks.iter().map(|t| ((t.0 - 1e6) / 7. * 30.) as usize).collect();
+    ticks.iter().zip(positions.windows(2)).for_each(|(t, w)| assert!(t.1.len() < w[1] - w[0]));
+
+    // On a log scale, ticks are at powers of 10
+    let log_ticks = axis_ticks(10, (-0.5, 4.5), true, Scale::Log10, &Ticks::Nice);
+    let labels: Vec<&str> = log_ticks.iter().map(|t| t.1.as_str()).collect();
+    assert_eq!(labels, vec!["1", "10", "100", "1000", "10000"]);
+}
+
+#[test]
+fn nice_ticks_test_3() {
+    let config = AxesConfig {x_ticks: Ticks::Nice, y_ticks: Ticks::Nice, ..Default::default()};
+    let plot = vec![" ".repeat(20); 5].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 10.), (-1., 1.)), Some(&config), None, None);
+    println!("{o}");
+
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines.len(), 7);
+    assert!(lines[0].starts_with("1  ┼") && lines[2].starts_with("0  ┼") && lines[4].starts_with("-1 ┼"));
+    assert!(lines[6].trim_start().starts_with("0   2   4   6   8"));
+
+    // Even ticks are the default
+    assert_eq!(AxesConfig::default().x_ticks, Ticks::Even);
+}
+
+#[test]
+fn custom_ticks_test_1() {
+    let config = AxesConfig {
+        x_ticks: Ticks::custom([(0., "zero"), (5., "five"), (100., "off the plot")]),
+        y_ticks: Ticks::custom([(0., "a very long label name")]),
+        ..Default::default()
+    };
+    let plot = vec![" ".repeat(20); 5].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 10.), (-1., 1.)), Some(&config), None, None);
+    println!("{o}");
+
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines.len(), 7);
+
+    // Labels longer than 12 charachters are truncated
+    assert!(lines[2].starts_with("a very long… ┼"));
+
+    // Each label starts under its tick, and values outside of the plot are left out
+    let axis: Vec<char> = lines[5].chars().collect();
+    let labels: Vec<char> = lines[6].chars().collect();
+    for label in ["zero", "five"] {
+        let i = lines[6].chars().collect::<String>().find(label).unwrap();
+        assert_eq!(axis[i], '┼');
+        assert_eq!(labels[i], label.chars().next().unwrap());
+    }
+    assert!(!o.contains("off"));
+}
+
+#[test]
+fn categorical_ticks_test_1() {
+    let data = [("first-experiment", 1.), ("second-experiment", 2.), ("third-experiment", 3.)];
+    let (points, ticks) = categorical_points(&data);
+    assert_eq!(points, vec![(0., 1.), (1., 2.), (2., 3.)]);
+
+    let config = AxesConfig {x_ticks: ticks, ..Default::default()};
+    let plot = vec![" ".repeat(20); 3].join("\n");
+
+    // Labels which don't fit side by side are drawn vertically
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((-0.5, 2.5), (0., 1.)), Some(&config), None, None);
+    println!("{o}");
+
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines.len(), 3 + 1 + 12);
+    assert_eq!(lines[4].split_whitespace().collect::<Vec<&str>>(), vec!["f", "s", "t"]);
+    assert_eq!(lines[15].split_whitespace().collect::<Vec<&str>>(), vec!["…", "…", "…"]);
+
+    // Categories which are too close together are thinned out
+    let keys: Vec<String> = (0..30).map(|i| format!("k{i}")).collect();
+    let config = AxesConfig {x_ticks: Ticks::categories(keys), ..Default::default()};
+    let plot = vec![" ".repeat(30); 3].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((-0.5, 29.5), (0., 1.)), Some(&config), None, None);
+    println!("{o}");
+
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines[4].trim().chars().filter(|c| *c == 'k').count(), 15);
+    assert!(lines[3].contains("┼─┼"));
+}
+
+#[test]
+fn axis_labels_test_1() {
+    let config = AxesConfig {x_label: Some("time".to_string()), y_label: Some("val".to_string()), ..Default::default()};
+    let plot = vec![" ".repeat(20); 5].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 1.), (0., 1.)), Some(&config), None, None);
+    println!("{o}");
+
+    // The y label is drawn downwards in its own column, and the x label is centered under the plot
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines.len(), 5 + 2 + 1);
+    assert_eq!(lines.iter().map(|l| l.chars().next().unwrap()).collect::<String>(), " val    ");
+    assert_eq!(lines[7].trim(), "time");
+    assert_eq!(lines[7].find("time"), Some(2 + 7 + 8));
+
+    // A y label which is taller than the plot goes above it, and long labels are cut off
+    let config = AxesConfig {x_label: Some("x".repeat(50)), y_label: Some("values".to_string()), ..Default::default()};
+    let plot = vec![" ".repeat(20); 2].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 1.), (0., 1.)), Some(&config), None, None);
+    println!("{o}");
+
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines[0].trim(), "values");
+    assert!(lines[1].starts_with("      │"));
+    assert!(lines.last().unwrap().ends_with('…'));
+    assert!(lines.iter().all(|l| l.chars().count() == lines[1].chars().count()));
+}
+
+#[test]
+fn caption_test_1() {
+    let o = add_opt_caption(add_opt_axes_and_opt_titles(&"##".to_string(), ((0., 0.), (0., 0.)), false, Some("title")), Some("Source: test"));
+    assert_eq!(o, "title\n##\nSource: test");
+
+    assert_eq!(add_opt_caption("##".to_string(), None), "##");
+}
+
+#[test]
+fn grid_lines_test_1() {
+    let config = AxesConfig {grid: true, x_ticks: Ticks::custom([(0.5, "a"), (2.5, "b")]), y_ticks: Ticks::custom([(0.5, "c")]), ..Default::default()};
+    let plot = ["    ", " #  ", "    "].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 4.), (0., 3.)), Some(&config), None, None);
+    println!("{o}");
+
+    // Gridlines are drawn at each tick, under the data, with vertical lines over horizontal ones
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert_eq!(lines[0], "  │┊ ┊ ");
+    assert_eq!(lines[1], "  │┊#┊ ");
+    assert_eq!(lines[2], "c ┼┊┈┊┈");
+}
+
+#[test]
+fn reference_lines_test_1() {
+    let config = AxesConfig {x_lines: vec![0., 100.], y_lines: vec![0.], ..Default::default()};
+    let plot = ["     ", "  #  ", "     "].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((-2.5, 2.5), (-1.5, 1.5)), Some(&config), None, None);
+    println!("{o}");
+
+    // Lines outside of the plot are left out, and the data stays on top
+    let rows: Vec<String> = o.split('\n').take(3).map(|l| l.chars().skip_while(|c| *c != '│' && *c != '┼').skip(1).collect()).collect();
+    assert_eq!(rows, vec!["  ╎  ", "╌╌#╌╌", "  ╎  "]);
+
+    // Lines aren't drawn without axes, or into a legend inside the plot
+    let legend = [("*".to_string(), "ab".to_string())];
+    let wide = vec![" ".repeat(9); 3].join("\n");
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&wide, ((-2.5, 2.5), (-1.5, 1.5)), Some(&config), None, Some((&legend, LegendPosition::TopLeft)));
+    println!("{o}");
+    assert!(o.contains("│* ab│"));
+    assert_eq!(add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((-2.5, 2.5), (-1.5, 1.5)), None, None, None), plot);
+}
+
+#[test]
+fn secondary_axis_test_1() {
+    let config = AxesConfig {secondary_range: Some((0., 100.)), secondary_ticks: Ticks::custom([(50., "50%")]), secondary_label: Some("pct".to_string()), ..Default::default()};
+    let plot = ["    ", " #  ", "    "].join("\n");
+
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 4.), (0., 3.)), Some(&config), None, None);
+    println!("{o}");
+
+    // The secondary axis is on the right, with its own ticks and a vertical title beside them
+    let lines: Vec<&str> = o.split('\n').collect();
+    assert!(lines[0].ends_with("    │     p"));
+    assert!(lines[1].ends_with(" #  ┼ 50% c"));
+    assert!(lines[2].ends_with("    │     t"));
+    assert!(lines[3].contains('┘'));
+
+    // Without a secondary range, the label is ignored
+    let config = AxesConfig {secondary_label: Some("pct".to_string()), ..Default::default()};
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&plot, ((0., 4.), (0., 3.)), Some(&config), None, None);
+    assert!(!o.contains('┘') && !o.contains('p'));
+}
@@ -0,0 +1,134 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::{
+        axes::*,
+        color::*,
+    },
+    plots::{
+        array_plot::array_plot,
+        figure_plot::figure_plot,
+        scatter_plot::scatter_plot,
+        subplot_grid::subplot_grid,
+    },
+};
+
+#[test]
+fn color_codes_test_1() {
+    assert_eq!(Color::Ansi16(0).fg_code(), "\x1b[30m");
+    assert_eq!(Color::Ansi16(15).fg_code(), "\x1b[97m");
+    assert_eq!(Color::Ansi16(3).bg_code(), "\x1b[43m");
+    assert_eq!(Color::Ansi256(7).bg_code(), "\x1b[48;5;7m");
+    assert_eq!(Color::TrueColor(255, 0, 10).fg_code(), "\x1b[38;2;255;0;10m");
+}
+
+#[test]
+fn split_cells_test_1() {
+    // Nested styles apply to every charachter until a reset
+    let s = "a\x1b[1m\x1b[32mbc\x1b[0md";
+    let cells = split_cells(s);
+
+    assert_eq!(cells, vec!["a", "\x1b[1m\x1b[32mb\x1b[0m", "\x1b[1m\x1b[32mc\x1b[0m", "d"]);
+    assert_eq!(cells.iter().map(|c| visible_char(c)).collect::<String>(), "abcd");
+    assert_eq!(join_cells(&cells), "a\x1b[1m\x1b[32mbc\x1b[0md");
+    assert_eq!(visible_len(s), 4);
+}
+
+#[test]
+fn split_cells_test_2() {
+    // A stray escape followed by a multibyte charachter
+    let s = "a\x1bé b";
+
+    assert_eq!(visible_len(s), 3);
+    assert_eq!(strip_ansi(s), "a b");
+    assert_eq!(split_cells(s).iter().map(|c| visible_char(c)).collect::<String>(), "a b");
+}
+
+#[test]
+fn colored_axes_test_1() {
+    // Colored plots have the same layout as uncolored ones
+    let plot = "██  \n ██ \n  ██";
+    let colored = Color::Ansi256(100).paint(plot);
+
+    let o = add_axes(plot, ((0., 4.), (0., 3.)));
+    let c = add_axes(&colored, ((0., 4.), (0., 3.)));
+
+    println!("{c}");
+
+    assert_eq!(strip_ansi(&c), o);
+}
+
+#[test]
+fn colored_axes_test_2() {
+    let config = AxesConfig {color: Color::RED, ..Default::default()};
+    let o = add_opt_axes_and_opt_titles_and_opt_legend(&"█".to_string(), ((0., 1.), (0., 1.)), Some(&config), None, None);
+
+    println!("{o}");
+
+    // Only the axes are colored
+    assert!(o.contains("\x1b[31m└"));
+    assert!(o.contains("█") && !o.contains("\x1b[31m█"));
+}
+
+#[test]
+fn colored_scatter_test_1() {
+    let pts = vec![(0., 0.), (1., 1.)];
+
+    let o = scatter_plot(&pts).set_size((4, 4)).set_axes(false).as_string();
+    let c = scatter_plot(&pts).set_size((4, 4)).set_axes(false).set_color(Color::BLUE).as_string();
+
+    assert_eq!(c, Color::BLUE.paint(&o));
+}
+
+#[test]
+fn colored_array_test_1() {
+    let data = vec![vec![0, 1, 2]];
+
+    let o = array_plot(&data).set_axes(false).set_colors(vec![Color::GREEN, Color::RED]).as_string();
+
+    assert_eq!(o, " \x1b[32m▒\x1b[0m\x1b[31m█\x1b[0m");
+}
+
+#[test]
+fn colored_figure_test_1() {
+    // Each series keeps its own color where it is drawn over the others
+    let pts = vec![(0., 0.)];
+    let f = |_x: f64| 0.;
+
+    let o = figure_plot()
+        .add_function(&f)
+        .set_color(Color::BLUE)
+        .add_scatter(&pts)
+        .set_color(Color::RED)
+        .set_domain((-1., 1.))
+        .set_range((-1., 1.))
+        .set_size((3, 1))
+        .set_axes(false)
+        .as_string();
+
+    println!("{o}");
+
+    assert_eq!(strip_ansi(&o), "―●―");
+    assert_eq!(o, "\x1b[34m―\x1b[0m\x1b[31m●\x1b[0m\x1b[34m―\x1b[0m");
+}
+
+#[test]
+fn colored_subplot_grid_test_1() {
+    // Escape codes are not counted when aligning cells
+    let data = vec![vec![1, 0]];
+
+    let o = subplot_grid()
+        .set_gutter((1, 0))
+        .add(array_plot(&data).set_axes(false).set_colors(vec![Color::RED]))
+        .add(array_plot(&data).set_axes(false))
+        .as_string();
+
+    assert_eq!(strip_ansi(&o), "█  █");
+}
+
+#[test]
+fn rgb_to_ansi256_test_1() {
+    assert_eq!(rgb_to_ansi256((0, 0, 0)), 16);
+    assert_eq!(rgb_to_ansi256((255, 255, 255)), 231);
+    assert_eq!(rgb_to_ansi256((0, 95, 135)), 24);
+    assert_eq!(rgb_to_ansi256((10, 10, 12)), 232);
+}
@@ -0,0 +1,66 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::helper::{color::Color, colormap::*};
+
+#[test]
+fn colormap_test_1() {
+    // Endpoints of the built-in colormaps
+    assert_eq!(Colormap::viridis().sample(0.), (68, 1, 84));
+    assert_eq!(Colormap::viridis().sample(1.), (253, 231, 37));
+    assert_eq!(Colormap::magma().sample(0.), (0, 0, 4));
+    assert_eq!(Colormap::coolwarm().sample(0.5), (221, 221, 221));
+
+    // Out of bounds and NaN
+    assert_eq!(Colormap::grayscale().sample(-1.), (0, 0, 0));
+    assert_eq!(Colormap::grayscale().sample(2.), (255, 255, 255));
+    assert_eq!(Colormap::grayscale().sample(f64::NAN), (0, 0, 0));
+}
+
+#[test]
+fn colormap_test_2() {
+    let cmap = Colormap::new(vec![(0, 0, 0), (200, 100, 0)]);
+
+    assert_eq!(cmap.reversed().sample(0.), (200, 100, 0));
+    assert_eq!(cmap.reversed().reversed(), cmap);
+
+    // Each band is a single color, with the first and last at either end of the colormap
+    let d = cmap.discretized(3);
+    assert_eq!(d.sample(0.), d.sample(0.3));
+    assert_eq!(d.sample(0.4), (100, 50, 0));
+    assert_eq!(d.sample(0.7), (200, 100, 0));
+    assert_eq!(d.reversed().sample(0.), (200, 100, 0));
+}
+
+#[test]
+fn colormap_test_3() {
+    let cmap = Colormap::new(vec![(255, 0, 0)]);
+
+    assert_eq!(cmap.sample(0.7), (255, 0, 0));
+    assert_eq!(cmap.colors(3), vec![Color::TrueColor(255, 0, 0); 3]);
+}
+
+#[test]
+fn apply_colormap_test_1() {
+    let data = vec![vec![0., f64::NAN], vec![5., 10.]];
+
+    let o = apply_colormap(&data, &Colormap::grayscale());
+
+    assert_eq!(o, vec![vec![(0, 0, 0), (0, 0, 0)], vec![(128, 128, 128), (255, 255, 255)]]);
+}
+
+#[test]
+fn apply_colormap_test_2() {
+    // Tables of one value don't divide by zero
+    let o = apply_colormap(&vec![vec![3., 3.]], &Colormap::grayscale());
+
+    assert_eq!(o, vec![vec![(128, 128, 128), (128, 128, 128)]]);
+}
+
+#[test]
+fn apply_colormap_frames_test_1() {
+    // Bounds are shared across every frame
+    let frames = vec![vec![vec![0.]], vec![vec![4.]], vec![vec![2.]]];
+
+    let o = apply_colormap_frames(&frames, &Colormap::grayscale());
+
+    assert_eq!(o, vec![vec![vec![(0, 0, 0)]], vec![vec![(255, 255, 255)]], vec![vec![(128, 128, 128)]]]);
+}
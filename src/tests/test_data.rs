@@ -0,0 +1,78 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::helper::{data::*, error::PlotError};
+
+#[test]
+fn data_reader_test_1() {
+    // Delimiters are detected from the first line
+    let expected = vec![vec![1., 2.], vec![3., 4.]];
+
+    assert_eq!(data_reader().parse("1,2\n3,4").matrix().unwrap(), expected);
+    assert_eq!(data_reader().parse("1\t2\n3\t4").matrix().unwrap(), expected);
+    assert_eq!(data_reader().parse("1;2\n3;4").matrix().unwrap(), expected);
+    assert_eq!(data_reader().parse("  1   2\n3 4  ").matrix().unwrap(), expected);
+    assert_eq!(data_reader().set_delimiter('|').parse("1|2\n3|4").matrix().unwrap(), expected);
+}
+
+#[test]
+fn data_reader_test_2() {
+    // Header detection
+    let data = data_reader().parse("\"x\",\"y, in m\"\n1,2\n");
+    assert_eq!(data.header(), Some(&["x".to_string(), "y, in m".to_string()][..]));
+    assert_eq!(data.column_index("y, in m"), Some(1));
+    assert_eq!(data.len(), 1);
+
+    // Text in a key column isn't mistaken for a header
+    let data = data_reader().parse("a,1\nb,2");
+    assert_eq!(data.header(), None);
+    assert_eq!(data.len(), 2);
+
+    // The header can be set explicitly
+    let data = data_reader().set_header(true).parse("1,2\n3,4");
+    assert_eq!(data.header(), Some(&["1".to_string(), "2".to_string()][..]));
+    assert_eq!(data_reader().set_header(false).parse("x,y\n1,2").len(), 2);
+}
+
+#[test]
+fn data_reader_test_3() {
+    // Missing cells and short rows are NaN, and comments and blank lines are skipped
+    let data = data_reader().parse("# comment\n1,2,3\n\n4,,NA\n5\n");
+    let m = data.matrix().unwrap();
+
+    assert_eq!(m.len(), 3);
+    assert!(m.iter().all(|r| r.len() == 3));
+    assert_eq!(m[0], vec![1., 2., 3.]);
+    assert!(m[1][1].is_nan() && m[1][2].is_nan());
+    assert!(m[2][1].is_nan() && m[2][2].is_nan());
+
+    assert_eq!(data_reader().set_comment('%').parse("% x\n1,2").len(), 1);
+}
+
+#[test]
+fn data_reader_test_4() {
+    // Errors have the line number in the original text
+    let data = data_reader().parse("x,y\n\n1,2\n# skipped\n3,four\n");
+
+    match data.points(0, 1) {
+        Err(PlotError::Parse {line, message}) => {
+            assert_eq!(line, 5);
+            assert!(message.contains("four"));
+        }
+        r => panic!("Expected a parse error, got {r:?}"),
+    }
+
+    assert!(matches!(data.text_column(2), Err(PlotError::Parse {line: 3, ..})));
+    assert!(matches!(data_reader().read("/this/directory/does/not/exist/data.csv"), Err(PlotError::Io {..})));
+}
+
+#[test]
+fn data_reader_test_5() {
+    // Grouped series keep the order each key first appears in
+    let data = data_reader().parse("run,step,loss\nb,0,3\na,0,1\nb,1,2\na,1,0.5");
+
+    assert_eq!(data.select(&[2, 1]).unwrap()[0], vec![3., 0.]);
+    assert_eq!(data.column(2).unwrap(), vec![3., 1., 2., 0.5]);
+    assert_eq!(data.grouped_points(0, 1, 2).unwrap(), vec![
+        ("b".to_string(), vec![(0., 3.), (1., 2.)]),
+        ("a".to_string(), vec![(0., 1.), (1., 0.5)]),
+    ]);
+}
@@ -0,0 +1,102 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::{
+        arrays::try_transpose_table,
+        error::PlotError,
+        file::{get_current_dir, try_save_to_file},
+        rendering::RenderableTextBuilder,
+    },
+    plots::{
+        array_plot::array_plot,
+        function_plot::function_plot,
+        region_plot::region_plot,
+        traits::Plot,
+    },
+};
+#[allow(unused_imports)]
+#[cfg(feature = "image-export")]
+use crate::helper::file::try_save_image;
+
+#[allow(dead_code)]
+const MISSING_DIR: &str = "/this/directory/does/not/exist/";
+
+#[test]
+fn try_save_to_file_test_1() {
+    let result = try_save_to_file("test", &(MISSING_DIR.to_string() + "plot.txt"));
+
+    assert!(matches!(result, Err(PlotError::Io {..})));
+    assert!(result.unwrap_err().to_string().contains(MISSING_DIR));
+}
+
+#[test]
+fn try_save_to_file_test_2() {
+    let path = get_current_dir() + "testoutput/try_save_to_file_test_2.txt";
+
+    assert!(try_save_to_file("test", &path).is_ok());
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "test");
+}
+
+#[test]
+#[cfg(feature = "image-export")]
+fn try_save_image_test_1() {
+    let img = vec![vec![(0, 0, 0), (0, 0, 0)], vec![(0, 0, 0)]];
+    let result = try_save_image(&img, &(get_current_dir() + "testoutput/try_save_image_test_1.png"));
+
+    assert!(matches!(result, Err(PlotError::InvalidInput(_))));
+}
+
+#[test]
+#[cfg(feature = "image-export")]
+fn try_save_image_test_2() {
+    let img = vec![vec![(0, 0, 0)]];
+    let result = try_save_image(&img, &(MISSING_DIR.to_string() + "image.png"));
+
+    assert!(matches!(result, Err(PlotError::Image {..})));
+}
+
+#[test]
+fn try_transpose_table_test_1() {
+    let empty: Vec<Vec<u32>> = vec![];
+
+    assert!(try_transpose_table(&empty).is_err());
+    assert!(try_transpose_table(&vec![vec![1, 2], vec![3]]).is_err());
+    assert_eq!(try_transpose_table(&vec![vec![1, 2]]).unwrap(), vec![vec![&1], vec![&2]]);
+}
+
+#[test]
+fn try_save_plot_test_1() {
+    let f = |x: f64| x * x;
+
+    assert!(function_plot(&f).try_save(&(MISSING_DIR.to_string() + "plot.txt")).is_err());
+    assert!(function_plot(&f).try_save_svg(&(MISSING_DIR.to_string() + "plot.svg")).is_err());
+    assert!(function_plot(&f).try_save(&(get_current_dir() + "testoutput/try_save_plot_test_1.txt")).is_ok());
+}
+
+#[test]
+fn try_save_plot_test_2() {
+    // Through the Plot trait
+    let d = vec![vec![0, 1], vec![1, 0]];
+    let plot: Box<dyn Plot> = Box::new(array_plot(&d));
+
+    assert!(plot.try_save(&(MISSING_DIR.to_string() + "plot.txt")).is_err());
+}
+
+#[test]
+#[cfg(feature = "image-export")]
+fn try_save_rendered_image_test_1() {
+    let r = RenderableTextBuilder::from("abc".to_string());
+
+    assert!(r.try_save(&(MISSING_DIR.to_string() + "plot.png")).is_err());
+    assert!(r.try_save(&(get_current_dir() + "testoutput/try_save_rendered_image_test_1.png")).is_ok());
+}
+
+#[test]
+fn region_plot_small_size_test_1() {
+    // Previously divided by size - 1
+    let p = |x: f64, y: f64| x < y;
+    let s = region_plot(&p).set_size((1, 1)).set_axes(false).as_string();
+    assert_eq!(s.chars().count(), 1);
+
+    let s = region_plot(&p).set_size((0, 0)).set_axes(false).as_string();
+    assert_eq!(s, "");
+}
@@ -0,0 +1,47 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::helper::expression::*;
+
+#[test]
+fn parse_expression_test_1() {
+    // Precedence and associativity
+    let eval = |s: &str| parse_expression(s, &[]).unwrap().eval(&[]);
+
+    assert_eq!(eval("1 + 2 * 3"), 7.);
+    assert_eq!(eval("(1 + 2) * 3"), 9.);
+    assert_eq!(eval("2 ^ 3 ^ 2"), 512.);
+    assert_eq!(eval("-2 ^ 2"), -4.);
+    assert_eq!(eval("10 - 4 - 3"), 3.);
+    assert_eq!(eval("7 % 4 / 2"), 1.5);
+    assert_eq!(eval("1.5e2 + .5"), 150.5);
+}
+
+#[test]
+fn parse_expression_test_2() {
+    // Variables, constants, and functions
+    let f = parse_expression("max(x, y) + abs(sin(pi * x)) + ln(e)", &["x", "y"]).unwrap();
+
+    assert!((f.eval(&[1., 3.]) - 4.).abs() < 1e-12);
+    assert!((f.eval(&[0.5, -1.]) - 2.5).abs() < 1e-12);
+}
+
+#[test]
+fn parse_expression_test_3() {
+    // Comparisons and logic are 1 or 0
+    let f = parse_expression("x > 0 && (y <= 1 || !(y != 5))", &["x", "y"]).unwrap();
+
+    assert!(f.eval_bool(&[1., 0.]));
+    assert!(f.eval_bool(&[1., 5.]));
+    assert!(!f.eval_bool(&[1., 3.]));
+    assert!(!f.eval_bool(&[-1., 0.]));
+    assert_eq!(f.eval(&[1., 0.]), 1.);
+}
+
+#[test]
+fn parse_expression_test_4() {
+    // Invalid expressions give errors
+    for s in ["", "1 +", "(1", "1)", "2x", "foo(1)", "z", "1 $ 2", "min(1)"] {
+        let result = parse_expression(s, &["x"]);
+        println!("{s:?}: {result:?}");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,169 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::axes::LegendPosition,
+    plots::{figure_plot::*, function_plot::function_plot, scatter_plot::scatter_plot},
+};
+
+#[test]
+fn figure_plot_test_1() {
+    // A single function should look the same as a function plot
+    let f = |x: f64| x * x;
+
+    let o = figure_plot()
+        .add_function(&f)
+        .set_domain((-1., 1.))
+        .set_range((0., 1.))
+        .set_size((20, 5))
+        .set_axes(false)
+        .as_string();
+
+    let e = function_plot(&f)
+        .set_domain((-1., 1.))
+        .set_range((0., 1.))
+        .set_domain_padding(0.)
+        .set_range_padding(0.)
+        .set_size((20, 5))
+        .set_axes(false)
+        .as_string();
+
+    println!("{o}");
+
+    assert_eq!(o, e);
+}
+
+#[test]
+fn figure_plot_test_2() {
+    // A single set of points should look the same as a scatter plot
+    let pts = vec![(1., 2.), (3., 4.), (5., 6.)];
+
+    let o = figure_plot().add_scatter(&pts).set_size((5, 5)).set_axes(false).as_string();
+    let e = scatter_plot(&pts).set_size((5, 5)).set_axes(false).as_string();
+
+    println!("{o}");
+
+    assert_eq!(o, e);
+}
+
+#[test]
+fn figure_plot_test_3() {
+    let pts = vec![(0., 1.), (4., 4.)];
+    let f = |x: f64| 0.5 * x + 1.;
+
+    let o = figure_plot()
+        .add_function(&f)
+        .add_scatter(&pts)
+        .set_domain((0., 4.))
+        .set_range((0., 4.))
+        .set_size((5, 4))
+        .set_axes(false)
+        .as_string();
+    
+    println!("{o}");
+
+    // Both points are drawn over the line, at the bottom left and top right
+    let rows: Vec<Vec<char>> = o.split('\n').map(|r| r.chars().collect()).collect();
+    assert_eq!(rows.len(), 4);
+    assert_eq!(rows[3][0], '●');
+    assert_eq!(rows[0][4], '●');
+    assert!(rows.iter().flatten().filter(|c| **c != ' ' && **c != '●').count() >= 2);
+}
+
+#[test]
+fn figure_plot_test_4() {
+    // Computed domain and range contain every series
+    let pts = vec![(-5., 0.), (0., 10.)];
+    let l = vec![(0., 0.), (8., -3.)];
+
+    let o = figure_plot().add_scatter(&pts).add_line(&l).set_padding(0.).set_size((20, 10)).as_string();
+
+    println!("{o}");
+
+    assert!(o.contains("-4.") && o.contains("●"));
+}
+
+#[test]
+fn figure_plot_test_5() {
+    // Regions are drawn below functions added after them
+    let p = |_x: f64, y: f64| y < 0.;
+    let f = |_x: f64| 0.5;
+
+    let o = figure_plot()
+        .add_region(&p)
+        .add_function(&f)
+        .set_domain((-1., 1.))
+        .set_range((-1., 1.))
+        .set_size((10, 4))
+        .set_axes(false)
+        .as_string();
+
+    println!("{o}");
+
+    let rows: Vec<&str> = o.split('\n').collect();
+    assert!(rows[0].contains('―') || rows[0].contains('_') || rows[0].contains('‾'));
+    assert!(rows[3].chars().all(|c| c == '█'));
+}
+
+
+#[test]
+fn figure_plot_test_6() {
+    // Only labeled series are shown in the legend, with their markers
+    let pts = vec![(0., 0.), (1., 1.)];
+    let f = |x: f64| x;
+    let p = |_x: f64, y: f64| y < 0.;
+
+    let o = figure_plot()
+        .add_scatter(&pts)
+        .set_label("points")
+        .add_function(&f)
+        .add_region(&p)
+        .set_label("region")
+        .set_legend(LegendPosition::Below)
+        .set_size((10, 4))
+        .set_axes(false)
+        .as_string();
+
+    println!("{o}");
+
+    let legend: Vec<&str> = o.split('\n').skip(4).collect();
+    assert_eq!(legend, vec!["┌────────┐", "│● points│", "│█ region│", "└────────┘"]);
+}
+
+#[test]
+fn figure_plot_test_7() {
+    // Series on the secondary axis are drawn over their own range
+    let low = vec![(0., 0.), (4., 1.)];
+    let high = vec![(0., 0.), (4., 1000.)];
+
+    let o = figure_plot()
+        .add_line(&low)
+        .add_line(&high)
+        .set_secondary_axis(true)
+        .set_domain((0., 4.))
+        .set_range((0., 1.))
+        .set_secondary_range((0., 1000.))
+        .set_size((10, 5))
+        .set_axes(false)
+        .as_string();
+
+    let e = figure_plot()
+        .add_line(&low)
+        .set_domain((0., 4.))
+        .set_range((0., 1.))
+        .set_size((10, 5))
+        .set_axes(false)
+        .as_string();
+
+    println!("{o}");
+
+    // Both lines rise across the whole plot, so they cover each other
+    assert_eq!(o, e);
+
+    // A computed secondary range comes from the secondary series only, and is labelled on the right
+    let o = figure_plot().add_line(&low).add_line(&high).set_secondary_axis(true).set_padding(0.).set_size((20, 6)).as_string();
+    println!("{o}");
+    assert!(o.contains("750.0") && o.contains('┘'));
+
+    // In an SVG, the secondary series is also mapped onto the whole plot area
+    let svg = figure_plot().add_line(&high).set_secondary_axis(true).set_domain((0., 4.)).set_range((0., 1.)).set_secondary_range((0., 1000.)).set_size((10, 5)).as_svg();
+    assert!(svg.contains(r#"<polyline points="0.00,100.00 100.00,0.00""#));
+}
@@ -3,6 +3,7 @@ use crate::plots::image_plot::*;
 use std::env::var;
 
 #[test]
+#[cfg(feature = "image-export")]
 fn image_plot_test_1 () {
     if var("NO_EXTERNAL_COMMANDS").is_ok() {return;}
 
@@ -42,4 +43,34 @@ fn image_plot_test_2 () {
     image_plot(&img).print();
 
     assert!(true)
+}
+
+#[test]
+fn image_plot_test_3 () {
+    // Two rows of pixels per row of charachters
+    let img = vec![vec![(255, 0, 0), (0, 255, 0)], vec![(0, 0, 255), (0, 0, 255)]];
+
+    let o = image_plot(&img).set_abs_path("img.png").set_render_mode(RenderMode::TrueColor).as_string();
+
+    assert_eq!(o, "img.png\n\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m\x1b[38;2;0;255;0m\x1b[48;2;0;0;255m▀\x1b[0m");
+}
+
+#[test]
+fn image_plot_test_4 () {
+    // Odd heights leave the background of the last row uncolored
+    let img = vec![vec![(255, 0, 0)], vec![(255, 0, 0)], vec![(128, 128, 128)]];
+
+    let o = image_plot(&img).set_abs_path("img.png").set_render_mode(RenderMode::Ansi256).as_string();
+
+    assert_eq!(o, "img.png\n\x1b[38;5;196m\x1b[48;5;196m▀\x1b[0m\n\x1b[38;5;244m▀\x1b[0m");
+}
+
+#[test]
+fn image_plot_test_5 () {
+    // A ragged image with a shorter lower row leaves the rest of the background uncolored
+    let img = vec![vec![(255, 0, 0), (0, 255, 0)], vec![(0, 0, 255)]];
+
+    let o = image_plot(&img).set_abs_path("img.png").set_render_mode(RenderMode::TrueColor).as_string();
+
+    assert_eq!(o, "img.png\n\x1b[38;2;255;0;0m\x1b[48;2;0;0;255m▀\x1b[0m\x1b[38;2;0;255;0m▀\x1b[0m");
 }
\ No newline at end of file
@@ -0,0 +1,72 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::plots::{
+    array_plot::{array_plot, ArrayPlotBuilder},
+    image_plot::{image_plot, ImagePlotBuilder},
+    line_plot::{line_plot, LinePlotBuilder},
+    scatter_plot::{scatter_plot, ScatterPlotBuilder},
+};
+
+#[allow(dead_code)]
+fn assert_static_send<T: Send + 'static>(_: &T) {}
+
+#[test]
+fn array_plot_inputs_test_1() {
+    let data: Vec<Vec<u32>> = vec![vec![0, 1, 2, 3], vec![3, 2, 1, 0]];
+    let expected = array_plot(&data).as_string();
+
+    // Slices, owned data, and iterators all give the same plot
+    assert_eq!(array_plot(&data[..]).as_string(), expected);
+    assert_eq!(array_plot(data.clone()).as_string(), expected);
+    assert_eq!(data.iter().cloned().collect::<ArrayPlotBuilder<u32>>().as_string(), expected);
+}
+
+#[test]
+fn scatter_plot_inputs_test_1() {
+    let points: Vec<(f64, f64)> = (0..20).map(|i| (i as f64, (i * i) as f64)).collect();
+    let expected = scatter_plot(&points).set_size((20, 10)).as_string();
+
+    let arr: [(f64, f64); 20] = points.clone().try_into().unwrap();
+    assert_eq!(scatter_plot(&arr[..]).set_size((20, 10)).as_string(), expected);
+    assert_eq!(scatter_plot(points.clone()).set_size((20, 10)).as_string(), expected);
+    assert_eq!((0..20).map(|i| (i as f64, (i * i) as f64)).collect::<ScatterPlotBuilder<f64>>().set_size((20, 10)).as_string(), expected);
+}
+
+#[test]
+fn line_plot_inputs_test_1() {
+    let points: Vec<(f64, f64)> = (0..30).map(|i| (i as f64, (0.3 * i as f64).sin())).collect();
+    let expected = line_plot(&points).set_size((30, 8)).as_string();
+
+    assert_eq!(line_plot(&points[..]).set_size((30, 8)).as_string(), expected);
+    assert_eq!(points.iter().copied().collect::<LinePlotBuilder>().set_size((30, 8)).as_string(), expected);
+}
+
+#[test]
+fn owned_builders_test_1() {
+    // Builders which own their data can be returned from functions and sent to threads
+    fn make_plot() -> LinePlotBuilder<'static> {
+        let points: Vec<(f64, f64)> = (0..10).map(|i| (i as f64, i as f64)).collect();
+        let mut plot = line_plot(points);
+        plot.set_size((10, 5)).set_title("owned");
+        plot
+    }
+
+    let plot = make_plot();
+    assert_static_send(&plot);
+    assert_static_send(&array_plot(vec![vec![1, 2], vec![3, 4]]));
+    assert_static_send(&scatter_plot(vec![(1, 2), (3, 4)]));
+    assert_static_send(&image_plot(vec![vec![(0, 0, 0)]]));
+
+    let expected = plot.as_string();
+    let from_thread = std::thread::spawn(move || plot.as_string()).join().unwrap();
+    assert_eq!(from_thread, expected);
+    assert!(from_thread.contains("owned"));
+}
+
+#[test]
+fn image_plot_inputs_test_1() {
+    let img: Vec<Vec<(u8, u8, u8)>> = (0..4).map(|r| (0..6).map(|c| ((40 * c) as u8, (60 * r) as u8, 0)).collect()).collect();
+    let expected = image_plot(&img).as_string();
+
+    assert_eq!(image_plot(img.clone()).as_string(), expected);
+    assert_eq!(img.into_iter().collect::<ImagePlotBuilder>().as_string(), expected);
+}
@@ -0,0 +1,79 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::plots::{live_plot::*, array_plot::array_plot};
+
+#[test]
+fn live_plot_test_1() {
+    // The first draw prints the plot, and later draws clear it first
+    let a = vec![vec![0, 1]];
+    let b = vec![vec![1, 0], vec![0, 1]];
+    let mut out: Vec<u8> = Vec::new();
+
+    let mut live = live_plot_to(&mut out);
+    live.draw(array_plot(&a).set_axes(false));
+    live.draw(array_plot(&b).set_axes(false));
+    live.draw(array_plot(&a).set_axes(false));
+
+    let o = String::from_utf8(out).unwrap();
+    println!("{o:?}");
+
+    assert_eq!(o, " █\n\x1b[1A\r\x1b[J█ \n █\n\x1b[2A\r\x1b[J █\n");
+}
+
+#[test]
+fn live_plot_test_2() {
+    // Updates are skipped until the plot is due to be redrawn
+    let a = vec![vec![0, 1]];
+    let mut out: Vec<u8> = Vec::new();
+
+    let mut live = live_plot_to(&mut out);
+    live.set_refresh_rate(0.001);
+
+    assert!(live.is_due());
+    assert!(live.update(array_plot(&a).set_axes(false)));
+    assert!(!live.is_due());
+    assert!(!live.update(array_plot(&a).set_axes(false)));
+    assert_eq!(live.try_update(array_plot(&a).set_axes(false)).unwrap(), false);
+
+    assert_eq!(String::from_utf8(out).unwrap(), " █\n");
+}
+
+#[test]
+fn live_plot_test_3() {
+    // With a fast refresh rate, waiting makes the plot due again
+    let a = vec![vec![0, 1]];
+    let mut out: Vec<u8> = Vec::new();
+
+    let mut live = live_plot_to(&mut out);
+    live.set_refresh_rate(200.);
+
+    for _ in 0..3 {
+        live.wait();
+        assert!(live.update(array_plot(&a).set_axes(false)));
+    }
+}
+
+#[test]
+fn live_plot_test_4() {
+    // A refresh rate of zero or less redraws on every update, instead of panicking
+    let a = vec![vec![0, 1]];
+
+    for rate in [0., -1., f64::NAN] {
+        let mut out: Vec<u8> = Vec::new();
+        let mut live = live_plot_to(&mut out);
+        live.set_refresh_rate(rate);
+
+        for _ in 0..3 {
+            live.wait();
+            assert!(live.is_due());
+            assert!(live.update(array_plot(&a).set_axes(false)));
+        }
+    }
+
+    // A rate too small to represent never redraws again
+    let mut out: Vec<u8> = Vec::new();
+    let mut live = live_plot_to(&mut out);
+    live.set_refresh_rate(f64::MIN_POSITIVE);
+
+    assert!(live.update(array_plot(&a).set_axes(false)));
+    assert!(!live.is_due());
+}
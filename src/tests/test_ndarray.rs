@@ -0,0 +1,81 @@
+#![allow(unused_imports)] // imports are used, but doesn't detect it?
+
+use crate::plots::{
+    array_plot::{array_plot, array_plot_from_ndarray},
+    image_plot::{image_plot, image_plot_from_ndarray, RenderMode},
+    line_plot::{line_plot, line_plot_from_ndarray},
+    scatter_plot::{scatter_plot, scatter_plot_from_ndarray},
+    traits::SvgPlot,
+};
+use ndarray::{Array2, Array3};
+
+#[test]
+fn array_plot_ndarray_test_1() {
+    let arr = Array2::from_shape_fn((4, 9), |(r, c)| (r * c) % 5);
+    let rows: Vec<Vec<usize>> = arr.rows().into_iter().map(|r| r.to_vec()).collect();
+
+    assert_eq!(array_plot_from_ndarray(arr.view()).as_string(), array_plot(&rows).as_string());
+    assert_eq!(array_plot_from_ndarray(arr.view()).as_svg(), array_plot(&rows).as_svg());
+}
+
+#[test]
+fn array_plot_ndarray_test_2() {
+    // A transposed view isn't contiguous by row, but is still plotted correctly
+    let arr = Array2::from_shape_fn((4, 9), |(r, c)| (r + 2 * c) as f64);
+    let transposed: Vec<Vec<f64>> = (0..9).map(|c| (0..4).map(|r| arr[[r, c]]).collect()).collect();
+
+    assert_eq!(array_plot_from_ndarray(arr.t()).as_string(), array_plot(&transposed).as_string());
+}
+
+#[test]
+fn scatter_plot_ndarray_test_1() {
+    let arr = Array2::from_shape_fn((50, 2), |(i, j)| if j == 0 {i as f64} else {(0.2 * i as f64).cos()});
+    let points: Vec<(f64, f64)> = arr.rows().into_iter().map(|r| (r[0], r[1])).collect();
+
+    assert_eq!(
+        scatter_plot_from_ndarray(arr.view()).set_size((25, 8)).as_string(),
+        scatter_plot(&points).set_size((25, 8)).as_string()
+    );
+}
+
+#[test]
+#[should_panic]
+fn scatter_plot_ndarray_test_2() {
+    scatter_plot_from_ndarray(Array2::<f64>::zeros((5, 3)));
+}
+
+#[test]
+fn line_plot_ndarray_test_1() {
+    let arr = Array2::from_shape_fn((30, 2), |(i, j)| if j == 0 {i as f64} else {(i as f64).sqrt()});
+    let points: Vec<(f64, f64)> = arr.rows().into_iter().map(|r| (r[0], r[1])).collect();
+
+    assert_eq!(
+        line_plot_from_ndarray(arr.view()).set_size((30, 8)).as_string(),
+        line_plot(&points).set_size((30, 8)).as_string()
+    );
+}
+
+#[test]
+fn image_plot_ndarray_test_1() {
+    let arr = Array3::from_shape_fn((5, 8, 3), |(r, c, ch)| [(30 * c) as u8, (50 * r) as u8, 7][ch]);
+    let img: Vec<Vec<(u8, u8, u8)>> = (0..5).map(|r| (0..8).map(|c| (arr[[r, c, 0]], arr[[r, c, 1]], arr[[r, c, 2]])).collect()).collect();
+
+    for mode in [RenderMode::Monochrome, RenderMode::TrueColor, RenderMode::Ansi256] {
+        assert_eq!(
+            image_plot_from_ndarray(arr.view()).set_render_mode(mode).as_string(),
+            image_plot(&img).set_render_mode(mode).as_string()
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "image-export")]
+fn image_plot_ndarray_test_2() {
+    let arr = Array3::from_shape_fn((6, 4, 3), |(r, c, ch)| [(40 * c) as u8, (40 * r) as u8, 200][ch]);
+
+    image_plot_from_ndarray(arr).set_rel_path("testoutput/test_image_plot_ndarray.png").try_save().unwrap();
+    let saved = image::open("testoutput/test_image_plot_ndarray.png").unwrap().to_rgb8();
+
+    assert_eq!(saved.dimensions(), (4, 6));
+    assert_eq!(saved.get_pixel(3, 5).0, [120, 200, 200]);
+}
@@ -0,0 +1,106 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::helper::{
+    color::Color,
+    font::{glyph_mask, CELL_WIDTH, CELL_HEIGHT},
+    rendering::RenderableTextBuilder,
+};
+
+#[test]
+fn render_size_test_1() {
+    let img = RenderableTextBuilder::from("ab\nc".to_string()).set_font_size(16).render();
+
+    assert_eq!(img.len(), 2 * CELL_HEIGHT);
+    assert!(img.iter().all(|row| row.len() == 2 * CELL_WIDTH));
+}
+
+#[test]
+fn render_size_test_2() {
+    let img = RenderableTextBuilder::from("a".to_string()).set_font_size(32).render();
+
+    assert_eq!(img.len(), 2 * CELL_HEIGHT);
+    assert_eq!(img[0].len(), 2 * CELL_WIDTH);
+}
+
+#[test]
+fn render_colors_test_1() {
+    let img = RenderableTextBuilder::from("█ ".to_string())
+        .set_font_color("#00ff00")
+        .set_background_color("white")
+        .set_font_size(16)
+        .render();
+
+    assert!(img.iter().all(|row| row[..CELL_WIDTH].iter().all(|p| *p == (0, 255, 0))));
+    assert!(img.iter().all(|row| row[CELL_WIDTH..].iter().all(|p| *p == (255, 255, 255))));
+}
+
+#[test]
+fn render_ansi_colors_test_1() {
+    let s = Color::RED.paint("█") + &Color::TrueColor(1, 2, 3).bg_code() + " \x1b[0m";
+    let img = RenderableTextBuilder::from(s).set_font_size(16).render();
+
+    assert!(img.iter().all(|row| row[..CELL_WIDTH].iter().all(|p| *p == (205, 0, 0))));
+    assert!(img.iter().all(|row| row[CELL_WIDTH..].iter().all(|p| *p == (1, 2, 3))));
+}
+
+#[test]
+fn glyph_mask_test_1() {
+    assert!(glyph_mask('█').iter().flatten().all(|p| *p));
+    assert!(glyph_mask(' ').iter().flatten().all(|p| !*p));
+    assert!(glyph_mask('⠀').iter().flatten().all(|p| !*p));
+}
+
+#[test]
+fn glyph_mask_test_2() {
+    // Upper left quadrant
+    let m = glyph_mask('▘');
+    for (y, row) in m.iter().enumerate() {
+        for (x, p) in row.iter().enumerate() {
+            assert_eq!(*p, x < CELL_WIDTH / 2 && y < CELL_HEIGHT / 2);
+        }
+    }
+}
+
+#[test]
+fn glyph_mask_test_3() {
+    // Box drawing lines reach the edges of the cell so that they connect
+    let m = glyph_mask('┼');
+    assert!(m[0].iter().any(|p| *p));
+    assert!(m[CELL_HEIGHT - 1].iter().any(|p| *p));
+    assert!(m.iter().all(|row| row.iter().any(|p| *p)));
+    assert!(m.iter().any(|row| row[0] && row[CELL_WIDTH - 1]));
+}
+
+#[test]
+fn glyph_mask_test_4() {
+    // Gridlines and reference lines are broken lines through the center, not the unknown charachter box
+    let unknown = glyph_mask('☃');
+
+    for (c, vertical) in [('┈', false), ('┊', true), ('╌', false), ('╎', true)] {
+        let m = glyph_mask(c);
+        assert_ne!(m, unknown);
+
+        let line: Vec<bool> = if vertical {
+            m.iter().map(|row| row[CELL_WIDTH / 2]).collect()
+        } else {
+            m[CELL_HEIGHT / 2].to_vec()
+        };
+        assert!(line.iter().any(|p| *p));
+        assert!(line.iter().any(|p| !*p));
+        assert_eq!(m.iter().flatten().filter(|p| **p).count(), line.iter().filter(|p| **p).count());
+    }
+
+    // Dots are shorter than dashes
+    let count = |c: char| glyph_mask(c)[CELL_HEIGHT / 2].iter().filter(|p| **p).count();
+    assert!(count('┈') < count('╌'));
+}
+
+#[test]
+fn glyph_mask_test_5() {
+    // Truncated labels end in an ellipsis, which has three dots along the bottom
+    let m = glyph_mask('…');
+    assert_ne!(m, glyph_mask('☃'));
+    assert!(m[..CELL_HEIGHT / 2].iter().flatten().all(|p| !*p));
+
+    let dots = m.iter().map(|row| row.iter().filter(|p| **p).count()).max().unwrap();
+    assert_eq!(dots, 6);
+}
@@ -0,0 +1,141 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::{axes::{axis_ticks, Ticks}, scale::*},
+    plots::{
+        function_plot::function_plot,
+        line_plot::line_plot,
+        scatter_plot::scatter_plot,
+        traits::{Plot, ScaleOption, SvgPlot},
+    },
+};
+
+#[test]
+fn scale_inverse_test_1() {
+    let scales = [Scale::Linear, Scale::Log10, Scale::Log2, Scale::Symlog(1.), Scale::Symlog(0.01)];
+
+    for scale in scales {
+        for v in [0.001, 0.5, 1., 3., 250., 1e6] {
+            let o = scale.inverse(scale.forward(v));
+            assert!((o - v).abs() <= 1e-9 * v, "{scale:?}: {v} became {o}");
+        }
+    }
+}
+
+#[test]
+fn scale_non_positive_test_1() {
+    assert!(Scale::Log10.forward(0.).is_nan());
+    assert!(Scale::Log2.forward(-4.).is_nan());
+    assert_eq!(Scale::Symlog(1.).forward(0.), 0.);
+    assert_eq!(Scale::Symlog(1.).forward(-99.), -2.);
+
+    let points = scale_points(vec![(1., 10.), (2., 0.), (3., -10.), (f64::NAN, 100.)].into_iter(), (Scale::Linear, Scale::Log10)).collect::<Vec<_>>();
+    assert_eq!(points.len(), 2);
+    assert_eq!(points[0], (1., 1.));
+    assert_eq!(points[1].1, 2.);
+}
+
+#[test]
+fn log_axis_ticks_test_1() {
+    // Ticks are placed in scaled space, but labelled with the original values
+    for (value, label) in axis_ticks(40, (0., 4.), false, Scale::Log10, &Ticks::Even) {
+        let n: f64 = label.parse().unwrap();
+        let expected = 10f64.powf(value);
+        assert!((n - expected).abs() <= 0.01 * expected, "{label} should be about {expected}");
+    }
+}
+
+#[test]
+fn linear_scale_test_1() {
+    let f = |x: f64| x * x - 2.;
+    let points: Vec<(f64, f64)> = (0..20).map(|i| (i as f64, (i as f64).sin())).collect();
+
+    assert_eq!(
+        function_plot(&f).set_x_scale(Scale::Linear).set_y_scale(Scale::Linear).as_string(),
+        function_plot(&f).as_string(),
+    );
+    assert_eq!(
+        scatter_plot(&points).set_y_scale(Scale::Linear).as_string(),
+        scatter_plot(&points).as_string(),
+    );
+    assert_eq!(
+        line_plot(&points).set_x_scale(Scale::Linear).as_svg(),
+        line_plot(&points).as_svg(),
+    );
+}
+
+#[test]
+fn log_scatter_plot_test_1() {
+    // Non-positive points are left out, rather than drawn or panicking
+    let points = vec![(1., 1.), (10., 100.), (100., 10000.)];
+    let with_invalid = vec![(1., 1.), (10., 100.), (100., 10000.), (0., 5.), (50., -3.)];
+
+    let plot = |p: &Vec<(f64, f64)>| scatter_plot(p).set_x_scale(Scale::Log10).set_y_scale(Scale::Log10).set_size((30, 10)).as_string();
+
+    println!("{}", plot(&points));
+    assert_eq!(plot(&points), plot(&with_invalid));
+
+    // Evenly spaced powers of 10 are evenly spaced on the plot
+    let rows: Vec<usize> = scatter_plot(&points)
+        .set_x_scale(Scale::Log10).set_y_scale(Scale::Log10).set_size((30, 9)).set_axes(false)
+        .as_string()
+        .split('\n')
+        .enumerate()
+        .filter(|(_, l)| l.trim() != "")
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(rows.len(), 3);
+    assert_eq!(rows[1] - rows[0], rows[2] - rows[1]);
+}
+
+#[test]
+fn log_function_plot_test_1() {
+    let f = |x: f64| x;
+
+    // A straight line on a log-log plot, which only has its positive half drawn
+    let full = function_plot(&f).set_domain((-10., 10.)).set_y_scale(Scale::Log10).set_size((40, 10)).set_axes(false).as_string();
+    println!("{full}");
+
+    let left_half: String = full.split('\n').map(|l| l.chars().take(18).collect::<String>()).collect();
+    assert!(left_half.trim().is_empty());
+
+    // A non-positive domain falls back to a computed one
+    let o = function_plot(&f).set_domain((-1., 10.)).set_x_scale(Scale::Log2).as_string();
+    assert!(!o.trim().is_empty());
+}
+
+#[test]
+fn log_line_plot_test_1() {
+    let points: Vec<(f64, f64)> = (1..10).map(|i| (i as f64, 3f64.powi(i))).collect();
+    let o = line_plot(&points).set_y_scale(Scale::Log10).set_size((30, 10)).as_string();
+    println!("{o}");
+
+    // No points can be shown, but it still plots
+    let negative = vec![(0., -1.), (1., -2.)];
+    line_plot(&negative).set_y_scale(Scale::Log2).as_string();
+}
+
+#[test]
+fn symlog_threshold_test_1() {
+    // A symlog threshold which isn't positive falls back to a linear scale, instead of NaN positions and labels
+    assert_eq!(Scale::Symlog(2.).validated(), Scale::Symlog(2.));
+
+    let f = |x: f64| x * x - 2.;
+    let points: Vec<(f64, f64)> = (0..20).map(|i| (i as f64 - 10., (i as f64).sin())).collect();
+
+    for c in [0., -1., f64::NAN, f64::INFINITY] {
+        assert_eq!(Scale::Symlog(c).validated(), Scale::Linear);
+
+        let o = function_plot(&f).set_y_scale(Scale::Symlog(c)).as_string();
+        assert!(!o.contains("NaN"));
+        assert_eq!(o, function_plot(&f).as_string());
+
+        assert_eq!(
+            scatter_plot(&points).set_x_scale(Scale::Symlog(c)).as_string(),
+            scatter_plot(&points).as_string(),
+        );
+        assert_eq!(
+            line_plot(&points).set_x_scale(Scale::Symlog(c)).as_string(),
+            line_plot(&points).as_string(),
+        );
+    }
+}
@@ -116,11 +116,11 @@ fn scatter_plot_test_1() {
         .set_axes(false)
         .as_string();
     let exp = String::from(
-"●    
+"    ●
      
   ●  
      
-    ●");
+●    ");
 
     println!("{res}");
 
@@ -135,7 +135,7 @@ fn scatter_plot_test_2() {
         .set_axes(false)
         .as_string();
     let exp = String::from(
-"▗         
+"         ▖
           
           
           
@@ -144,7 +144,7 @@ fn scatter_plot_test_2() {
           
           
           
-         ▘");
+▝         ");
 
     println!("{res}");
 
@@ -158,7 +158,7 @@ fn scatter_plot_test_3() {
         .set_size((1, 1))
         .set_axes(false)
         .as_string();
-    let exp = String::from("⢣");
+    let exp = String::from("⡜");
 
     println!("{res}");
 
@@ -178,7 +178,7 @@ fn scatter_plot_test_4() {
 
 #[test]
 fn scatter_plot_test_5() {
-    let pts = (0..20).flat_map(|i| (0..20).map(move |j| (i, j))).collect();
+    let pts: Vec<(i32, i32)> = (0..20).flat_map(|i| (0..20).map(move |j| (i, j))).collect();
     let res = scatter_plot(&pts)
         .set_size((5, 5))
         .set_axes(false)
@@ -199,7 +199,7 @@ fn scatter_plot_test_5() {
 
 #[test]
 fn scatter_plot_test_6() {
-    let pts = (0..100).flat_map(|i| (0..100).map(move |j| (i, j))).collect();
+    let pts: Vec<(i32, i32)> = (0..100).flat_map(|i| (0..100).map(move |j| (i, j))).collect();
     let res = scatter_plot(&pts)
         .set_size((5, 5))
         .set_axes(false)
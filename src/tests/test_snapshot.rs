@@ -0,0 +1,65 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    assert_plot_snapshot,
+    helper::{color::Color, file::get_current_dir, snapshot::*},
+    plots::{array_plot::array_plot, function_plot::function_plot, traits::Plot},
+};
+
+#[test]
+fn snapshot_diff_test_1() {
+    // Differing lines are marked under each changed charachter, including extra lines
+    let diff = snapshot_diff("abc\ndef", "abd\ndef\ng").unwrap();
+    println!("{diff}");
+
+    assert_eq!(diff, "line 1:\n  expected: abc\n  actual:   abd\n              ^\nline 3:\n  expected: <no line>\n  actual:   g\n            ^\n(2 of 3 lines differ)");
+}
+
+#[test]
+fn snapshot_diff_test_2() {
+    // Color changes are marked, but only visible charachters are shown
+    let diff = snapshot_diff("ab", &format!("a{}", Color::RED.paint("b"))).unwrap();
+    println!("{diff}");
+
+    assert!(diff.contains("actual:   ab\n             ^"));
+}
+
+#[test]
+fn check_snapshot_test_1() {
+    // Missing snapshots fail unless updating, and updated snapshots then match
+    let path = get_current_dir() + "testoutput/snapshots/check_snapshot_test_1.txt";
+    let _ = std::fs::remove_file(&path);
+
+    assert!(check_snapshot("a\nb", &path, false).unwrap_err().contains(UPDATE_SNAPSHOTS_VAR));
+    assert!(check_snapshot("a\nb", &path, true).is_ok());
+    assert!(check_snapshot("a\nb", &path, false).is_ok());
+    assert!(check_snapshot("a\nc", &path, false).unwrap_err().contains("line 2:"));
+}
+
+#[test]
+fn check_snapshot_test_2() {
+    // Snapshots with CRLF line endings still match
+    let path = get_current_dir() + "testoutput/snapshots/check_snapshot_test_2.txt";
+    std::fs::create_dir_all(get_current_dir() + "testoutput/snapshots").unwrap();
+    std::fs::write(&path, "a\r\nb\r\n").unwrap();
+
+    assert!(check_snapshot("a\nb", &path, false).is_ok());
+    assert!(check_snapshot("a\nb\n", &path, false).is_ok());
+    assert!(check_snapshot("a\nb\n\n", &path, false).is_err());
+}
+
+#[test]
+fn assert_plot_snapshot_test_1() {
+    let data = vec![vec![0, 1, 2], vec![2, 1, 0]];
+    assert_plot_snapshot!(array_plot(&data), "src/tests/snapshots/array_plot_1.txt");
+
+    let f = |x: f64| x * x;
+    let plot: Box<dyn Plot> = Box::new(function_plot(&f).set_domain((-1., 1.)).set_size((30, 8)).clone());
+    assert_plot_snapshot!(plot, "src/tests/snapshots/function_plot_1.txt");
+}
+
+#[test]
+#[should_panic(expected = "does not match")]
+fn assert_plot_snapshot_test_2() {
+    let data = vec![vec![2, 1, 0], vec![0, 1, 2]];
+    assert_plot_snapshot!(array_plot(&data), "src/tests/snapshots/array_plot_1.txt");
+}
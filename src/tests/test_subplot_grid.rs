@@ -0,0 +1,56 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::plots::{subplot_grid::*, array_plot::array_plot, traits::Plot};
+
+#[test]
+fn subplot_grid_test_1() {
+    // Cells are padded to the widest plot in their column
+    let a = vec![vec![0, 1, 2]];
+    let b = vec![vec![2], vec![0]];
+
+    let o = subplot_grid()
+        .set_columns(2)
+        .set_gutter((1, 0))
+        .add(array_plot(&a).set_axes(false))
+        .add(array_plot(&b).set_axes(false))
+        .add(array_plot(&b).set_axes(false))
+        .as_string();
+
+    println!("{o}");
+
+    assert_eq!(o, " ▒█ █\n\n█\n");
+}
+
+#[test]
+fn subplot_grid_test_2() {
+    // Cell titles, row gutters, and an overall title
+    let a = vec![vec![0, 1]];
+
+    let o = subplot_grid()
+        .set_columns(1)
+        .set_gutter((0, 1))
+        .set_title("grid")
+        .add_titled(array_plot(&a).set_axes(false), "a")
+        .add_titled(array_plot(&a).set_axes(false), "b")
+        .as_string();
+
+    println!("{o}");
+
+    assert_eq!(o, "grid\na\n █\n\nb\n █");
+}
+
+#[test]
+fn subplot_grid_test_3() {
+    // Boxed plots can be added directly
+    let a = vec![vec![0, 1]];
+    let plots: Vec<Box<dyn Plot + '_>> = vec![
+        Box::new(array_plot(&a).set_axes(false).clone()),
+        Box::new(array_plot(&a).set_axes(false).clone()),
+    ];
+
+    let mut grid = subplot_grid();
+    for plot in plots.iter() {
+        grid.add(plot);
+    }
+
+    assert_eq!(grid.as_string(), " █     █");
+}
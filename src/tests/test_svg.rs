@@ -0,0 +1,214 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::{
+        axes::{axis_ticks, AxesConfig, Ticks},
+        scale::Scale,
+        color::Color,
+        svg::*,
+    },
+    plots::{
+        array_plot::array_plot,
+        figure_plot::figure_plot,
+        function_plot::function_plot,
+        region_plot::region_plot,
+        scatter_plot::scatter_plot,
+        traits::SvgPlot,
+    },
+};
+
+#[test]
+fn svg_canvas_test_1() {
+    // A NaN point splits the line in two
+    let mut canvas = SvgCanvas::new((10, 5), ((0., 10.), (0., 10.)));
+    canvas.polyline(&[(0., 0.), (1., 1.), (2., f64::NAN), (3., 3.), (4., 4.)], Color::Default);
+    let svg = canvas.finish(None, None);
+
+    assert_eq!(svg.matches("<polyline").count(), 2);
+    assert!(svg.contains(r#"<polyline points="0.00,100.00 10.00,90.00""#));
+}
+
+#[test]
+fn svg_canvas_test_2() {
+    // Neighbouring cells of the same color are merged
+    let mut canvas = SvgCanvas::new((4, 1), ((0., 4.), (0., 1.)));
+    canvas.cells(&[vec![Some(Color::RED), Some(Color::RED), None, Some(Color::BLUE)]]);
+    let svg = canvas.finish(None, None);
+
+    assert_eq!(svg.matches("<rect x=").count(), 2);
+    assert!(svg.contains(r#"<rect x="0.00" y="0.00" width="20.00" height="20.00" fill="rgb(205,0,0)"/>"#));
+    assert!(svg.contains(r#"<rect x="30.00" y="0.00" width="10.00" height="20.00" fill="rgb(0,0,238)"/>"#));
+}
+
+#[test]
+fn svg_canvas_test_3() {
+    let canvas = SvgCanvas::new((10, 5), ((0., 1.), (0., 1.)));
+    let svg = canvas.finish(None, Some("a < b & c"));
+
+    assert!(svg.contains(">a &lt; b &amp; c</text>"));
+}
+
+#[test]
+fn function_plot_svg_test_1() {
+    let f = |x: f64| x * x;
+    let mut plot = function_plot(&f);
+    plot.set_domain((-1., 1.)).set_range((0., 1.)).set_domain_padding(0.).set_range_padding(0.).set_size((30, 10)).set_title("Square");
+    let svg = plot.as_svg();
+
+    assert!(svg.starts_with("<svg"));
+    assert!(svg.ends_with("</svg>"));
+    assert_eq!(svg.matches("<polyline").count(), 1);
+    assert!(svg.contains(">Square</text>"));
+
+    // Labels are the same as the text plot's
+    let text = plot.as_string();
+    for (_, label) in axis_ticks(30, (-1., 1.), false, Scale::Linear, &Ticks::Even).into_iter().chain(axis_ticks(10, (0., 1.), true, Scale::Linear, &Ticks::Even)) {
+        assert!(text.contains(&label));
+        assert!(svg.contains(&format!(">{label}</text>")));
+    }
+}
+
+#[test]
+fn function_plot_svg_test_2() {
+    let f = |x: f64| x;
+    let svg = function_plot(&f).set_axes(false).as_svg();
+
+    assert!(!svg.contains("<text"));
+    assert!(!svg.contains("<line"));
+}
+
+#[test]
+fn scatter_plot_svg_test_1() {
+    let d: Vec<(f64, f64)> = (0..10).map(|x| (x as f64, (x * x) as f64)).collect();
+    let svg = scatter_plot(&d).set_color(Color::GREEN).as_svg();
+
+    assert_eq!(svg.matches("<circle").count(), 10);
+    assert_eq!(svg.matches(r#"fill="rgb(0,205,0)""#).count(), 10);
+}
+
+#[test]
+fn array_plot_svg_test_1() {
+    let d = vec![vec![0, 1, 2], vec![2, 1, 0]];
+    let svg = array_plot(&d).as_svg();
+
+    assert_eq!(svg.matches("<rect x=").count(), 6);
+    assert_eq!(svg.matches(r#"fill="rgb(0,0,0)""#).count(), 2);
+    assert_eq!(svg.matches(r#"fill="rgb(128,128,128)""#).count(), 2);
+}
+
+#[test]
+fn region_plot_svg_test_1() {
+    let p = |x: f64, _y: f64| x < 0.;
+    let svg = region_plot(&p).set_domain_and_range(((-1., 1.), (-1., 1.))).set_padding(0.).set_size((10, 5)).as_svg();
+
+    // The left half of every row is filled, as a single rectangle per row
+    assert_eq!(svg.matches("<rect x=").count(), 10);
+    assert!(svg.contains(r#"<rect x="0.00" y="0.00" width="50.00" height="10.00" fill="black"/>"#));
+}
+
+#[test]
+fn figure_plot_svg_test_1() {
+    let f = |x: f64| x;
+    let r = |x: f64, y: f64| x > y;
+    let svg = figure_plot()
+        .add_function(&f).set_label("line")
+        .add_region(&r)
+        .add_scatter(&[(0., 0.), (1., 1.)]).set_label("points").set_color(Color::RED)
+        .set_domain((-1., 1.))
+        .set_range((-1., 1.))
+        .as_svg();
+
+    assert_eq!(svg.matches("<polyline").count(), 1);
+    assert_eq!(svg.matches("<circle").count(), 2);
+    assert!(svg.contains(">line</text>"));
+    assert!(svg.contains(">points</text>"));
+}
+
+#[test]
+fn svg_canvas_test_4() {
+    let canvas = SvgCanvas::new((20, 5), ((-0.5, 2.5), (0., 1.)));
+
+    // Short labels are drawn horizontally
+    let config = AxesConfig {x_ticks: Ticks::categories(["a", "b", "c"]), ..Default::default()};
+    let svg = canvas.finish(Some(&config), None);
+    assert!(svg.contains(">a</text>") && !svg.contains("rotate("));
+
+    // Labels which would overlap are drawn vertically, and the image is taller to fit them
+    let long = AxesConfig {x_ticks: Ticks::categories(["first-experiment", "second-experiment", "third"]), ..Default::default()};
+    let long_svg = canvas.finish(Some(&long), None);
+    println!("{long_svg}");
+    assert_eq!(long_svg.matches("rotate(90").count(), 3);
+
+    let height = |s: &str| s.split("height=\"").nth(1).unwrap().split('"').next().unwrap().parse::<f64>().unwrap();
+    assert!(height(&long_svg) > height(&svg));
+}
+
+#[test]
+fn svg_canvas_test_5() {
+    let mut canvas = SvgCanvas::new((20, 5), ((0., 1.), (0., 1.)));
+    let plain = canvas.finish(Some(&AxesConfig::default()), None);
+
+    // Axis titles and a caption each make room for themselves
+    let config = AxesConfig {x_label: Some("time".to_string()), y_label: Some("a < b".to_string()), ..Default::default()};
+    canvas.caption("Source: test\nSecond line");
+    let svg = canvas.finish(Some(&config), None);
+    println!("{svg}");
+
+    assert!(svg.contains(">time</text>"));
+    assert!(svg.contains("rotate(-90") && svg.contains(">a &lt; b</text>"));
+    assert!(svg.contains(">Source: test</text>") && svg.contains(">Second line</text>"));
+
+    let size = |s: &str, attr: &str| s.split(&format!("{attr}=\"")).nth(1).unwrap().split('"').next().unwrap().parse::<f64>().unwrap();
+    assert!(size(&svg, "width") > size(&plain, "width"));
+    assert!(size(&svg, "height") > size(&plain, "height"));
+}
+
+#[test]
+fn svg_canvas_test_6() {
+    let mut canvas = SvgCanvas::new((20, 5), ((-1., 1.), (-1., 1.)));
+    canvas.polyline(&[(-1., -1.), (1., 1.)], Color::RED);
+
+    let config = AxesConfig {grid: true, x_lines: vec![0., 5.], y_lines: vec![0.], ..Default::default()};
+    let svg = canvas.finish(Some(&config), None);
+    println!("{svg}");
+
+    // Gridlines and reference lines come before the data, so they are drawn underneath it
+    let first_data = svg.find("<polyline").unwrap();
+    assert!(svg.find("stroke-dasharray=\"2,2\"").unwrap() < first_data);
+    assert!(svg.rfind("stroke-dasharray=\"6,3\"").unwrap() < first_data);
+
+    // The line at x = 5 is outside of the plot, so it is clipped along with the data
+    assert_eq!(svg.matches("stroke-dasharray=\"6,3\"").count(), 3);
+    assert!(svg.contains(r#"<line x1="100.00" y1="0" x2="100.00" y2="100.00" stroke="gray""#));
+}
+
+#[test]
+fn svg_canvas_test_7() {
+    let canvas = SvgCanvas::new((20, 5), ((0., 1.), (0., 1.)));
+
+    let config = AxesConfig {secondary_range: Some((0., 100.)), secondary_ticks: Ticks::custom([(50., "half")]), secondary_label: Some("pct".to_string()), ..Default::default()};
+    let svg = canvas.finish(Some(&config), None);
+    println!("{svg}");
+
+    // The secondary axis is on the right edge of the plot area, with its title reading downwards
+    assert!(svg.contains(r#"<line x1="200.00" y1="0" x2="200.00" y2="100.00""#));
+    assert!(svg.contains(r#"y="54.20" fill="black">half</text>"#));
+    assert!(svg.contains("rotate(90") && svg.contains(">pct</text>"));
+}
+
+#[test]
+fn svg_clip_id_test_1() {
+    // Images of the same size clip to their own plot area, so they can be inlined in one page
+    let clip_id = |svg: &str| svg.split(r#"<clipPath id=""#).nth(1).unwrap().split('"').next().unwrap().to_string();
+
+    let f = |x: f64| x.sin();
+    let g = |x: f64| x.cos();
+    let a = function_plot(&f).set_size((30, 10)).as_svg();
+    let b = function_plot(&g).set_size((30, 10)).as_svg();
+
+    assert_ne!(clip_id(&a), clip_id(&b));
+    assert!(a.contains(&format!("url(#{})", clip_id(&a))));
+    assert!(!a.contains("<clip-id>"));
+
+    // The same plot always gives the same image
+    assert_eq!(a, function_plot(&f).set_size((30, 10)).as_svg());
+}
@@ -0,0 +1,82 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::{color::visible_len, terminal::*},
+    plots::{
+        function_plot::function_plot,
+        line_plot::line_plot,
+        region_plot::region_plot,
+        scatter_plot::scatter_plot,
+        traits::{Plot, SizeOption},
+    },
+};
+
+#[allow(dead_code)]
+fn assert_fits(o: &str, terminal: (u32, u32)) {
+    println!("{o}");
+
+    let width = o.split('\n').map(visible_len).max().unwrap_or(0);
+    let height = o.split('\n').count();
+
+    assert!(width <= terminal.0 as usize, "{width} columns doesn't fit in {terminal:?}");
+    assert!(height < terminal.1 as usize, "{height} lines doesn't fit in {terminal:?}");
+}
+
+#[test]
+fn fit_size_test_1() {
+    assert_eq!(fit_size((80, 24), false, false), (80, 23));
+    assert_eq!(fit_size((80, 24), false, true), (80, 22));
+    assert_eq!(fit_size((80, 24), true, true), (66, 20));
+}
+
+#[test]
+fn fit_size_test_2() {
+    // Tiny terminals still give a nonempty plot
+    assert_eq!(fit_size((3, 2), true, true), (1, 1));
+    assert_eq!(fit_size((0, 0), false, false), (1, 1));
+}
+
+#[test]
+fn fit_size_plots_test_1() {
+    // Plots with axes and titles fit in the terminal they were sized for
+    let f = |x: f64| x.sin() * 1000.;
+    let points: Vec<(f64, f64)> = (0..200).map(|i| (i as f64 * 0.37, (i as f64 * 0.1).cos())).collect();
+
+    for terminal in [(40, 12), (80, 24), (123, 41), (250, 70)] {
+        let size = fit_size(terminal, true, true);
+
+        assert_fits(&function_plot(&f).set_size(size).set_title("f").as_string(), terminal);
+        assert_fits(&line_plot(&points).set_size(size).set_title("line").as_string(), terminal);
+        assert_fits(&scatter_plot(&points).set_size(size).set_title("scatter").as_string(), terminal);
+        assert_fits(&region_plot(&|x: f64, y: f64| x > y).set_size(size).set_title("region").as_string(), terminal);
+    }
+}
+
+#[test]
+fn fit_terminal_test_1() {
+    // Without fit_terminal, the set size is always used
+    let f = |x: f64| x;
+
+    let o = function_plot(&f)
+        .set_size((20, 5))
+        .set_fit_terminal(false)
+        .set_axes(false)
+        .as_string();
+
+    assert_eq!(o.split('\n').count(), 5);
+    assert_eq!(visible_len(o.split('\n').next().unwrap()), 20);
+}
+
+#[test]
+fn fit_terminal_test_2() {
+    // With fit_terminal, the plot matches the terminal if its size is known
+    let f = |x: f64| x;
+    let mut plot = function_plot(&f);
+    SizeOption::set_fit_terminal(plot.set_size((20, 5)).set_axes(false), true);
+
+    let o = plot.as_string();
+
+    match terminal_size() {
+        Some(terminal) => assert_eq!(o.split('\n').count() as u32, fit_size(terminal, false, false).1),
+        None => assert_eq!(o.split('\n').count(), 5),
+    }
+}
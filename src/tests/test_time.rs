@@ -0,0 +1,84 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use std::time::{Duration, UNIX_EPOCH};
+
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::{
+    helper::{axes::{axis_ticks, Ticks}, scale::Scale, time::*},
+    plots::{
+        line_plot::line_plot,
+        scatter_plot::scatter_plot,
+        traits::{AxesOption, Plot},
+    },
+};
+
+/// 2026-03-03 14:05:00 UTC
+#[allow(dead_code)]
+const T0: f64 = 1_772_546_700.;
+
+/// Labels of time ticks on a horizontal axis n charachters long.
+#[allow(dead_code)]
+fn labels(n: usize, range: (f64, f64)) -> Vec<String> {
+    axis_ticks(n, range, false, Scale::Linear, &Ticks::Time).into_iter().map(|t| t.1).collect()
+}
+
+#[test]
+fn format_time_test_1() {
+    assert_eq!(format_time(0.), "1970-01-01 00:00:00");
+    assert_eq!(format_time(-1.), "1969-12-31 23:59:59");
+    assert_eq!(format_time(T0), "2026-03-03 14:05:00");
+    assert_eq!(format_time(1_709_208_000.), "2024-02-29 12:00:00");
+    assert_eq!(format_time(951_782_400.), "2000-02-29 00:00:00");
+
+    let before = UNIX_EPOCH - Duration::from_secs(86400);
+    assert_eq!(timestamp(before), -86400.);
+    assert_eq!(time_points(&[(before, 2.)]), vec![(-86400., 2.)]);
+}
+
+#[test]
+fn time_ticks_test_1() {
+    // Seconds
+    assert_eq!(labels(40, (T0, T0 + 90.)), vec!["14:05:00", "14:05:30", "14:06:00", "14:06:30"]);
+
+    // Hours, where midnight shows the date
+    assert_eq!(labels(60, (T0, T0 + 2. * 86400.)), vec!["18:00", "Mar 04", "06:00", "12:00", "18:00", "Mar 05", "06:00", "12:00"]);
+
+    // Days
+    assert_eq!(labels(60, (T0, T0 + 40. * 86400.)), vec!["Mar 08", "Mar 15", "Mar 22", "Apr 01", "Apr 08"]);
+
+    // Months, where January shows the year
+    assert_eq!(labels(60, (T0, T0 + 400. * 86400.)), vec!["Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec", "2027", "Feb", "Mar", "Apr"]);
+
+    // Years
+    assert_eq!(labels(60, (T0, T0 + 30. * 365.25 * 86400.)), vec!["2030", "2035", "2040", "2045", "2050", "2055"]);
+}
+
+#[test]
+fn time_ticks_test_2() {
+    // Ticks are on the charachter their time falls in
+    let ticks = axis_ticks(24, (0., 86400.), false, Scale::Linear, &Ticks::Time);
+    assert_eq!(ticks.iter().map(|t| t.1.as_str()).collect::<Vec<&str>>(), vec!["1970", "06:00", "12:00", "18:00", "Jan 02"]);
+    assert_eq!(ticks.iter().map(|t| t.0).collect::<Vec<f64>>(), vec![1800., 23400., 45000., 66600., 84600.]);
+
+    // Vertical axes only need a free charachter between ticks
+    let ticks = axis_ticks(10, (0., 86400.), true, Scale::Linear, &Ticks::Time);
+    assert_eq!(ticks.len(), 5);
+
+    // Ranges which are too long for any step fall back to even ticks
+    assert!(time_ticks(20, (0., f64::INFINITY), false, |_| 0).is_empty());
+    assert!(time_ticks(20, (0., 1e20), false, |t| (t / 5e18) as usize).is_empty());
+    assert!(!labels(20, (0., 1e20)).is_empty());
+}
+
+#[test]
+fn time_plots_test_1() {
+    let data: Vec<(f64, f64)> = (0..48).map(|i| (T0 + 3600. * i as f64, i as f64)).collect();
+
+    let o = line_plot(&data).set_size((60, 5)).set_x_ticks(Ticks::Time).as_string();
+    println!("{o}");
+    assert!(o.contains("Mar 04") && o.contains("12:00"));
+
+    let swapped: Vec<(f64, f64)> = data.iter().map(|(t, v)| (*v, *t)).collect();
+    let o = scatter_plot(&swapped).set_size((40, 10)).set_y_ticks(Ticks::Time).as_string();
+    println!("{o}");
+    assert!(o.contains("Mar 04 ┼"));
+}
@@ -0,0 +1,48 @@
+#[allow(unused_imports)] // imports are used, but doesn't detect it?
+use crate::plots::{
+    array_plot::array_plot,
+    function_plot::function_plot,
+    scatter_plot::scatter_plot,
+    traits::*,
+};
+
+#[test]
+fn plot_trait_test_1() {
+    let f = |x: f64| x * x;
+    let points = vec![(0., 1.), (2., 3.), (4., 0.)];
+    let data = vec![vec![0, 1, 2], vec![2, 1, 0]];
+
+    let plots: Vec<Box<dyn Plot + '_>> = vec![
+        Box::new(function_plot(&f).set_domain((-1., 1.)).set_size((20, 5)).clone()),
+        Box::new(scatter_plot(&points).set_size((10, 5)).clone()),
+        Box::new(array_plot(&data)),
+    ];
+
+    let expected = [
+        function_plot(&f).set_domain((-1., 1.)).set_size((20, 5)).as_string(),
+        scatter_plot(&points).set_size((10, 5)).as_string(),
+        array_plot(&data).as_string(),
+    ];
+
+    for (p, e) in plots.iter().zip(expected.iter()) {
+        assert_eq!(&p.as_string(), e);
+    }
+}
+
+#[test]
+fn option_trait_test_1() {
+    // Generic over any builder with the common options
+    fn configure<'a, P: TitleOption<'a> + AxesOption + SizeOption + Plot>(p: &mut P) -> String {
+        p.set_title("Generic").set_axes(false).set_size((8, 4));
+        p.as_string()
+    }
+
+    let points = vec![(0., 0.), (1., 1.)];
+    let mut builder = scatter_plot(&points);
+    let o = configure(&mut builder);
+
+    println!("{o}");
+
+    assert!(o.starts_with("Generic\n"));
+    assert_eq!(o.lines().count(), 5);
+}